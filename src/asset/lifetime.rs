@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use bevy_asset::{Asset, AssetEvent, AssetId, Assets};
+use bevy_ecs::{
+	event::EventReader,
+	system::{NonSendMut, Res, ResMut, Resource},
+};
+use miniquad::TextureId;
+
+use crate::prelude::material::Material;
+use crate::prelude::RenderingBackend;
+use crate::render::pipeline::GlPipeline;
+
+use super::Texture;
+
+/// An asset that owns a resource living on the GPU
+pub(crate) trait GpuAsset: Asset {
+	type Resource: Copy + Eq + Hash + Send + Sync + 'static;
+
+	/// The resource to free with the asset, `None` if the asset doesn't own it
+	fn resource(&self) -> Option<Self::Resource>;
+	fn free(backend: &mut RenderingBackend, resource: Self::Resource);
+}
+
+impl GpuAsset for Texture {
+	type Resource = TextureId;
+
+	fn resource(&self) -> Option<TextureId> {
+		self.is_owned().then(|| self.id())
+	}
+
+	fn free(backend: &mut RenderingBackend, resource: TextureId) {
+		backend.delete_texture(resource);
+	}
+}
+
+impl GpuAsset for Material {
+	type Resource = GlPipeline;
+
+	fn resource(&self) -> Option<GlPipeline> {
+		(!self.pipeline.is_default()).then_some(self.pipeline)
+	}
+
+	fn free(backend: &mut RenderingBackend, resource: GlPipeline) {
+		backend.delete_pipeline(resource);
+	}
+}
+
+/// GPU resources of all live assets of type `A`. Assets are already gone from [`Assets`] by the time they're removed,
+/// so their resources have to be remembered up front. Several assets can share a resource, which is only freed with the last of them
+#[derive(Resource)]
+pub(crate) struct GpuResources<A: GpuAsset> {
+	assets: HashMap<AssetId<A>, A::Resource>,
+	owners: HashMap<A::Resource, usize>,
+}
+
+impl<A: GpuAsset> Default for GpuResources<A> {
+	fn default() -> Self {
+		Self {
+			assets: HashMap::new(),
+			owners: HashMap::new(),
+		}
+	}
+}
+
+impl<A: GpuAsset> GpuResources<A> {
+	/// Remembers the resource owned by an asset, returning the one it owned before
+	fn insert(&mut self, id: AssetId<A>, resource: A::Resource) -> Option<A::Resource> {
+		*self.owners.entry(resource).or_default() += 1;
+		self.assets.insert(id, resource)
+	}
+
+	/// Forgets an asset, returning the resource it owned if no other asset owns it
+	fn remove(&mut self, id: AssetId<A>) -> Option<A::Resource> {
+		let resource = self.assets.remove(&id)?;
+		self.release(resource)
+	}
+
+	/// Drops one owner of `resource`, returning it once it has none left
+	fn release(&mut self, resource: A::Resource) -> Option<A::Resource> {
+		let owners = self.owners.get_mut(&resource)?;
+		*owners -= 1;
+
+		if *owners > 0 {
+			return None;
+		}
+
+		self.owners.remove(&resource);
+		Some(resource)
+	}
+}
+
+/// Frees GPU resources of removed or unused assets. Replacing an asset also frees its previous resource.
+///
+/// *Note: use [`Assets::remove_untracked`] to take an asset out without freeing it*
+pub(crate) fn free_gpu_resources<A: GpuAsset>(mut backend: NonSendMut<RenderingBackend>, mut resources: ResMut<GpuResources<A>>, mut events: EventReader<AssetEvent<A>>, assets: Res<Assets<A>>) {
+	for event in events.read() {
+		match *event {
+			AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+				let Some(asset) = assets.get(id) else {
+					continue;
+				};
+
+				let unused = match asset.resource() {
+					Some(current) => resources.insert(id, current).and_then(|previous| resources.release(previous)),
+					None => resources.remove(id),
+				};
+
+				if let Some(resource) = unused {
+					A::free(&mut backend, resource);
+				}
+			}
+			AssetEvent::Removed { id } | AssetEvent::Unused { id } => {
+				if let Some(resource) = resources.remove(id) {
+					A::free(&mut backend, resource);
+				}
+			}
+			AssetEvent::LoadedWithDependencies { .. } => {}
+		}
+	}
+}
@@ -0,0 +1,152 @@
+use std::sync::{Arc, Mutex};
+
+use bevy_asset::io::Reader;
+use bevy_asset::{AssetLoader, LoadContext};
+use bevy_ecs::system::{NonSendMut, Res, Resource};
+use image::RgbaImage;
+use miniquad::TextureId;
+
+use crate::prelude::RenderingBackend;
+
+use super::Texture;
+
+/// A decoded image waiting for its GPU texture
+struct PendingTexture {
+	image: RgbaImage,
+	sender: oneshot::Sender<TextureId>,
+}
+
+/// Images decoded by [`TextureLoader`], uploaded to GPU during [`MiniquadPrepareDraw`](crate::window::state::MiniquadPrepareDraw)
+#[derive(Resource, Clone, Default)]
+pub(crate) struct TextureUploadQueue(Arc<Mutex<Vec<PendingTexture>>>);
+
+#[derive(Debug)]
+pub enum TextureLoaderError {
+	/// Failed to read the image bytes
+	Io(std::io::Error),
+	/// Failed to decode the image, or the image format isn't enabled
+	Image(image::ImageError),
+	/// The image is larger than the `65535x65535` pixels a texture can hold
+	TooLarge { width: u32, height: u32 },
+	/// The decoding thread or the upload queue went away before the texture was created. Usually happens if the App was closed while loading
+	Cancelled,
+}
+
+impl std::fmt::Display for TextureLoaderError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io(err) => write!(f, "Failed to read image: {}", err),
+			Self::Image(err) => write!(f, "Failed to decode image: {}", err),
+			Self::TooLarge { width, height } => write!(f, "Image of {}x{} pixels is too large for a texture", width, height),
+			Self::Cancelled => write!(f, "Texture upload was cancelled"),
+		}
+	}
+}
+
+impl std::error::Error for TextureLoaderError {}
+
+impl From<std::io::Error> for TextureLoaderError {
+	fn from(value: std::io::Error) -> Self {
+		Self::Io(value)
+	}
+}
+
+impl From<image::ImageError> for TextureLoaderError {
+	fn from(value: image::ImageError) -> Self {
+		Self::Image(value)
+	}
+}
+
+/// Loads images as [`Texture`]s. Decoding happens on a separate thread, while the GPU upload is queued for [`MiniquadPrepareDraw`](crate::window::state::MiniquadPrepareDraw).
+/// The returned handle only resolves after the texture has been created on GPU.
+///
+/// *Note: on the Web, images are decoded on the main thread, as part of the loading task*
+///
+/// *Note: image codecs are opt-in, enable the `png`, `jpeg`, `bmp`, `tga` or `webp` features (or the respective `image` features) for the formats you need*
+pub struct TextureLoader {
+	uploads: TextureUploadQueue,
+	extensions: Vec<&'static str>,
+}
+
+impl AssetLoader for TextureLoader {
+	type Asset = Texture;
+	type Settings = ();
+	type Error = TextureLoaderError;
+
+	async fn load(&self, reader: &mut dyn Reader, _settings: &(), load_context: &mut LoadContext<'_>) -> Result<Texture, Self::Error> {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes).await?;
+
+		let format = load_context.path().extension().and_then(|ext| ext.to_str()).and_then(image::ImageFormat::from_extension);
+		let image = decode(bytes, format).await?;
+
+		let (sender, receiver) = oneshot::channel();
+		self.uploads.0.lock().unwrap().push(PendingTexture { image, sender });
+
+		match receiver.await {
+			Ok(texture) => Ok(Texture::new(texture)),
+			Err(_) => Err(TextureLoaderError::Cancelled),
+		}
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&self.extensions
+	}
+}
+
+/// Decodes on a separate thread, as loading tasks are ticked on the main thread
+#[cfg(not(target_arch = "wasm32"))]
+async fn decode(bytes: Vec<u8>, format: Option<image::ImageFormat>) -> Result<RgbaImage, TextureLoaderError> {
+	let (sender, receiver) = oneshot::channel();
+	std::thread::spawn(move || {
+		let _ = sender.send(decode_image(&bytes, format));
+	});
+
+	receiver.await.unwrap_or(Err(TextureLoaderError::Cancelled))
+}
+
+/// The browser has no threads to spare, so the image is decoded right on the loading task
+#[cfg(target_arch = "wasm32")]
+async fn decode(bytes: Vec<u8>, format: Option<image::ImageFormat>) -> Result<RgbaImage, TextureLoaderError> {
+	decode_image(&bytes, format)
+}
+
+/// Decodes an image in the given format, or guesses it from the bytes
+fn decode_image(bytes: &[u8], format: Option<image::ImageFormat>) -> Result<RgbaImage, TextureLoaderError> {
+	let image = match format {
+		Some(fmt) => image::load_from_memory_with_format(bytes, fmt)?,
+		None => image::load_from_memory(bytes)?,
+	};
+
+	if image.width() > u16::MAX as u32 || image.height() > u16::MAX as u32 {
+		return Err(TextureLoaderError::TooLarge {
+			width: image.width(),
+			height: image.height(),
+		});
+	}
+
+	Ok(image.into_rgba8())
+}
+
+impl TextureLoader {
+	pub(crate) fn new(uploads: TextureUploadQueue) -> Self {
+		// Codecs can also be enabled on the `image` crate directly, so check what's available at runtime
+		let extensions = image::ImageFormat::all().filter(|fmt| fmt.reading_enabled()).flat_map(|fmt| fmt.extensions_str()).copied().collect();
+
+		Self { uploads, extensions }
+	}
+}
+
+/// Pushes all decoded images to GPU, and notifies their loading tasks
+pub(crate) fn upload_textures(mut backend: NonSendMut<RenderingBackend>, uploads: Res<TextureUploadQueue>) {
+	let pending = std::mem::take(&mut *uploads.0.lock().unwrap());
+
+	for PendingTexture { image, sender } in pending {
+		let texture = backend.new_texture_from_rgba8(image.width() as u16, image.height() as u16, image.as_raw());
+
+		// The loading task is gone, so nobody will own this texture
+		if sender.send(texture).is_err() {
+			backend.delete_texture(texture);
+		}
+	}
+}
@@ -1,14 +1,18 @@
 use bevy_app::Plugin;
-use bevy_asset::{Asset, AssetApp, Assets};
-use bevy_asset::{AssetLoader, AssetPlugin as BevyAssetPlugin};
+use bevy_asset::{Asset, AssetApp, AssetMetaCheck, AssetPlugin as BevyAssetPlugin};
 use bevy_reflect::Reflect;
-use miniquad::{ShaderMeta, ShaderSource, TextureId};
+use bevy_tasks::{IoTaskPool, TaskPool};
+use miniquad::TextureId;
 
 use crate::prelude::material::Material;
 use crate::prelude::Mesh;
+use crate::window::state::MiniquadPrepareDraw;
 
 pub mod io;
+pub(crate) mod lifetime;
+pub mod loader;
 pub use io::*;
+pub use loader::{TextureLoader, TextureLoaderError};
 
 // ? I'm using Option here to workaround rendering types not implementing Default trait. If there's a better way
 // ? of course - it would be great!
@@ -17,11 +21,43 @@ pub use io::*;
 pub struct Texture {
 	#[reflect(ignore)]
 	texture: Option<TextureId>,
+	/// Whether the GPU texture is freed along with the asset
+	#[reflect(ignore)]
+	owned: bool,
+	/// Whether rows are stored bottom first, like in render targets
+	#[reflect(ignore)]
+	upside_down: bool,
 }
 
 impl Texture {
 	pub fn new(texture: TextureId) -> Self {
-		Self { texture: Some(texture) }
+		Self {
+			texture: Some(texture),
+			owned: true,
+			upside_down: false,
+		}
+	}
+
+	/// Wraps a texture owned by something else, like a [`RenderTarget`](crate::prelude::RenderTarget). It isn't freed along with the asset
+	pub fn borrowed(texture: TextureId) -> Self {
+		Self {
+			texture: Some(texture),
+			owned: false,
+			upside_down: false,
+		}
+	}
+
+	/// Marks the texture's rows as stored bottom first, so sprites sample it flipped and draw it upright
+	pub fn upside_down(self) -> Self {
+		Self { upside_down: true, ..self }
+	}
+
+	pub fn is_owned(&self) -> bool {
+		self.owned
+	}
+
+	pub fn is_upside_down(&self) -> bool {
+		self.upside_down
 	}
 
 	pub fn id(&self) -> TextureId {
@@ -30,16 +66,65 @@ impl Texture {
 	}
 }
 
-// pub struct AssetPlugin;
-
-// impl Plugin for AssetPlugin {
-// 	fn build(&self, app: &mut bevy_app::App) {
-// 		app.add_plugins(BevyAssetPlugin::default())
-// 			.register_asset_reflect::<Mesh>()
-// 			.init_resource::<Assets<Mesh>>()
-// 			.register_asset_reflect::<Texture>()
-// 			.init_resource::<Assets<Texture>>()
-// 			.register_asset_reflect::<Material>()
-// 			.init_resource::<Assets<Material>>();
-// 	}
-// }
+/// Sets up [`bevy_asset`] and registers quadify's rendering types ([`Mesh`], [`Texture`] and [`Material`]) as reflected assets.
+pub struct AssetPlugin {
+	/// Path to the assets folder. Relative to the executable's directory on Desktop, and to the page on Web
+	pub file_path: String,
+	/// Whether to look for `.meta` files next to loaded assets. Defaults to [`AssetMetaCheck::Never`], as every check is an extra request on the Web
+	pub meta_check: AssetMetaCheck,
+}
+
+impl Default for AssetPlugin {
+	fn default() -> Self {
+		Self {
+			file_path: "assets".to_string(),
+			meta_check: AssetMetaCheck::Never,
+		}
+	}
+}
+
+impl Plugin for AssetPlugin {
+	fn build(&self, app: &mut bevy_app::App) {
+		// AssetServer spawns its loading tasks on the IoTaskPool
+		IoTaskPool::get_or_init(TaskPool::default);
+
+		app.add_plugins(BevyAssetPlugin {
+			file_path: self.file_path.clone(),
+			meta_check: self.meta_check.clone(),
+			..Default::default()
+		})
+		.init_asset::<Mesh>()
+		.init_asset::<Texture>()
+		.init_asset::<Material>()
+		.register_asset_reflect::<Mesh>()
+		.register_asset_reflect::<Texture>()
+		.register_asset_reflect::<Material>();
+
+		let uploads = loader::TextureUploadQueue::default();
+		app.insert_resource(uploads.clone())
+			.register_asset_loader(TextureLoader::new(uploads))
+			.add_systems(MiniquadPrepareDraw, loader::upload_textures);
+
+		// Free GPU memory of dropped assets
+		app.init_resource::<lifetime::GpuResources<Texture>>()
+			.init_resource::<lifetime::GpuResources<Material>>()
+			.add_systems(MiniquadPrepareDraw, (lifetime::free_gpu_resources::<Texture>, lifetime::free_gpu_resources::<Material>));
+
+		#[cfg(not(target_arch = "wasm32"))]
+		app.add_systems(bevy_app::Last, tick_io_task_pool);
+	}
+}
+
+/// The miniquad runner never yields to an async executor, so pending loading tasks are advanced once per frame.
+/// Heavy work like image decoding is handed off to other threads, tasks only wait for it here.
+/// On the Web, tasks are spawned directly onto the browser's event loop instead.
+#[cfg(not(target_arch = "wasm32"))]
+fn tick_io_task_pool() {
+	IoTaskPool::get().with_local_executor(|executor| {
+		for _ in 0..100 {
+			if !executor.try_tick() {
+				break;
+			}
+		}
+	});
+}
@@ -0,0 +1,298 @@
+use std::time::Duration;
+
+use super::AudioSource;
+
+/// Identifies a sound playing on the [`Mixer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VoiceId(u64);
+
+/// Groups of sounds sharing a volume. Every bus other than [`AudioBus::Master`] also goes through the master bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AudioBus {
+	#[default]
+	Master,
+	Music,
+	Sfx,
+	Ui,
+}
+
+impl AudioBus {
+	pub const ALL: [AudioBus; 4] = [AudioBus::Master, AudioBus::Music, AudioBus::Sfx, AudioBus::Ui];
+}
+
+/// Volume of an [`AudioBus`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bus {
+	pub volume: f32,
+	pub muted: bool,
+}
+
+impl Default for Bus {
+	fn default() -> Self {
+		Self { volume: 1.0, muted: false }
+	}
+}
+
+impl Bus {
+	fn gain(&self) -> f32 {
+		if self.muted {
+			0.0
+		} else {
+			self.volume
+		}
+	}
+}
+
+/// Playback parameters of a single voice, can be changed while it's playing
+#[derive(Debug, Clone, Copy)]
+pub struct VoiceSettings {
+	pub volume: f32,
+	pub pitch: f32,
+	pub looping: bool,
+	pub paused: bool,
+	pub bus: AudioBus,
+	/// Stereo balance, `-1.0` only plays on the left side and `1.0` only on the right
+	pub pan: f32,
+}
+
+impl Default for VoiceSettings {
+	fn default() -> Self {
+		Self {
+			volume: 1.0,
+			pitch: 1.0,
+			looping: false,
+			paused: false,
+			bus: AudioBus::Master,
+			pan: 0.0,
+		}
+	}
+}
+
+/// A volume ramp towards `target`, applied on top of the voice's volume
+#[derive(Debug, Clone, Copy)]
+struct Fade {
+	target: f32,
+	/// Change of the envelope per output frame
+	step: f32,
+	/// Stop the voice once the target is reached
+	stop: bool,
+}
+
+/// A playing instance of an [`AudioSource`]
+struct Voice {
+	id: VoiceId,
+	source: AudioSource,
+	/// Position in source frames, fractional because of resampling and pitch
+	position: f64,
+	settings: VoiceSettings,
+	envelope: f32,
+	fade: Option<Fade>,
+}
+
+/// Mixes every playing sound into a single stereo stream, at the output's sample rate.
+/// Sources recorded at other rates are resampled with linear interpolation.
+///
+/// It's shared between the ECS and the output backend through [`AudioMixer`](super::AudioMixer), but can be used on its own to render mixes offline.
+/// Rendering is deterministic, the same calls always produce the same samples
+pub struct Mixer {
+	sample_rate: u32,
+	voices: Vec<Voice>,
+	buses: [Bus; AudioBus::ALL.len()],
+	next_id: u64,
+}
+
+impl Mixer {
+	/// Amount of interleaved channels in the mixed stream
+	pub const CHANNELS: usize = 2;
+
+	pub fn new(sample_rate: u32) -> Self {
+		Self {
+			sample_rate,
+			voices: Vec::new(),
+			buses: Default::default(),
+			next_id: 0,
+		}
+	}
+
+	/// Sample rate of the mixed stream
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Amount of sounds currently playing, including paused ones
+	pub fn voices(&self) -> usize {
+		self.voices.len()
+	}
+
+	#[cfg(feature = "audio_output")]
+	pub(crate) fn set_sample_rate(&mut self, sample_rate: u32) {
+		self.sample_rate = sample_rate;
+	}
+
+	pub fn bus(&self, bus: AudioBus) -> &Bus {
+		&self.buses[bus as usize]
+	}
+
+	pub fn bus_mut(&mut self, bus: AudioBus) -> &mut Bus {
+		&mut self.buses[bus as usize]
+	}
+
+	/// Starts playing `source` from the beginning
+	pub fn play(&mut self, source: &AudioSource, settings: VoiceSettings) -> VoiceId {
+		let id = VoiceId(self.next_id);
+		self.next_id += 1;
+
+		self.voices.push(Voice {
+			id,
+			source: source.clone(),
+			position: 0.0,
+			settings,
+			envelope: 1.0,
+			fade: None,
+		});
+		id
+	}
+
+	/// Settings of a playing voice, `None` once it finished or was stopped
+	pub fn settings(&self, id: VoiceId) -> Option<&VoiceSettings> {
+		self.voices.iter().find(|voice| voice.id == id).map(|voice| &voice.settings)
+	}
+
+	pub fn settings_mut(&mut self, id: VoiceId) -> Option<&mut VoiceSettings> {
+		self.voice_mut(id).map(|voice| &mut voice.settings)
+	}
+
+	/// Whether the voice is still playing or paused
+	pub fn contains(&self, id: VoiceId) -> bool {
+		self.voices.iter().any(|voice| voice.id == id)
+	}
+
+	pub fn stop(&mut self, id: VoiceId) {
+		self.voices.retain(|voice| voice.id != id);
+	}
+
+	/// Fades the voice in from silence over `duration`
+	pub fn fade_in(&mut self, id: VoiceId, duration: Duration) {
+		let frames = self.duration_frames(duration);
+		if let Some(voice) = self.voice_mut(id) {
+			voice.envelope = 0.0;
+			voice.fade = Some(Fade {
+				target: 1.0,
+				step: 1.0 / frames,
+				stop: false,
+			});
+		}
+	}
+
+	/// Fades the voice out to silence over `duration`, then stops it
+	pub fn fade_out(&mut self, id: VoiceId, duration: Duration) {
+		let frames = self.duration_frames(duration);
+		if let Some(voice) = self.voice_mut(id) {
+			voice.fade = Some(Fade {
+				target: 0.0,
+				step: voice.envelope / frames,
+				stop: true,
+			});
+		}
+	}
+
+	/// Renders `frames` of interleaved stereo samples, advancing playback
+	pub fn render(&mut self, frames: usize) -> Vec<f32> {
+		let mut out = vec![0.0; frames * Self::CHANNELS];
+		self.render_into(&mut out);
+		out
+	}
+
+	/// Fills `out` with interleaved stereo samples, and drops voices that finished playing
+	pub fn render_into(&mut self, out: &mut [f32]) {
+		out.fill(0.0);
+
+		let sample_rate = self.sample_rate as f64;
+		let buses = self.buses;
+		let master = buses[AudioBus::Master as usize].gain();
+
+		self.voices.retain_mut(|voice| {
+			let bus = match voice.settings.bus {
+				AudioBus::Master => 1.0,
+				bus => buses[bus as usize].gain(),
+			};
+			voice.render(out, sample_rate, master * bus)
+		});
+	}
+
+	fn voice_mut(&mut self, id: VoiceId) -> Option<&mut Voice> {
+		self.voices.iter_mut().find(|voice| voice.id == id)
+	}
+
+	/// At least one frame, so fades never divide by zero
+	fn duration_frames(&self, duration: Duration) -> f32 {
+		(duration.as_secs_f64() * self.sample_rate as f64).max(1.0) as f32
+	}
+}
+
+impl Voice {
+	/// Adds this voice into `out`, returns `false` once it reached the end of a non-looping source or faded out
+	fn render(&mut self, out: &mut [f32], sample_rate: f64, gain: f32) -> bool {
+		if self.settings.paused {
+			return true;
+		}
+
+		let frames = self.source.frames();
+		if frames == 0 {
+			return false;
+		}
+
+		let channels = self.source.channels as usize;
+		let samples = &self.source.samples;
+		let step = self.settings.pitch.max(0.0) as f64 * self.source.sample_rate as f64 / sample_rate;
+		let VoiceSettings { volume, looping, pan, .. } = self.settings;
+		let pan = pan.clamp(-1.0, 1.0);
+		let balance = [(1.0 - pan).min(1.0), (1.0 + pan).min(1.0)];
+
+		// Mono sources play on both sides, extra channels past stereo are dropped
+		let sample = |frame: usize, channel: usize| samples[frame * channels + channel.min(channels - 1)];
+
+		for out in out.chunks_exact_mut(Mixer::CHANNELS) {
+			if self.position >= frames as f64 {
+				if !looping {
+					return false;
+				}
+				self.position %= frames as f64;
+			}
+
+			if let Some(fade) = self.fade {
+				self.envelope = if fade.target > self.envelope {
+					(self.envelope + fade.step).min(fade.target)
+				} else {
+					(self.envelope - fade.step).max(fade.target)
+				};
+
+				if self.envelope == fade.target {
+					self.fade = None;
+					if fade.stop {
+						return false;
+					}
+				}
+			}
+
+			// Linear interpolation between the two closest source frames
+			let frame = self.position as usize;
+			let t = (self.position - frame as f64) as f32;
+			let next = match frame + 1 {
+				next if next < frames => next,
+				_ if looping => 0,
+				_ => frame,
+			};
+
+			let gain = volume * gain * self.envelope;
+			for (channel, out) in out.iter_mut().enumerate() {
+				let (a, b) = (sample(frame, channel), sample(next, channel));
+				*out += (a + (b - a) * t) * gain * balance[channel];
+			}
+
+			self.position += step;
+		}
+
+		true
+	}
+}
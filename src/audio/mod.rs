@@ -0,0 +1,287 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{AssetApp, Assets, Handle};
+use bevy_ecs::{
+	component::Component,
+	entity::Entity,
+	query::{Changed, Without},
+	schedule::IntoSystemConfigs,
+	system::{Commands, Query, Res, Resource},
+};
+
+pub mod mixer;
+pub mod output;
+pub mod source;
+pub mod spatial;
+
+pub use mixer::*;
+pub use output::*;
+pub use source::*;
+pub use spatial::*;
+
+use crate::render::transform::Transform2D;
+
+/// Decodes [`AudioSource`]s, and plays every [`AudioPlayer`] through a shared [`Mixer`]
+pub struct AudioPlugin {
+	pub output: AudioOutput,
+	/// Sample rate of the mixer when there's no device, devices use their own rate
+	pub sample_rate: u32,
+}
+
+impl Default for AudioPlugin {
+	fn default() -> Self {
+		Self {
+			output: AudioOutput::default(),
+			sample_rate: 44100,
+		}
+	}
+}
+
+impl Plugin for AudioPlugin {
+	fn build(&self, app: &mut App) {
+		let mixer = AudioMixer(Arc::new(Mutex::new(Mixer::new(self.sample_rate))));
+
+		match self.output {
+			#[cfg(feature = "audio_output")]
+			AudioOutput::Device => match output::open_device(&mixer) {
+				Some(device) => {
+					app.insert_non_send_resource(device);
+				}
+				None => {
+					#[cfg(feature = "log")]
+					bevy_log::warn!("No audio output device available, audio will not be heard");
+					app.insert_resource(NullAudioOutput { mixer: mixer.clone() });
+				}
+			},
+			AudioOutput::Null => {
+				app.insert_resource(NullAudioOutput { mixer: mixer.clone() });
+			}
+		}
+
+		app.init_asset::<AudioSource>()
+			.init_asset_loader::<AudioLoader>()
+			.insert_resource(mixer)
+			.init_resource::<AudioListener2D>()
+			.add_systems(
+				PostUpdate,
+				(spatial::update_listener, play_audio, update_audio, spatial::update_spatial_audio, remove_finished_audio).chain(),
+			);
+	}
+}
+
+/// The [`Mixer`], shared between the ECS and the output backend
+#[derive(Resource, Clone)]
+pub struct AudioMixer(Arc<Mutex<Mixer>>);
+
+impl AudioMixer {
+	/// Locks the mixer. Keep it short, as the audio thread waits on it
+	pub fn lock(&self) -> MutexGuard<'_, Mixer> {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+/// Plays an [`AudioSource`] once it's loaded. Changing its fields while playing applies them to the sound
+#[derive(Debug, Clone, Component)]
+#[require(PlaybackSettings)]
+pub struct AudioPlayer {
+	pub source: Handle<AudioSource>,
+	pub volume: f32,
+	/// Restart from the beginning once the end is reached
+	pub looping: bool,
+	/// Playback speed, also shifting the pitch. `2.0` plays twice as fast, an octave higher
+	pub pitch: f32,
+	pub bus: AudioBus,
+}
+
+impl AudioPlayer {
+	pub fn new(source: Handle<AudioSource>) -> Self {
+		Self {
+			source,
+			volume: 1.0,
+			looping: false,
+			pitch: 1.0,
+			bus: AudioBus::Master,
+		}
+	}
+}
+
+/// What happens to the entity once its sound is done playing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaybackMode {
+	/// Keep everything, the [`AudioSink`] will report being empty
+	#[default]
+	Once,
+	/// Despawn the entity
+	Despawn,
+	/// Remove the [`AudioPlayer`], [`PlaybackSettings`] and [`AudioSink`] components
+	Remove,
+}
+
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub struct PlaybackSettings {
+	pub mode: PlaybackMode,
+	/// Start the sound paused, it can be resumed through its [`AudioSink`]
+	pub paused: bool,
+	/// Fade the sound in from silence when it starts
+	pub fade_in: Option<Duration>,
+}
+
+impl PlaybackSettings {
+	pub const ONCE: Self = Self {
+		mode: PlaybackMode::Once,
+		paused: false,
+		fade_in: None,
+	};
+	pub const DESPAWN: Self = Self {
+		mode: PlaybackMode::Despawn,
+		paused: false,
+		fade_in: None,
+	};
+	pub const REMOVE: Self = Self {
+		mode: PlaybackMode::Remove,
+		paused: false,
+		fade_in: None,
+	};
+}
+
+/// Controls a playing sound, inserted next to its [`AudioPlayer`] once playback starts. Removing it stops the sound
+#[derive(Component)]
+pub struct AudioSink {
+	voice: VoiceId,
+	mixer: AudioMixer,
+}
+
+impl AudioSink {
+	fn with_voice<T>(&self, f: impl FnOnce(&mut VoiceSettings) -> T) -> Option<T> {
+		self.mixer.lock().settings_mut(self.voice).map(f)
+	}
+
+	pub fn pause(&self) {
+		self.with_voice(|settings| settings.paused = true);
+	}
+
+	pub fn resume(&self) {
+		self.with_voice(|settings| settings.paused = false);
+	}
+
+	pub fn toggle(&self) {
+		self.with_voice(|settings| settings.paused = !settings.paused);
+	}
+
+	pub fn is_paused(&self) -> bool {
+		self.with_voice(|settings| settings.paused).unwrap_or(false)
+	}
+
+	/// Stops the sound for good, the sink becomes empty
+	pub fn stop(&self) {
+		self.mixer.lock().stop(self.voice);
+	}
+
+	/// Fades the sound in from silence over `duration`
+	pub fn fade_in(&self, duration: Duration) {
+		self.mixer.lock().fade_in(self.voice, duration);
+	}
+
+	/// Fades the sound out over `duration`, then stops it
+	pub fn fade_out(&self, duration: Duration) {
+		self.mixer.lock().fade_out(self.voice, duration);
+	}
+
+	/// Whether the sound finished playing or was stopped
+	pub fn empty(&self) -> bool {
+		!self.mixer.lock().contains(self.voice)
+	}
+
+	pub fn volume(&self) -> f32 {
+		self.with_voice(|settings| settings.volume).unwrap_or(0.0)
+	}
+
+	pub fn set_volume(&self, volume: f32) {
+		self.with_voice(|settings| settings.volume = volume);
+	}
+
+	pub fn pitch(&self) -> f32 {
+		self.with_voice(|settings| settings.pitch).unwrap_or(1.0)
+	}
+
+	pub fn set_pitch(&self, pitch: f32) {
+		self.with_voice(|settings| settings.pitch = pitch);
+	}
+}
+
+impl Drop for AudioSink {
+	fn drop(&mut self) {
+		self.stop();
+	}
+}
+
+/// Starts playing the [`AudioPlayer`]s whose sources finished loading
+#[allow(clippy::type_complexity)]
+fn play_audio(
+	mut commands: Commands,
+	players: Query<(Entity, &AudioPlayer, &PlaybackSettings, Option<(&AudioEmitter2D, &Transform2D)>), Without<AudioSink>>,
+	sources: Res<Assets<AudioSource>>,
+	mixer: Res<AudioMixer>,
+	listener: Res<AudioListener2D>,
+) {
+	for (entity, player, settings, emitter) in players.iter() {
+		let Some(source) = sources.get(&player.source) else {
+			continue;
+		};
+
+		// Positional sounds start at the right volume, instead of waiting a frame for their sink
+		let (attenuation, pan) = emitter.map_or((1.0, 0.0), |(emitter, transform)| emitter.spatialize(transform.translation.truncate(), &listener));
+
+		let voice = {
+			let mut mixer = mixer.lock();
+			let voice = mixer.play(
+				source,
+				VoiceSettings {
+					volume: player.volume * attenuation,
+					pitch: player.pitch,
+					looping: player.looping,
+					paused: settings.paused,
+					bus: player.bus,
+					pan,
+				},
+			);
+			if let Some(duration) = settings.fade_in {
+				mixer.fade_in(voice, duration);
+			}
+			voice
+		};
+
+		commands.entity(entity).insert(AudioSink { voice, mixer: mixer.clone() });
+	}
+}
+
+/// Applies changes of [`AudioPlayer`]s to their playing sounds
+fn update_audio(players: Query<(&AudioPlayer, &AudioSink), Changed<AudioPlayer>>) {
+	for (player, sink) in players.iter() {
+		sink.with_voice(|settings| {
+			settings.volume = player.volume;
+			settings.pitch = player.pitch;
+			settings.looping = player.looping;
+			settings.bus = player.bus;
+		});
+	}
+}
+
+/// Applies the [`PlaybackMode`] of sounds that are done playing
+fn remove_finished_audio(mut commands: Commands, sinks: Query<(Entity, &AudioSink, &PlaybackSettings)>) {
+	for (entity, sink, settings) in sinks.iter() {
+		if !sink.empty() {
+			continue;
+		}
+
+		match settings.mode {
+			PlaybackMode::Once => {}
+			PlaybackMode::Despawn => commands.entity(entity).despawn(),
+			PlaybackMode::Remove => {
+				commands.entity(entity).remove::<(AudioPlayer, PlaybackSettings, AudioSink)>();
+			}
+		}
+	}
+}
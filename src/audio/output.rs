@@ -0,0 +1,81 @@
+use bevy_ecs::system::Resource;
+
+use super::AudioMixer;
+
+/// Where the mixed stream of [`AudioPlugin`](super::AudioPlugin) goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioOutput {
+	/// The system's default output device, falls back to [`AudioOutput::Null`] if there's none
+	#[cfg(feature = "audio_output")]
+	#[default]
+	Device,
+	/// Nothing pulls from the mixer on its own, samples are rendered on demand through [`NullAudioOutput`]
+	#[cfg_attr(not(feature = "audio_output"), default)]
+	Null,
+}
+
+/// Inserted when audio isn't played on a device. Renders the mixer into a buffer instead, which is mostly useful for tests and headless apps
+#[derive(Resource, Clone)]
+pub struct NullAudioOutput {
+	pub(crate) mixer: AudioMixer,
+}
+
+impl NullAudioOutput {
+	/// Advances playback by `frames`, and returns the mixed interleaved stereo samples
+	pub fn render(&self, frames: usize) -> Vec<f32> {
+		self.mixer.lock().render(frames)
+	}
+}
+
+/// Keeps the device stream alive. Streams aren't [`Send`] on every platform, so this is a non-send resource
+#[cfg(feature = "audio_output")]
+pub(crate) struct AudioDevice {
+	_stream: cpal::Stream,
+}
+
+/// Opens the default output device, and starts pulling samples from `mixer` on the audio thread
+#[cfg(feature = "audio_output")]
+pub(crate) fn open_device(mixer: &AudioMixer) -> Option<AudioDevice> {
+	use super::Mixer;
+	use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+	let device = cpal::default_host().default_output_device()?;
+	let config: cpal::StreamConfig = device.default_output_config().ok()?.into();
+	let channels = config.channels as usize;
+
+	mixer.lock().set_sample_rate(config.sample_rate.0);
+
+	let stream_mixer = mixer.clone();
+	let mut buffer = Vec::new();
+	let stream = device
+		.build_output_stream(
+			&config,
+			move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+				let frames = data.len() / channels;
+				buffer.resize(frames * Mixer::CHANNELS, 0.0);
+				stream_mixer.lock().render_into(&mut buffer);
+
+				// Map the stereo mix onto the device's channel layout
+				for (out, mixed) in data.chunks_exact_mut(channels).zip(buffer.chunks_exact(Mixer::CHANNELS)) {
+					match out {
+						[mono] => *mono = ((mixed[0] + mixed[1]) * 0.5).clamp(-1.0, 1.0),
+						[left, right, rest @ ..] => {
+							*left = mixed[0].clamp(-1.0, 1.0);
+							*right = mixed[1].clamp(-1.0, 1.0);
+							rest.fill(0.0);
+						}
+						[] => {}
+					}
+				}
+			},
+			|_err| {
+				#[cfg(feature = "log")]
+				bevy_log::error!("Audio stream error: {}", _err);
+			},
+			None,
+		)
+		.ok()?;
+	stream.play().ok()?;
+
+	Some(AudioDevice { _stream: stream })
+}
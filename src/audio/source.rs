@@ -0,0 +1,153 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy_asset::io::Reader;
+use bevy_asset::{Asset, AssetLoader, LoadContext};
+use bevy_reflect::TypePath;
+
+/// A decoded sound, played by [`AudioPlayer`](super::AudioPlayer). Samples are stored interleaved, and are shared between all the voices playing it
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct AudioSource {
+	pub(crate) samples: Arc<[f32]>,
+	pub(crate) channels: u16,
+	pub(crate) sample_rate: u32,
+}
+
+impl AudioSource {
+	/// Creates a source from interleaved samples in the `-1.0..=1.0` range
+	pub fn from_samples(samples: impl Into<Arc<[f32]>>, channels: u16, sample_rate: u32) -> Self {
+		assert!(channels > 0, "Audio sources need at least one channel");
+		assert!(sample_rate > 0, "Audio sources need a non-zero sample rate");
+
+		Self {
+			samples: samples.into(),
+			channels,
+			sample_rate,
+		}
+	}
+
+	/// Decodes a WAV file, integer samples are normalized to `-1.0..=1.0`
+	pub fn from_wav(bytes: &[u8]) -> Result<Self, AudioLoaderError> {
+		let reader = hound::WavReader::new(Cursor::new(bytes))?;
+		let spec = reader.spec();
+
+		let samples = match spec.sample_format {
+			hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<Vec<_>, _>>()?,
+			hound::SampleFormat::Int => {
+				let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+				reader.into_samples::<i32>().map(|sample| sample.map(|sample| sample as f32 * scale)).collect::<Result<Vec<_>, _>>()?
+			}
+		};
+
+		Ok(Self::from_samples(samples, spec.channels, spec.sample_rate))
+	}
+
+	/// Decodes an OGG Vorbis file
+	pub fn from_ogg(bytes: &[u8]) -> Result<Self, AudioLoaderError> {
+		let mut reader = lewton::inside_ogg::OggStreamReader::new(Cursor::new(bytes))?;
+		let channels = reader.ident_hdr.audio_channels as u16;
+		let sample_rate = reader.ident_hdr.audio_sample_rate;
+
+		let mut samples = Vec::new();
+		while let Some(packet) = reader.read_dec_packet_itl()? {
+			samples.extend(packet.into_iter().map(|sample| sample as f32 / 32768.0));
+		}
+
+		Ok(Self::from_samples(samples, channels, sample_rate))
+	}
+
+	/// Amount of channels, samples are interleaved in this order
+	pub fn channels(&self) -> u16 {
+		self.channels
+	}
+
+	/// Sample rate the sound was recorded at. It's resampled to the output's rate while playing
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Amount of samples per channel
+	pub fn frames(&self) -> usize {
+		self.samples.len() / self.channels as usize
+	}
+
+	/// How long the sound plays for at normal pitch
+	pub fn duration(&self) -> Duration {
+		Duration::from_secs_f64(self.frames() as f64 / self.sample_rate as f64)
+	}
+
+	/// Interleaved samples of the sound
+	pub fn samples(&self) -> &[f32] {
+		&self.samples
+	}
+}
+
+#[derive(Debug)]
+pub enum AudioLoaderError {
+	/// Failed to read the file
+	Io(std::io::Error),
+	/// The file isn't a valid WAV file
+	Wav(hound::Error),
+	/// The file isn't a valid OGG Vorbis file
+	Ogg(lewton::VorbisError),
+	/// The file's extension isn't `.wav` or `.ogg`
+	UnsupportedFormat,
+}
+
+impl std::fmt::Display for AudioLoaderError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io(err) => write!(f, "Failed to read audio: {}", err),
+			Self::Wav(err) => write!(f, "Failed to decode WAV: {}", err),
+			Self::Ogg(err) => write!(f, "Failed to decode OGG: {}", err),
+			Self::UnsupportedFormat => write!(f, "Unsupported audio format"),
+		}
+	}
+}
+
+impl std::error::Error for AudioLoaderError {}
+
+impl From<std::io::Error> for AudioLoaderError {
+	fn from(value: std::io::Error) -> Self {
+		Self::Io(value)
+	}
+}
+
+impl From<hound::Error> for AudioLoaderError {
+	fn from(value: hound::Error) -> Self {
+		Self::Wav(value)
+	}
+}
+
+impl From<lewton::VorbisError> for AudioLoaderError {
+	fn from(value: lewton::VorbisError) -> Self {
+		Self::Ogg(value)
+	}
+}
+
+/// Loads `.wav` and `.ogg` files as [`AudioSource`]s
+#[derive(Default)]
+pub struct AudioLoader;
+
+impl AssetLoader for AudioLoader {
+	type Asset = AudioSource;
+	type Settings = ();
+	type Error = AudioLoaderError;
+
+	async fn load(&self, reader: &mut dyn Reader, _settings: &(), load_context: &mut LoadContext<'_>) -> Result<AudioSource, Self::Error> {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes).await?;
+
+		let extension = load_context.path().extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+		match extension.to_ascii_lowercase().as_str() {
+			"wav" => AudioSource::from_wav(&bytes),
+			"ogg" => AudioSource::from_ogg(&bytes),
+			_ => Err(AudioLoaderError::UnsupportedFormat),
+		}
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["wav", "ogg"]
+	}
+}
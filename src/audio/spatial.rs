@@ -0,0 +1,127 @@
+use bevy_ecs::{
+	component::Component,
+	system::{Query, Res, ResMut, Resource},
+};
+use glam::{vec4, Vec2};
+
+use super::{AudioPlayer, AudioSink};
+use crate::render::{
+	camera::{Camera2D, CurrentCameraTag},
+	transform::Transform2D,
+};
+
+/// How the volume of an [`AudioEmitter2D`] decreases with its distance to the listener
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Falloff {
+	/// Fades linearly from full volume at the reference distance, to silence at the max distance
+	Linear,
+	/// `reference / (reference + rolloff * (distance - reference))`, like real sound
+	Inverse { rolloff: f32 },
+	/// `(distance / reference) ^ -rolloff`
+	Exponential { rolloff: f32 },
+}
+
+impl Default for Falloff {
+	fn default() -> Self {
+		Self::Inverse { rolloff: 1.0 }
+	}
+}
+
+/// Makes an [`AudioPlayer`] positional. Its volume and stereo pan follow its [`Transform2D`] relative to the [`AudioListener2D`]
+#[derive(Debug, Clone, Copy, Component)]
+#[require(Transform2D)]
+pub struct AudioEmitter2D {
+	pub falloff: Falloff,
+	/// Closer than this, the sound plays at full volume
+	pub reference_distance: f32,
+	/// Further than this, the sound is silent
+	pub max_distance: f32,
+}
+
+impl Default for AudioEmitter2D {
+	fn default() -> Self {
+		Self {
+			falloff: Falloff::default(),
+			reference_distance: 100.0,
+			max_distance: 1000.0,
+		}
+	}
+}
+
+impl AudioEmitter2D {
+	/// Volume multiplier at `distance` from the listener
+	pub fn attenuation(&self, distance: f32) -> f32 {
+		if distance >= self.max_distance {
+			return 0.0;
+		}
+
+		let reference = self.reference_distance.max(f32::EPSILON);
+		let distance = distance.max(reference);
+
+		match self.falloff {
+			Falloff::Linear => 1.0 - (distance - reference) / (self.max_distance - reference).max(f32::EPSILON),
+			Falloff::Inverse { rolloff } => reference / (reference + rolloff * (distance - reference)),
+			Falloff::Exponential { rolloff } => (distance / reference).powf(-rolloff),
+		}
+		.clamp(0.0, 1.0)
+	}
+
+	/// Volume multiplier and stereo pan of the emitter at `position`
+	pub(crate) fn spatialize(&self, position: Vec2, listener: &AudioListener2D) -> (f32, f32) {
+		let offset = position - listener.position;
+		let distance = offset.length();
+
+		// Sounds within the reference distance drift towards the center
+		let pan = offset.dot(listener.right) / distance.max(self.reference_distance).max(f32::EPSILON);
+		(self.attenuation(distance), pan.clamp(-1.0, 1.0))
+	}
+}
+
+/// Where sounds of [`AudioEmitter2D`]s are heard from
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct AudioListener2D {
+	pub position: Vec2,
+	/// Direction of the right ear, normalized
+	pub right: Vec2,
+	/// Move the listener to the center of the [`CurrentCameraTag`] camera every frame
+	pub follow_camera: bool,
+}
+
+impl Default for AudioListener2D {
+	fn default() -> Self {
+		Self {
+			position: Vec2::ZERO,
+			right: Vec2::X,
+			follow_camera: true,
+		}
+	}
+}
+
+pub(crate) fn update_listener(mut listener: ResMut<AudioListener2D>, current_camera: Option<Res<CurrentCameraTag>>, cameras: Query<&Camera2D>) {
+	if !listener.follow_camera {
+		return;
+	}
+
+	let Some(camera) = current_camera.and_then(|current| cameras.get(current.0).ok()) else {
+		return;
+	};
+
+	// The center of the screen, and the screen's x axis in world space
+	let inverse = camera.matrix().inverse();
+	let position = inverse * vec4(0.0, 0.0, 0.0, 1.0);
+	let right = (inverse * vec4(1.0, 0.0, 0.0, 0.0)).truncate().truncate().try_normalize().unwrap_or(Vec2::X);
+
+	listener.position = position.truncate().truncate();
+	listener.right = right;
+}
+
+/// Applies the position of emitters to their playing sounds
+pub(crate) fn update_spatial_audio(listener: Res<AudioListener2D>, emitters: Query<(&AudioPlayer, &AudioSink, &AudioEmitter2D, &Transform2D)>) {
+	for (player, sink, emitter, transform) in emitters.iter() {
+		let (attenuation, pan) = emitter.spatialize(transform.translation.truncate(), &listener);
+		sink.with_voice(|settings| {
+			settings.volume = player.volume * attenuation;
+			settings.pan = pan;
+		});
+	}
+}
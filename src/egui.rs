@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+
+use bevy_app::{App, Last, Plugin, PreUpdate};
+use bevy_ecs::{
+	event::EventReader,
+	schedule::IntoSystemConfigs,
+	system::{Local, NonSendMut, Res, ResMut, Resource},
+};
+use egui::epaint::{ClippedShape, ImageDelta, Primitive};
+use egui::{ImageData, Modifiers, PointerButton, Pos2, RawInput, TexturesDelta};
+use glam::{vec3, Mat4};
+use miniquad::{BlendFactor, BlendState, BlendValue, Equation, FilterMode, KeyCode, MipmapFilterMode, MouseButton, PipelineParams, ShaderSource, TouchPhase};
+
+use crate::render::{commit_frame, geometry::Vertex, pipeline::shader, pipeline::GlPipeline, rgba::Rgba, screenshot::capture_screenshots, RenderingBackend};
+use crate::window::events::{apply_cursor_properties, CursorProperties, KeyCharEvent, KeyCodeEvent, MouseButtonEvent, MouseWheelEvent, TouchEvent, WindowProperties};
+use crate::window::{clipboard::Clipboard, state::Headless, state::MiniquadEndDraw};
+
+/// Integrates [egui](https://docs.rs/egui), fed by quadify's input events and drawn through the [`RenderingBackend`] after everything else.
+///
+/// Build your UI through the [`EguiContext`] resource, in any system running between [`PreUpdate`] and [`Last`]
+pub struct EguiPlugin;
+
+impl Plugin for EguiPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<EguiContext>()
+			.init_resource::<EguiInput>()
+			.init_resource::<EguiOutput>()
+			.add_systems(PreUpdate, begin_egui_pass)
+			.add_systems(Last, end_egui_pass.before(apply_cursor_properties))
+			.add_systems(MiniquadEndDraw, draw_egui.before(capture_screenshots).before(commit_frame));
+	}
+}
+
+/// The [`egui::Context`] of the window
+#[derive(Resource, Clone, Default)]
+pub struct EguiContext(egui::Context);
+
+impl EguiContext {
+	pub fn get(&self) -> &egui::Context {
+		&self.0
+	}
+}
+
+impl std::ops::Deref for EguiContext {
+	type Target = egui::Context;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+/// Input gathered for the next egui pass
+#[derive(Default, Resource)]
+pub(crate) struct EguiInput {
+	raw: RawInput,
+	pointer: Pos2,
+}
+
+/// Output of the last egui pass, waiting to be drawn
+#[derive(Default, Resource)]
+pub(crate) struct EguiOutput {
+	shapes: Vec<ClippedShape>,
+	textures: TexturesDelta,
+	pixels_per_point: f32,
+}
+
+fn modifiers(mods: miniquad::KeyMods) -> Modifiers {
+	Modifiers {
+		alt: mods.alt,
+		ctrl: mods.ctrl,
+		shift: mods.shift,
+		mac_cmd: cfg!(target_os = "macos") && mods.logo,
+		command: if cfg!(target_os = "macos") { mods.logo } else { mods.ctrl },
+	}
+}
+
+fn key(keycode: KeyCode) -> Option<egui::Key> {
+	use egui::Key;
+
+	Some(match keycode {
+		KeyCode::Down => Key::ArrowDown,
+		KeyCode::Left => Key::ArrowLeft,
+		KeyCode::Right => Key::ArrowRight,
+		KeyCode::Up => Key::ArrowUp,
+		KeyCode::Escape => Key::Escape,
+		KeyCode::Tab => Key::Tab,
+		KeyCode::Backspace => Key::Backspace,
+		KeyCode::Enter | KeyCode::KpEnter => Key::Enter,
+		KeyCode::Space => Key::Space,
+		KeyCode::Insert => Key::Insert,
+		KeyCode::Delete => Key::Delete,
+		KeyCode::Home => Key::Home,
+		KeyCode::End => Key::End,
+		KeyCode::PageUp => Key::PageUp,
+		KeyCode::PageDown => Key::PageDown,
+		KeyCode::Comma => Key::Comma,
+		KeyCode::Minus | KeyCode::KpSubtract => Key::Minus,
+		KeyCode::Period | KeyCode::KpDecimal => Key::Period,
+		KeyCode::Slash | KeyCode::KpDivide => Key::Slash,
+		KeyCode::Backslash => Key::Backslash,
+		KeyCode::Semicolon => Key::Semicolon,
+		KeyCode::Apostrophe => Key::Quote,
+		KeyCode::Equal | KeyCode::KpEqual => Key::Equals,
+		KeyCode::KpAdd => Key::Plus,
+		KeyCode::LeftBracket => Key::OpenBracket,
+		KeyCode::RightBracket => Key::CloseBracket,
+		KeyCode::GraveAccent => Key::Backtick,
+		KeyCode::Key0 | KeyCode::Kp0 => Key::Num0,
+		KeyCode::Key1 | KeyCode::Kp1 => Key::Num1,
+		KeyCode::Key2 | KeyCode::Kp2 => Key::Num2,
+		KeyCode::Key3 | KeyCode::Kp3 => Key::Num3,
+		KeyCode::Key4 | KeyCode::Kp4 => Key::Num4,
+		KeyCode::Key5 | KeyCode::Kp5 => Key::Num5,
+		KeyCode::Key6 | KeyCode::Kp6 => Key::Num6,
+		KeyCode::Key7 | KeyCode::Kp7 => Key::Num7,
+		KeyCode::Key8 | KeyCode::Kp8 => Key::Num8,
+		KeyCode::Key9 | KeyCode::Kp9 => Key::Num9,
+		KeyCode::A => Key::A,
+		KeyCode::B => Key::B,
+		KeyCode::C => Key::C,
+		KeyCode::D => Key::D,
+		KeyCode::E => Key::E,
+		KeyCode::F => Key::F,
+		KeyCode::G => Key::G,
+		KeyCode::H => Key::H,
+		KeyCode::I => Key::I,
+		KeyCode::J => Key::J,
+		KeyCode::K => Key::K,
+		KeyCode::L => Key::L,
+		KeyCode::M => Key::M,
+		KeyCode::N => Key::N,
+		KeyCode::O => Key::O,
+		KeyCode::P => Key::P,
+		KeyCode::Q => Key::Q,
+		KeyCode::R => Key::R,
+		KeyCode::S => Key::S,
+		KeyCode::T => Key::T,
+		KeyCode::U => Key::U,
+		KeyCode::V => Key::V,
+		KeyCode::W => Key::W,
+		KeyCode::X => Key::X,
+		KeyCode::Y => Key::Y,
+		KeyCode::Z => Key::Z,
+		KeyCode::F1 => Key::F1,
+		KeyCode::F2 => Key::F2,
+		KeyCode::F3 => Key::F3,
+		KeyCode::F4 => Key::F4,
+		KeyCode::F5 => Key::F5,
+		KeyCode::F6 => Key::F6,
+		KeyCode::F7 => Key::F7,
+		KeyCode::F8 => Key::F8,
+		KeyCode::F9 => Key::F9,
+		KeyCode::F10 => Key::F10,
+		KeyCode::F11 => Key::F11,
+		KeyCode::F12 => Key::F12,
+		_ => return None,
+	})
+}
+
+fn cursor_icon(icon: egui::CursorIcon) -> miniquad::CursorIcon {
+	use egui::CursorIcon as Egui;
+	use miniquad::CursorIcon as Mqd;
+
+	match icon {
+		Egui::Help => Mqd::Help,
+		Egui::PointingHand => Mqd::Pointer,
+		Egui::Wait | Egui::Progress => Mqd::Wait,
+		Egui::Crosshair | Egui::Cell => Mqd::Crosshair,
+		Egui::Text | Egui::VerticalText => Mqd::Text,
+		Egui::Move | Egui::AllScroll | Egui::Grab | Egui::Grabbing => Mqd::Move,
+		Egui::NotAllowed | Egui::NoDrop => Mqd::NotAllowed,
+		Egui::ResizeHorizontal | Egui::ResizeEast | Egui::ResizeWest | Egui::ResizeColumn => Mqd::EWResize,
+		Egui::ResizeVertical | Egui::ResizeNorth | Egui::ResizeSouth | Egui::ResizeRow => Mqd::NSResize,
+		Egui::ResizeNeSw | Egui::ResizeNorthEast | Egui::ResizeSouthWest => Mqd::NESWResize,
+		Egui::ResizeNwSe | Egui::ResizeNorthWest | Egui::ResizeSouthEast => Mqd::NWSEResize,
+		_ => Mqd::Default,
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+fn begin_egui_pass(
+	context: Res<EguiContext>,
+	mut input: ResMut<EguiInput>,
+	cursor: Res<CursorProperties>,
+	window: Res<WindowProperties>,
+	mut buttons: EventReader<MouseButtonEvent>,
+	mut wheel: EventReader<MouseWheelEvent>,
+	mut touches: EventReader<TouchEvent>,
+	(mut chars, mut keys): (EventReader<KeyCharEvent>, EventReader<KeyCodeEvent>),
+	(clipboard, headless): (Clipboard, Option<Res<Headless>>),
+) {
+	// Headless apps have no window to query
+	let pixels_per_point = if headless.is_some() { 1.0 } else { miniquad::window::dpi_scale() };
+	let input = input.as_mut();
+	let to_points = |position: glam::Vec2| Pos2::new(position.x / pixels_per_point, position.y / pixels_per_point);
+
+	let pointer = to_points(cursor.position);
+	if pointer != input.pointer {
+		input.raw.events.push(egui::Event::PointerMoved(pointer));
+		input.pointer = pointer;
+	}
+
+	for event in buttons.read() {
+		let button = match event.button {
+			MouseButton::Left => PointerButton::Primary,
+			MouseButton::Right => PointerButton::Secondary,
+			MouseButton::Middle => PointerButton::Middle,
+			_ => continue,
+		};
+
+		input.raw.events.push(egui::Event::PointerButton {
+			pos: to_points(event.position),
+			button,
+			pressed: !event.released,
+			modifiers: input.raw.modifiers,
+		});
+	}
+
+	for event in wheel.read() {
+		input.raw.events.push(egui::Event::MouseWheel {
+			unit: egui::MouseWheelUnit::Line,
+			delta: egui::vec2(event.delta.x, event.delta.y),
+			modifiers: input.raw.modifiers,
+		});
+	}
+
+	// Touches also drive the pointer, as miniquad doesn't emulate mouse events for us
+	for event in touches.read() {
+		let pos = to_points(event.position);
+		let phase = match event.phase {
+			TouchPhase::Started => egui::TouchPhase::Start,
+			TouchPhase::Moved => egui::TouchPhase::Move,
+			TouchPhase::Ended => egui::TouchPhase::End,
+			TouchPhase::Cancelled => egui::TouchPhase::Cancel,
+		};
+
+		input.raw.events.push(egui::Event::Touch {
+			device_id: egui::TouchDeviceId(0),
+			id: egui::TouchId(event.id),
+			phase,
+			pos,
+			force: None,
+		});
+
+		let modifiers = input.raw.modifiers;
+		match phase {
+			egui::TouchPhase::Start => {
+				input.raw.events.push(egui::Event::PointerMoved(pos));
+				input.raw.events.push(egui::Event::PointerButton {
+					pos,
+					button: PointerButton::Primary,
+					pressed: true,
+					modifiers,
+				});
+			}
+			egui::TouchPhase::Move => input.raw.events.push(egui::Event::PointerMoved(pos)),
+			egui::TouchPhase::End | egui::TouchPhase::Cancel => {
+				input.raw.events.push(egui::Event::PointerButton {
+					pos,
+					button: PointerButton::Primary,
+					pressed: false,
+					modifiers,
+				});
+				input.raw.events.push(egui::Event::PointerGone);
+			}
+		}
+	}
+
+	for event in keys.read() {
+		let modifiers = modifiers(event.mods);
+		input.raw.modifiers = modifiers;
+
+		if !event.released && modifiers.command {
+			match event.keycode {
+				KeyCode::C => input.raw.events.push(egui::Event::Copy),
+				KeyCode::X => input.raw.events.push(egui::Event::Cut),
+				KeyCode::V => {
+					if let Some(text) = clipboard.get() {
+						input.raw.events.push(egui::Event::Paste(text));
+					}
+				}
+				_ => {}
+			}
+		}
+
+		if let Some(key) = key(event.keycode) {
+			input.raw.events.push(egui::Event::Key {
+				key,
+				physical_key: None,
+				pressed: !event.released,
+				repeat: event.repeat,
+				modifiers,
+			});
+		}
+	}
+
+	for event in chars.read() {
+		// Shortcuts are handled through key events
+		if event.character.is_control() || event.mods.ctrl || event.mods.logo {
+			continue;
+		}
+
+		input.raw.events.push(egui::Event::Text(event.character.to_string()));
+	}
+
+	input.raw.screen_rect = Some(egui::Rect::from_min_size(Pos2::ZERO, egui::vec2(window.width as f32, window.height as f32) / pixels_per_point));
+	input.raw.time = Some(miniquad::date::now());
+	input.raw.viewports.entry(egui::ViewportId::ROOT).or_default().native_pixels_per_point = Some(pixels_per_point);
+
+	context.begin_pass(input.raw.take());
+}
+
+fn end_egui_pass(context: Res<EguiContext>, mut output: ResMut<EguiOutput>, mut cursor: ResMut<CursorProperties>, mut clipboard: Clipboard) {
+	let full_output = context.end_pass();
+	let platform = full_output.platform_output;
+
+	let icon = cursor_icon(platform.cursor_icon);
+	if cursor.icon != icon {
+		cursor.icon = icon;
+	}
+
+	if !platform.copied_text.is_empty() {
+		clipboard.set(&platform.copied_text);
+	}
+
+	// Texture changes pile up if no frame gets drawn, e.g. while the window is minimized
+	output.textures.append(full_output.textures_delta);
+	output.shapes = full_output.shapes;
+	output.pixels_per_point = full_output.pixels_per_point;
+}
+
+/// Most indices drawn per `geometry` call, kept below the backend's batch size
+const INDICES_PER_BATCH: usize = 3 * 1024;
+
+#[allow(clippy::too_many_arguments)]
+fn draw_egui(
+	mut backend: NonSendMut<RenderingBackend>,
+	mut pipeline: Local<Option<GlPipeline>>,
+	mut textures: Local<HashMap<egui::TextureId, miniquad::TextureId>>,
+	mut remap: Local<Vec<(usize, u16)>>,
+	(mut vertices, mut indices): (Local<Vec<Vertex>>, Local<Vec<u16>>),
+	context: Res<EguiContext>,
+	mut output: ResMut<EguiOutput>,
+	window: Res<WindowProperties>,
+) {
+	let output = output.as_mut();
+	let delta = std::mem::take(&mut output.textures);
+
+	for (id, image) in delta.set {
+		update_texture(&mut backend, &mut textures, id, image);
+	}
+
+	// egui colours and textures are premultiplied
+	let pipeline = *pipeline.get_or_insert_with(|| {
+		let params = PipelineParams {
+			color_blend: Some(BlendState::new(Equation::Add, BlendFactor::One, BlendFactor::OneMinusValue(BlendValue::SourceAlpha))),
+			..Default::default()
+		};
+
+		backend
+			.make_pipeline(ShaderSource::new(shader::VERTEX, shader::FRAGMENT), params, vec![], vec![])
+			.expect("Failed to compile egui's pipeline")
+	});
+
+	let pixels_per_point = output.pixels_per_point;
+	let primitives = context.tessellate(std::mem::take(&mut output.shapes), pixels_per_point);
+
+	backend.render_pass(None);
+	backend.viewport(None);
+	backend.pipeline(Some(pipeline));
+
+	let (width, height) = (window.width as f32, window.height as f32);
+	remap.clear();
+
+	for (generation, primitive) in primitives.iter().enumerate() {
+		let Primitive::Mesh(mesh) = &primitive.primitive else {
+			continue;
+		};
+		let Some(texture) = textures.get(&mesh.texture_id) else {
+			continue;
+		};
+
+		let clip = primitive.clip_rect;
+		let (min_x, min_y) = ((clip.min.x * pixels_per_point).clamp(0.0, width), (clip.min.y * pixels_per_point).clamp(0.0, height));
+		let (max_x, max_y) = ((clip.max.x * pixels_per_point).clamp(min_x, width), (clip.max.y * pixels_per_point).clamp(min_y, height));
+		if max_x - min_x < 1.0 || max_y - min_y < 1.0 {
+			continue;
+		}
+
+		backend.scissor(Some((min_x.round() as i32, min_y.round() as i32, (max_x - min_x).round() as i32, (max_y - min_y).round() as i32)));
+		backend.texture(Some(texture));
+
+		// Meshes use u32 indices and can be larger than a batch, draw them a few triangles at a time
+		if remap.len() < mesh.vertices.len() {
+			remap.resize(mesh.vertices.len(), (usize::MAX, 0));
+		}
+		for (chunk, triangles) in mesh.indices.chunks(INDICES_PER_BATCH).enumerate() {
+			let batch = generation << 16 | chunk;
+			vertices.clear();
+			indices.clear();
+
+			for &index in triangles {
+				let slot = &mut remap[index as usize];
+				if slot.0 != batch {
+					let vertex = mesh.vertices[index as usize];
+					let color = Rgba::new(vertex.color.r(), vertex.color.g(), vertex.color.b(), vertex.color.a());
+
+					*slot = (batch, vertices.len() as u16);
+					vertices.push(Vertex::new(vec3(vertex.pos.x, vertex.pos.y, 0.0), glam::vec2(vertex.uv.x, vertex.uv.y), color));
+				}
+
+				indices.push(slot.1);
+			}
+
+			backend.geometry(&vertices, &indices);
+		}
+	}
+
+	backend.scissor(None);
+	backend.pipeline(None);
+
+	// Points, from the top-left corner
+	backend.draw(Mat4::orthographic_rh_gl(0.0, width / pixels_per_point, height / pixels_per_point, 0.0, -1.0, 1.0));
+
+	for id in delta.free {
+		if let Some(texture) = textures.remove(&id) {
+			backend.delete_texture(texture);
+		}
+	}
+}
+
+fn update_texture(backend: &mut RenderingBackend, textures: &mut HashMap<egui::TextureId, miniquad::TextureId>, id: egui::TextureId, delta: ImageDelta) {
+	let [width, height] = delta.image.size();
+	let pixels: Vec<u8> = match &delta.image {
+		ImageData::Color(image) => image.pixels.iter().flat_map(|color| color.to_array()).collect(),
+		ImageData::Font(image) => image.srgba_pixels(None).flat_map(|color| color.to_array()).collect(),
+	};
+
+	match (delta.pos, textures.get(&id)) {
+		(Some([x, y]), Some(&texture)) => backend.texture_update_part(texture, x as _, y as _, width as _, height as _, &pixels),
+		_ => {
+			let texture = backend.new_texture_from_rgba8(width as u16, height as u16, &pixels);
+			let filter = match delta.options.magnification {
+				egui::TextureFilter::Nearest => FilterMode::Nearest,
+				egui::TextureFilter::Linear => FilterMode::Linear,
+			};
+			backend.texture_set_filter(texture, filter, MipmapFilterMode::None);
+
+			if let Some(previous) = textures.insert(id, texture) {
+				backend.delete_texture(previous);
+			}
+		}
+	}
+}
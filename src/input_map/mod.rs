@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+	event::EventReader,
+	schedule::IntoSystemConfigs,
+	system::{Local, Res, ResMut, Resource},
+};
+use glam::Vec2;
+use miniquad::{KeyCode, MouseButton, TouchPhase};
+
+use crate::render::geometry::Rect;
+use crate::window::events::{GamepadAxis, GamepadAxisEvent, GamepadButton, GamepadButtonEvent, KeyCodeEvent, MouseButtonEvent, TouchEvent, WindowEvent};
+use crate::window::gamepad::GamepadSystem;
+
+#[cfg(feature = "serialize")]
+pub(crate) mod remote;
+
+/// A game action, usually a fieldless enum
+pub trait Action: Copy + Eq + Hash + Send + Sync + 'static {}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Action for T {}
+
+/// An input that is either pressed or not
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Binding {
+	Key(#[cfg_attr(feature = "serialize", serde(with = "remote::KeyCodeDef"))] KeyCode),
+	Mouse(#[cfg_attr(feature = "serialize", serde(with = "remote::MouseButtonDef"))] MouseButton),
+	/// A finger touching the screen inside a region, in physical pixels
+	Touch(Rect),
+	/// A button of any gamepad
+	Gamepad(GamepadButton),
+}
+
+/// An input with a value in the `-1.0..=1.0` range
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisBinding {
+	/// Two keys pushing the value in opposite directions
+	Keys {
+		#[cfg_attr(feature = "serialize", serde(with = "remote::KeyCodeDef"))]
+		negative: KeyCode,
+		#[cfg_attr(feature = "serialize", serde(with = "remote::KeyCodeDef"))]
+		positive: KeyCode,
+	},
+	/// An axis of any gamepad, values closer to zero than `deadzone` are ignored
+	Gamepad { axis: GamepadAxis, deadzone: f32 },
+}
+
+/// Binds inputs to the actions `A`. Bindings can be changed at runtime, and saved to RON or JSON with the `serialize` feature
+#[derive(Debug, Clone, Resource)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap<A: Action> {
+	buttons: Vec<(A, Binding)>,
+	axes: Vec<(A, AxisBinding)>,
+}
+
+impl<A: Action> Default for InputMap<A> {
+	fn default() -> Self {
+		Self {
+			buttons: Vec::new(),
+			axes: Vec::new(),
+		}
+	}
+}
+
+impl<A: Action> InputMap<A> {
+	/// Pressing `binding` presses `action`. An action can have any amount of bindings
+	pub fn bind(&mut self, action: A, binding: Binding) -> &mut Self {
+		if !self.buttons.contains(&(action, binding)) {
+			self.buttons.push((action, binding));
+		}
+		self
+	}
+
+	/// Sets the value of `action` from an axis
+	pub fn bind_axis(&mut self, action: A, binding: AxisBinding) -> &mut Self {
+		if !self.axes.contains(&(action, binding)) {
+			self.axes.push((action, binding));
+		}
+		self
+	}
+
+	/// Removes a single binding, from every action it's bound to
+	pub fn unbind(&mut self, binding: Binding) -> &mut Self {
+		self.buttons.retain(|(_, bound)| *bound != binding);
+		self
+	}
+
+	/// Removes every binding of `action`, usually before rebinding it
+	pub fn clear_action(&mut self, action: A) -> &mut Self {
+		self.buttons.retain(|(bound, _)| *bound != action);
+		self.axes.retain(|(bound, _)| *bound != action);
+		self
+	}
+
+	pub fn bindings(&self, action: A) -> impl Iterator<Item = &Binding> {
+		self.buttons.iter().filter(move |(bound, _)| *bound == action).map(|(_, binding)| binding)
+	}
+
+	pub fn axis_bindings(&self, action: A) -> impl Iterator<Item = &AxisBinding> {
+		self.axes.iter().filter(move |(bound, _)| *bound == action).map(|(_, binding)| binding)
+	}
+
+	/// Actions bound to anything
+	pub fn actions(&self) -> impl Iterator<Item = A> + '_ {
+		let mut seen = HashSet::new();
+		self.buttons
+			.iter()
+			.map(|(action, _)| *action)
+			.chain(self.axes.iter().map(|(action, _)| *action))
+			.filter(move |action| seen.insert(*action))
+	}
+}
+
+#[cfg(feature = "serialize")]
+impl<A: Action + serde::Serialize + serde::de::DeserializeOwned> InputMap<A> {
+	pub fn to_ron(&self) -> Result<String, ron::Error> {
+		ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+	}
+
+	pub fn from_ron(ron: &str) -> Result<Self, ron::de::SpannedError> {
+		ron::from_str(ron)
+	}
+
+	pub fn to_json(&self) -> Result<String, serde_json::Error> {
+		serde_json::to_string_pretty(self)
+	}
+
+	pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+		serde_json::from_str(json)
+	}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct ActionData {
+	pressed: bool,
+	just_pressed: bool,
+	just_released: bool,
+	value: f32,
+}
+
+/// State of every action `A` this frame, updated from its [`InputMap`] in [`PreUpdate`]
+#[derive(Debug, Clone, Resource)]
+pub struct ActionState<A: Action> {
+	actions: HashMap<A, ActionData>,
+}
+
+impl<A: Action> Default for ActionState<A> {
+	fn default() -> Self {
+		Self { actions: HashMap::new() }
+	}
+}
+
+impl<A: Action> ActionState<A> {
+	/// Axes press their action past this value
+	pub const AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+	pub fn pressed(&self, action: A) -> bool {
+		self.actions.get(&action).is_some_and(|data| data.pressed)
+	}
+
+	pub fn just_pressed(&self, action: A) -> bool {
+		self.actions.get(&action).is_some_and(|data| data.just_pressed)
+	}
+
+	pub fn just_released(&self, action: A) -> bool {
+		self.actions.get(&action).is_some_and(|data| data.just_released)
+	}
+
+	/// `1.0` for pressed buttons, or the value of the strongest bound axis
+	pub fn value(&self, action: A) -> f32 {
+		self.actions.get(&action).map_or(0.0, |data| data.value)
+	}
+
+	/// Sets the state of `action` for this frame, keeping track of presses and releases
+	pub fn set(&mut self, action: A, pressed: bool, value: f32) {
+		let data = self.actions.entry(action).or_default();
+		data.just_pressed = pressed && !data.pressed;
+		data.just_released = !pressed && data.pressed;
+		data.pressed = pressed;
+		data.value = value;
+	}
+}
+
+/// Keeps [`ActionState<A>`] up to date with the bindings of [`InputMap<A>`]. Both are initialized if missing
+pub struct InputMapPlugin<A: Action>(PhantomData<A>);
+
+impl<A: Action> Default for InputMapPlugin<A> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<A: Action> Plugin for InputMapPlugin<A> {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<InputMap<A>>()
+			.init_resource::<ActionState<A>>()
+			.add_systems(PreUpdate, update_action_state::<A>.after(GamepadSystem));
+	}
+}
+
+/// Held inputs, rebuilt from events
+#[derive(Default)]
+pub(crate) struct RawInput {
+	keys: HashSet<KeyCode>,
+	mouse: HashSet<MouseButton>,
+	touches: HashMap<u64, Vec2>,
+	gamepad_buttons: HashSet<(usize, GamepadButton)>,
+	gamepad_axes: HashMap<(usize, GamepadAxis), f32>,
+}
+
+impl RawInput {
+	fn pressed(&self, binding: &Binding) -> bool {
+		match binding {
+			Binding::Key(key) => self.keys.contains(key),
+			Binding::Mouse(button) => self.mouse.contains(button),
+			Binding::Touch(region) => self.touches.values().any(|position| region.contains(*position)),
+			Binding::Gamepad(button) => self.gamepad_buttons.iter().any(|(_, pressed)| pressed == button),
+		}
+	}
+
+	fn value(&self, binding: &AxisBinding) -> f32 {
+		match *binding {
+			AxisBinding::Keys { negative, positive } => self.keys.contains(&positive) as i32 as f32 - self.keys.contains(&negative) as i32 as f32,
+			AxisBinding::Gamepad { axis, deadzone } => self
+				.gamepad_axes
+				.iter()
+				.filter(|((_, bound), value)| *bound == axis && value.abs() > deadzone)
+				.map(|(_, value)| *value)
+				.fold(0.0, |strongest: f32, value| if value.abs() > strongest.abs() { value } else { strongest }),
+		}
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_action_state<A: Action>(
+	mut raw: Local<RawInput>,
+	mut keys: EventReader<KeyCodeEvent>,
+	mut mouse: EventReader<MouseButtonEvent>,
+	mut touches: EventReader<TouchEvent>,
+	mut gamepad_buttons: EventReader<GamepadButtonEvent>,
+	mut gamepad_axes: EventReader<GamepadAxisEvent>,
+	mut window: EventReader<WindowEvent>,
+	map: Res<InputMap<A>>,
+	mut state: ResMut<ActionState<A>>,
+) {
+	for event in keys.read() {
+		if event.released {
+			raw.keys.remove(&event.keycode);
+		} else {
+			raw.keys.insert(event.keycode);
+		}
+	}
+
+	for event in mouse.read() {
+		if event.released {
+			raw.mouse.remove(&event.button);
+		} else {
+			raw.mouse.insert(event.button);
+		}
+	}
+
+	for event in touches.read() {
+		match event.phase {
+			TouchPhase::Started | TouchPhase::Moved => raw.touches.insert(event.id, event.position),
+			TouchPhase::Ended | TouchPhase::Cancelled => raw.touches.remove(&event.id),
+		};
+	}
+
+	for event in gamepad_buttons.read() {
+		if event.released {
+			raw.gamepad_buttons.remove(&(event.gamepad, event.button));
+		} else {
+			raw.gamepad_buttons.insert((event.gamepad, event.button));
+		}
+	}
+
+	for event in gamepad_axes.read() {
+		raw.gamepad_axes.insert((event.gamepad, event.axis), event.value);
+	}
+
+	// Releases are missed while the window is minimized
+	if window.read().any(|event| matches!(event, WindowEvent::Minimized)) {
+		*raw = RawInput::default();
+	}
+
+	// Actions that lost all their bindings are released too
+	let actions: HashSet<A> = map.actions().chain(state.actions.keys().copied()).collect();
+	for action in actions {
+		let button = map.bindings(action).any(|binding| raw.pressed(binding));
+		let axis = map
+			.axis_bindings(action)
+			.map(|binding| raw.value(binding))
+			.fold(0.0, |strongest: f32, value| if value.abs() > strongest.abs() { value } else { strongest });
+
+		let pressed = button || axis.abs() >= ActionState::<A>::AXIS_PRESS_THRESHOLD;
+		let value = if button { 1.0 } else { axis };
+		state.set(action, pressed, value);
+	}
+}
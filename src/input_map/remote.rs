@@ -0,0 +1,158 @@
+//! Mirrors of miniquad's input types, so bindings can be serialized
+
+use miniquad::{KeyCode, KeyMods, MouseButton, TouchPhase};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "MouseButton")]
+pub(crate) enum MouseButtonDef {
+	Right,
+	Left,
+	Middle,
+	Unknown,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "KeyMods")]
+pub(crate) struct KeyModsDef {
+	shift: bool,
+	ctrl: bool,
+	alt: bool,
+	logo: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "TouchPhase")]
+pub(crate) enum TouchPhaseDef {
+	Started,
+	Moved,
+	Ended,
+	Cancelled,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "KeyCode")]
+pub(crate) enum KeyCodeDef {
+	Space,
+	Apostrophe,
+	Comma,
+	Minus,
+	Period,
+	Slash,
+	Key0,
+	Key1,
+	Key2,
+	Key3,
+	Key4,
+	Key5,
+	Key6,
+	Key7,
+	Key8,
+	Key9,
+	Semicolon,
+	Equal,
+	A,
+	B,
+	C,
+	D,
+	E,
+	F,
+	G,
+	H,
+	I,
+	J,
+	K,
+	L,
+	M,
+	N,
+	O,
+	P,
+	Q,
+	R,
+	S,
+	T,
+	U,
+	V,
+	W,
+	X,
+	Y,
+	Z,
+	LeftBracket,
+	Backslash,
+	RightBracket,
+	GraveAccent,
+	World1,
+	World2,
+	Escape,
+	Enter,
+	Tab,
+	Backspace,
+	Insert,
+	Delete,
+	Right,
+	Left,
+	Down,
+	Up,
+	PageUp,
+	PageDown,
+	Home,
+	End,
+	CapsLock,
+	ScrollLock,
+	NumLock,
+	PrintScreen,
+	Pause,
+	F1,
+	F2,
+	F3,
+	F4,
+	F5,
+	F6,
+	F7,
+	F8,
+	F9,
+	F10,
+	F11,
+	F12,
+	F13,
+	F14,
+	F15,
+	F16,
+	F17,
+	F18,
+	F19,
+	F20,
+	F21,
+	F22,
+	F23,
+	F24,
+	F25,
+	Kp0,
+	Kp1,
+	Kp2,
+	Kp3,
+	Kp4,
+	Kp5,
+	Kp6,
+	Kp7,
+	Kp8,
+	Kp9,
+	KpDecimal,
+	KpDivide,
+	KpMultiply,
+	KpSubtract,
+	KpAdd,
+	KpEnter,
+	KpEqual,
+	LeftShift,
+	LeftControl,
+	LeftAlt,
+	LeftSuper,
+	RightShift,
+	RightControl,
+	RightAlt,
+	RightSuper,
+	Menu,
+	Back,
+	Unknown,
+}
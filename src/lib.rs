@@ -1,10 +1,26 @@
 /// Simplified import for all the crate's types and functions
 pub mod prelude {
+	#[cfg(feature = "audio")]
+	pub use crate::audio::*;
+	pub use crate::input_map::*;
 	pub use crate::io::*;
 	pub use crate::render::RenderBackendPlugin;
-	pub use crate::render::{camera::*, geometry::*, *};
-	pub use crate::window::{events::*, icon::*, state::*, *};
+	pub use crate::render::{camera::*, dynamic_atlas::*, geometry::*, screenshot::*, transform::*, *};
+	#[cfg(feature = "headless")]
+	pub use crate::render::{null::*, software::*};
+	pub use crate::sprite::*;
+	#[cfg(feature = "text")]
+	pub use crate::text::*;
+	#[cfg(feature = "ui")]
+	pub use crate::ui::*;
+	#[cfg(feature = "headless")]
+	pub use crate::window::headless::*;
+	#[cfg(feature = "bevy_input")]
+	pub use crate::window::input_bridge::InputBridgePlugin;
+	pub use crate::window::{clipboard::*, events::*, gamepad::*, icon::*, input::*, replay::*, state::*, text_input::*, touch::*, *};
 	pub use crate::QuadifyPlugins;
+	#[cfg(feature = "egui")]
+	pub use {crate::egui::*, egui};
 
 	pub use glam;
 	pub use miniquad;
@@ -15,8 +31,18 @@ pub mod color {
 }
 
 pub mod asset;
+#[cfg(feature = "audio")]
+pub(crate) mod audio;
+#[cfg(feature = "egui")]
+pub(crate) mod egui;
+pub(crate) mod input_map;
 pub(crate) mod io;
 pub(crate) mod render;
+pub(crate) mod sprite;
+#[cfg(feature = "text")]
+pub(crate) mod text;
+#[cfg(feature = "ui")]
+pub(crate) mod ui;
 pub(crate) mod window;
 
 // Create Default plugin bundle
@@ -30,7 +56,37 @@ impl PluginGroup for QuadifyPlugins {
 		// TODO: Minimal async-runtime,get rendering working, simple asset system and audio
 
 		#[allow(unused_mut)]
-		let mut builder = PluginGroupBuilder::start::<Self>().add(render::RenderBackendPlugin::default()).add(window::WindowPlugin::default());
+		let mut builder = PluginGroupBuilder::start::<Self>()
+			.add(render::RenderBackendPlugin::default())
+			.add(window::WindowPlugin::default())
+			.add(asset::AssetPlugin::default())
+			.add(sprite::SpritePlugin)
+			.add(window::gamepad::GamepadPlugin::default());
+
+		#[cfg(feature = "bevy_input")]
+		{
+			builder = builder.add(window::input_bridge::InputBridgePlugin);
+		}
+
+		#[cfg(feature = "text")]
+		{
+			builder = builder.add(text::TextPlugin);
+		}
+
+		#[cfg(feature = "ui")]
+		{
+			builder = builder.add(ui::UiPlugin);
+		}
+
+		#[cfg(feature = "egui")]
+		{
+			builder = builder.add(egui::EguiPlugin);
+		}
+
+		#[cfg(feature = "audio")]
+		{
+			builder = builder.add(audio::AudioPlugin::default());
+		}
 
 		#[cfg(feature = "log")]
 		{
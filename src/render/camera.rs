@@ -1,5 +1,13 @@
-use bevy_ecs::{component::Component, entity::Entity, system::Resource};
+use bevy_ecs::{
+	component::Component,
+	entity::Entity,
+	system::{NonSendMut, Query, Resource},
+};
 use glam::{EulerRot, Mat4, Quat, Vec2, Vec3, Vec4};
+use miniquad::{FilterMode, TextureFormat, TextureParams};
+
+use super::RenderingBackend;
+use crate::asset::Texture;
 
 /// Tag component for the current camera.
 #[derive(Debug, Resource)]
@@ -28,6 +36,100 @@ impl RenderTarget {
 			Self::Texture { depth, .. } => depth.is_some(),
 		}
 	}
+
+	/// Render pass to draw into, `None` being the window
+	pub fn render_pass(&self) -> Option<miniquad::RenderPass> {
+		match self {
+			Self::Window => None,
+			Self::Texture { render_pass, .. } => Some(*render_pass),
+		}
+	}
+
+	/// Creates an `RGBA8` texture to render into, with an optional depth attachment. Free it with [`RenderTarget::delete`]
+	pub fn new_texture(backend: &mut RenderingBackend, width: u32, height: u32, depth: bool, filter: FilterMode) -> Self {
+		let colour_texture = backend.new_render_texture(TextureParams {
+			format: TextureFormat::RGBA8,
+			min_filter: filter,
+			mag_filter: filter,
+			width,
+			height,
+			..Default::default()
+		});
+		let depth = depth.then(|| {
+			backend.new_render_texture(TextureParams {
+				format: TextureFormat::Depth,
+				width,
+				height,
+				..Default::default()
+			})
+		});
+		let render_pass = backend.new_render_pass(colour_texture, depth);
+
+		Self::Texture { colour_texture, depth, render_pass }
+	}
+
+	/// Size of the colour texture, `None` for the window
+	pub fn size(&self, backend: &RenderingBackend) -> Option<(u32, u32)> {
+		match self {
+			Self::Window => None,
+			Self::Texture { colour_texture, .. } => {
+				let params = backend.texture_params(*colour_texture);
+				Some((params.width, params.height))
+			}
+		}
+	}
+
+	/// Resizes the attachments in place, clearing them. Their ids, and [`Texture`] assets made from them, stay valid
+	pub fn resize(&self, backend: &mut RenderingBackend, width: u32, height: u32) {
+		if let Self::Texture { colour_texture, depth, .. } = self {
+			backend.texture_resize(*colour_texture, width, height, None);
+			if let Some(depth) = depth {
+				backend.texture_resize(*depth, width, height, None);
+			}
+		}
+	}
+
+	/// The colour texture as a [`Texture`] asset, to draw the output as a sprite. The asset doesn't own the texture,
+	/// it stays valid until the render target is deleted.
+	///
+	/// Rows are stored bottom first like in OpenGL, the asset is marked [`upside_down`](Texture::upside_down) so sprites still draw it upright
+	pub fn texture(&self) -> Option<Texture> {
+		match self {
+			Self::Window => None,
+			Self::Texture { colour_texture, .. } => Some(Texture::borrowed(*colour_texture).upside_down()),
+		}
+	}
+
+	/// Frees the render pass and its attachments
+	pub fn delete(self, backend: &mut RenderingBackend) {
+		if let Self::Texture { render_pass, .. } = self {
+			backend.delete_render_pass(render_pass);
+		}
+	}
+}
+
+/// Keeps the texture [`RenderTarget`] of the same entity the size of the window, times `scale`.
+/// A `scale` below 1 renders at a lower resolution, for pixel-art upscaling
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ResizeWithWindow {
+	pub scale: f32,
+}
+
+impl Default for ResizeWithWindow {
+	fn default() -> Self {
+		Self { scale: 1.0 }
+	}
+}
+
+pub(crate) fn resize_render_targets(mut render_ctx: NonSendMut<RenderingBackend>, targets: Query<(&RenderTarget, &ResizeWithWindow)>) {
+	let (width, height) = render_ctx.screen_size();
+
+	for (target, resize) in &targets {
+		let size = ((width * resize.scale).round().max(1.0) as u32, (height * resize.scale).round().max(1.0) as u32);
+		if target.size(&render_ctx).is_some_and(|current| current != size) {
+			target.resize(&mut render_ctx, size.0, size.1);
+		}
+	}
 }
 
 /// Main camera that renders to screen
@@ -0,0 +1,270 @@
+use bevy_asset::{Assets, Handle};
+use glam::{uvec2, UVec2};
+
+use super::geometry::Rect;
+use super::RenderingBackend;
+use crate::asset::Texture;
+
+/// A row of packed rectangles, all fitting under the row's height
+#[derive(Debug, Clone)]
+struct Shelf {
+	y: u32,
+	height: u32,
+	cursor: u32,
+}
+
+/// Packs rectangles into a fixed area, row by row. Fast, and good enough for similarly sized rectangles like glyphs or icons
+#[derive(Debug, Clone)]
+pub struct ShelfPacker {
+	size: UVec2,
+	shelves: Vec<Shelf>,
+}
+
+impl ShelfPacker {
+	pub fn new(width: u32, height: u32) -> Self {
+		Self {
+			size: uvec2(width, height),
+			shelves: Vec::new(),
+		}
+	}
+
+	pub fn size(&self) -> UVec2 {
+		self.size
+	}
+
+	/// Finds a spot for a `width` x `height` rectangle, returning its top-left corner
+	pub fn pack(&mut self, width: u32, height: u32) -> Option<UVec2> {
+		if width > self.size.x || height > self.size.y {
+			return None;
+		}
+
+		// Prefer the shelf that wastes the least height
+		let best = self
+			.shelves
+			.iter_mut()
+			.filter(|shelf| shelf.height >= height && self.size.x - shelf.cursor >= width)
+			.min_by_key(|shelf| shelf.height - height);
+
+		if let Some(shelf) = best {
+			let position = uvec2(shelf.cursor, shelf.y);
+			shelf.cursor += width;
+			return Some(position);
+		}
+
+		// Open a new shelf below the last one
+		let y = self.shelves.last().map_or(0, |shelf| shelf.y + shelf.height);
+		if self.size.y - y < height {
+			return None;
+		}
+
+		self.shelves.push(Shelf { y, height, cursor: width });
+		Some(uvec2(0, y))
+	}
+
+	/// Extends the packing area. Already packed rectangles keep their positions
+	pub fn grow(&mut self, width: u32, height: u32) {
+		self.size = self.size.max(uvec2(width, height));
+	}
+
+	/// Forgets all packed rectangles
+	pub fn clear(&mut self) {
+		self.shelves.clear();
+	}
+}
+
+/// Location of an image added to a [`DynamicTextureAtlas`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasRegion {
+	/// Index of the page (texture) the image was packed into
+	pub page: usize,
+	/// Region of the page's texture, in pixels
+	pub rect: Rect,
+}
+
+struct AtlasPage {
+	packer: ShelfPacker,
+	pixels: Vec<u8>,
+	texture: Handle<Texture>,
+	/// Size of the current GPU texture, if one was made already
+	uploaded: Option<UVec2>,
+	/// Region that changed since the last upload
+	dirty: Option<(UVec2, UVec2)>,
+}
+
+impl AtlasPage {
+	fn new(size: u32, texture: Handle<Texture>) -> Self {
+		Self {
+			packer: ShelfPacker::new(size, size),
+			pixels: vec![0; (size * size * 4) as usize],
+			texture,
+			uploaded: None,
+			dirty: None,
+		}
+	}
+
+	/// Doubles the page's size, keeping its contents in place
+	fn grow(&mut self) {
+		let old = self.packer.size();
+		let new = old * 2;
+
+		let mut pixels = vec![0; (new.x * new.y * 4) as usize];
+		for (row, src) in self.pixels.chunks_exact((old.x * 4) as usize).enumerate() {
+			let start = row * (new.x * 4) as usize;
+			pixels[start..start + src.len()].copy_from_slice(src);
+		}
+
+		self.pixels = pixels;
+		self.packer.grow(new.x, new.y);
+	}
+
+	fn write(&mut self, position: UVec2, width: u32, height: u32, rgba: &[u8]) {
+		let stride = (self.packer.size().x * 4) as usize;
+		let row_len = (width * 4) as usize;
+
+		for (row, src) in rgba.chunks_exact(row_len).take(height as usize).enumerate() {
+			let start = (position.y as usize + row) * stride + position.x as usize * 4;
+			self.pixels[start..start + row_len].copy_from_slice(src);
+		}
+
+		let (min, max) = (position, position + uvec2(width, height));
+		self.dirty = Some(match self.dirty {
+			Some((dmin, dmax)) => (dmin.min(min), dmax.max(max)),
+			None => (min, max),
+		});
+	}
+}
+
+/// Packs many small RGBA images into a few large textures at runtime, so they can be drawn in a single draw call.
+///
+/// Images are packed on the CPU with [`add`](DynamicTextureAtlas::add), and pushed to GPU in a single go with
+/// [`flush`](DynamicTextureAtlas::flush). Pages start at `initial_size`, grow up to `max_size` and then new pages are made.
+///
+/// *Note: a page's [`Texture`] is replaced when it grows, so always read it through the page's handle*
+pub struct DynamicTextureAtlas {
+	pages: Vec<AtlasPage>,
+	initial_size: u32,
+	max_size: u32,
+	/// Empty pixels left between images, to avoid sampling neighbours
+	pub padding: u32,
+}
+
+impl DynamicTextureAtlas {
+	/// Pages double in size as they fill up, so `max_size` is rounded down to `initial_size` times a power of two.
+	///
+	/// # Panics
+	/// If `initial_size` is 0 or larger than `max_size`, or if `max_size` is larger than the `65535` pixels a texture can hold
+	pub fn new(initial_size: u32, max_size: u32) -> Self {
+		assert!(initial_size > 0 && initial_size <= max_size, "Invalid atlas page sizes: {} to {}", initial_size, max_size);
+		assert!(max_size <= u16::MAX as u32, "Atlas pages can't be larger than {} pixels", u16::MAX);
+
+		let mut grown_size = initial_size;
+		while grown_size * 2 <= max_size {
+			grown_size *= 2;
+		}
+
+		Self {
+			pages: Vec::new(),
+			initial_size,
+			max_size: grown_size,
+			padding: 1,
+		}
+	}
+
+	/// Packs a `width` x `height` RGBA8 image into the atlas. Returns `None` if the image is larger than a page can ever be
+	pub fn add(&mut self, textures: &Assets<Texture>, width: u32, height: u32, rgba: &[u8]) -> Option<AtlasRegion> {
+		debug_assert_eq!(rgba.len(), (width * height * 4) as usize, "Image data doesn't match its size");
+		let (padded_width, padded_height) = (width + self.padding, height + self.padding);
+
+		if padded_width > self.max_size || padded_height > self.max_size {
+			return None;
+		}
+
+		for (index, page) in self.pages.iter_mut().enumerate() {
+			loop {
+				if let Some(position) = page.packer.pack(padded_width, padded_height) {
+					page.write(position, width, height, rgba);
+					return Some(AtlasRegion {
+						page: index,
+						rect: Rect::new(position.x as f32, position.y as f32, width as f32, height as f32),
+					});
+				}
+
+				if page.packer.size().x * 2 > self.max_size {
+					break;
+				}
+				page.grow();
+			}
+		}
+
+		// All pages are full, start a new one that fits the image
+		let mut size = self.initial_size;
+		while size < padded_width.max(padded_height) {
+			size *= 2;
+		}
+
+		let mut page = AtlasPage::new(size.min(self.max_size), textures.reserve_handle());
+		let position = page.packer.pack(padded_width, padded_height)?;
+		page.write(position, width, height, rgba);
+		self.pages.push(page);
+
+		Some(AtlasRegion {
+			page: self.pages.len() - 1,
+			rect: Rect::new(position.x as f32, position.y as f32, width as f32, height as f32),
+		})
+	}
+
+	/// Uploads all changes to GPU. Grown pages get a new [`Texture`], the previous one is freed once the asset is replaced
+	pub fn flush(&mut self, backend: &mut RenderingBackend, textures: &mut Assets<Texture>) {
+		for page in self.pages.iter_mut() {
+			let Some((min, max)) = page.dirty.take() else {
+				continue;
+			};
+
+			let size = page.packer.size();
+			if page.uploaded == Some(size) {
+				let texture = textures.get(&page.texture).expect("Atlas page texture was removed").id();
+				let (width, height) = (max.x - min.x, max.y - min.y);
+
+				let mut region = Vec::with_capacity((width * height * 4) as usize);
+				for row in min.y..max.y {
+					let start = ((row * size.x + min.x) * 4) as usize;
+					region.extend_from_slice(&page.pixels[start..start + (width * 4) as usize]);
+				}
+
+				backend.texture_update_part(texture, min.x as _, min.y as _, width as _, height as _, &region);
+			} else {
+				let texture = backend.new_texture_from_rgba8(size.x as u16, size.y as u16, &page.pixels);
+				textures.insert(&page.texture, Texture::new(texture));
+				page.uploaded = Some(size);
+			}
+		}
+	}
+
+	/// Handle to the texture of a page. The texture itself is available after the first [`flush`](DynamicTextureAtlas::flush)
+	pub fn texture(&self, page: usize) -> Option<&Handle<Texture>> {
+		self.pages.get(page).map(|page| &page.texture)
+	}
+
+	/// Size of a page, in pixels
+	pub fn page_size(&self, page: usize) -> Option<UVec2> {
+		self.pages.get(page).map(|page| page.packer.size())
+	}
+
+	pub fn pages(&self) -> usize {
+		self.pages.len()
+	}
+
+	/// Removes the pages after the first `pages`, along with their images. Their textures are freed with their handles
+	pub fn truncate(&mut self, pages: usize) {
+		self.pages.truncate(pages);
+	}
+
+	/// Removes all images, keeping the pages and their textures around for reuse
+	pub fn clear(&mut self) {
+		for page in self.pages.iter_mut() {
+			page.packer.clear();
+			page.pixels.fill(0);
+			page.dirty = Some((UVec2::ZERO, page.packer.size()));
+		}
+	}
+}
@@ -9,7 +9,7 @@ use bevy_reflect::Reflect;
 use super::rgba::Rgba;
 
 #[repr(C)]
-#[derive(Clone, Debug, Copy, PartialEq)]
+#[derive(Clone, Debug, Copy, PartialEq, Reflect)]
 pub struct Vertex {
 	pub position: Vec3,
 	pub uv: Vec2,
@@ -31,7 +31,54 @@ impl Vertex {
 	}
 }
 
-#[derive(Clone, PartialEq)]
+/// Axis aligned rectangle. Used for texture regions (in pixels) among other things
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+	pub min: Vec2,
+	pub max: Vec2,
+}
+
+impl Rect {
+	pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+		Self {
+			min: vec2(x, y),
+			max: vec2(x + width, y + height),
+		}
+	}
+
+	/// Makes a rectangle from any two opposite corners
+	pub fn from_corners(a: Vec2, b: Vec2) -> Self {
+		Self { min: a.min(b), max: a.max(b) }
+	}
+
+	pub fn width(&self) -> f32 {
+		self.max.x - self.min.x
+	}
+
+	pub fn height(&self) -> f32 {
+		self.max.y - self.min.y
+	}
+
+	pub fn size(&self) -> Vec2 {
+		self.max - self.min
+	}
+
+	pub fn contains(&self, point: Vec2) -> bool {
+		point.cmpge(self.min).all() && point.cmplt(self.max).all()
+	}
+
+	/// Area covered by both rectangles. Empty rectangles have a size of zero
+	pub fn intersect(&self, other: Rect) -> Rect {
+		let min = self.min.max(other.min);
+		Self {
+			min,
+			max: self.max.min(other.max).max(min),
+		}
+	}
+}
+
+#[derive(Asset, Clone, PartialEq, Reflect)]
 pub struct Mesh {
 	pub vertices: Vec<Vertex>,
 	pub indices: Vec<u16>,
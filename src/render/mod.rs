@@ -1,3 +1,5 @@
+use bevy_ecs::component::Component;
+use bevy_ecs::schedule::IntoSystemConfigs;
 use bevy_ecs::system::{NonSendMut, Query, Res, Resource};
 use glam::{vec2, vec3};
 use miniquad::*;
@@ -11,15 +13,29 @@ use crate::window::state;
 use super::render::{material::*, pipeline::*};
 
 pub mod camera;
+pub mod dynamic_atlas;
 pub mod geometry;
 pub mod material;
+#[cfg(feature = "headless")]
+pub mod null;
 pub mod pipeline;
 pub mod rgba;
+pub mod screenshot;
+#[cfg(feature = "headless")]
+pub mod software;
+pub mod transform;
 
 /// Miniquad rendering backend object.
 pub struct RenderingBackend {
 	backend: Box<dyn MqdRenderingBackend>,
 	start_time: f64,
+	/// Fixed screen size of headless backends, the window's size is used otherwise
+	screen_size: Option<(f32, f32)>,
+	/// Copies of uploaded geometry, for backends that can't read buffers back
+	#[cfg(feature = "headless")]
+	geometry: Option<software::GeometryMirror>,
+	#[cfg(feature = "headless")]
+	software_screen: Option<software::SoftwareScreen>,
 
 	white_texture: miniquad::TextureId,
 
@@ -56,14 +72,43 @@ impl Default for RenderingBackend {
 
 impl RenderingBackend {
 	pub fn new() -> Self {
-		let mut backend = window::new_rendering_backend();
+		Self::from_backend(window::new_rendering_backend())
+	}
+
+	/// Wraps a custom `miniquad` backend, like the `NullBackend` of the `headless` feature, rendering into a `width` by `height` screen
+	pub fn headless(backend: Box<dyn MqdRenderingBackend>, width: u32, height: u32) -> Self {
+		Self {
+			screen_size: Some((width as f32, height as f32)),
+			..Self::from_backend(backend)
+		}
+	}
+
+	/// Rasterizes on the CPU with a [`SoftwareBackend`](software::SoftwareBackend), drawing into its screen
+	#[cfg(feature = "headless")]
+	pub fn software(backend: software::SoftwareBackend) -> Self {
+		let screen = backend.screen();
+		let (width, height) = screen.size();
+		let geometry = backend.geometry();
+
+		Self {
+			geometry: Some(geometry),
+			software_screen: Some(screen),
+			..Self::headless(Box::new(backend), width, height)
+		}
+	}
 
+	fn from_backend(mut backend: Box<dyn MqdRenderingBackend>) -> Self {
 		let white_texture = backend.new_texture_from_rgba8(1, 1, &[255, 255, 255, 255]);
 		let pipelines = pipeline::PipelineStorage::new(&mut *backend);
 
 		Self {
 			backend,
 			start_time: miniquad::date::now(),
+			screen_size: None,
+			#[cfg(feature = "headless")]
+			geometry: None,
+			#[cfg(feature = "headless")]
+			software_screen: None,
 
 			white_texture,
 
@@ -104,6 +149,9 @@ impl RenderingBackend {
 		}
 	}
 
+	/// Frees the material's pipeline and shader.
+	///
+	/// *Note: materials stored in [`Assets<Material>`](bevy_asset::Assets) are freed automatically once removed or unused*
 	pub fn remove_material(&mut self, material: Material) {
 		self.delete_pipeline(material.pipeline);
 	}
@@ -154,7 +202,7 @@ impl RenderingBackend {
 		}
 		assert_eq!(self.draw_call_bindings.len(), self.draw_calls.len());
 
-		let (screen_width, screen_height) = miniquad::window::screen_size();
+		let (screen_width, screen_height) = self.screen_size();
 		let time = (miniquad::date::now() - self.start_time) as f32;
 		let time = glam::vec4(time, time.sin(), time.cos(), 0.);
 
@@ -177,6 +225,10 @@ impl RenderingBackend {
 
 			self.backend.buffer_update(bindings.vertex_buffers[0], BufferSource::slice(dc.vertices()));
 			self.backend.buffer_update(bindings.index_buffer, BufferSource::slice(dc.indices()));
+			#[cfg(feature = "headless")]
+			if let Some(geometry) = &self.geometry {
+				geometry.upload(bindings.vertex_buffers[0], dc.vertices(), bindings.index_buffer, dc.indices());
+			}
 
 			bindings.images[0] = dc.texture.unwrap_or(white_texture);
 			bindings.images.resize(1 + pipeline.textures.len(), white_texture);
@@ -250,8 +302,45 @@ impl RenderingBackend {
 		self.state.viewport = viewport;
 	}
 
+	/// Size of the window, or of the headless screen
+	pub fn screen_size(&self) -> (f32, f32) {
+		self.screen_size.unwrap_or_else(miniquad::window::screen_size)
+	}
+
+	/// Reads the window's pixels drawn so far this frame, as `(width, height, RGBA8 bytes)` with the top row first.
+	/// `None` for backends that can't read the window back, like Metal, WebGL or the `NullBackend`
+	pub fn read_screen_pixels(&mut self) -> Option<(u32, u32, Vec<u8>)> {
+		#[cfg(feature = "headless")]
+		if let Some(screen) = &self.software_screen {
+			let (width, height) = screen.size();
+			return Some((width, height, screen.read_pixels()));
+		}
+
+		// Other headless backends have no window to read
+		if self.screen_size.is_some() || self.backend.info().backend != Backend::OpenGl {
+			return None;
+		}
+
+		#[cfg(not(target_arch = "wasm32"))]
+		{
+			let (width, height) = self.screen_size();
+			let (width, height) = (width as u32, height as u32);
+			let mut bytes = vec![0; width as usize * height as usize * 4];
+			// SAFETY: The buffer fits the whole RGBA8 framebuffer, with rows aligned to 4 bytes
+			unsafe {
+				miniquad::gl::glReadPixels(0, 0, width as i32, height as i32, miniquad::gl::GL_RGBA, miniquad::gl::GL_UNSIGNED_BYTE, bytes.as_mut_ptr() as *mut _);
+			}
+
+			Some((width, height, flip_rows(bytes, width)))
+		}
+
+		// WebGL has no bindings to read pixels back through
+		#[cfg(target_arch = "wasm32")]
+		None
+	}
+
 	pub fn get_viewport(&self) -> (i32, i32, i32, i32) {
-		let (w, h) = miniquad::window::screen_size();
+		let (w, h) = self.screen_size();
 		self.state.viewport.unwrap_or((0, 0, w as _, h as _))
 	}
 
@@ -362,11 +451,11 @@ impl RenderingBackend {
 		dc.texture = self.state.texture;
 	}
 
-	/// Deletes the pipeline from the inner pipeline storage.
+	/// Deletes the pipeline from the inner pipeline storage, freeing its slot and GPU objects.
 	///
 	/// *Attention: using the same pipeline again will panic, or give unexpected results*
 	pub fn delete_pipeline(&mut self, pipeline: GlPipeline) {
-		self.pipelines.delete_pipeline(pipeline);
+		self.pipelines.delete_pipeline(&mut *self.backend, pipeline);
 	}
 
 	/// Update the uniform of a loaded pipeline
@@ -430,6 +519,14 @@ impl RenderingBackend {
 #[derive(Resource, Default)]
 pub struct ClearColor(pub rgba::Rgba);
 
+/// Controls whether an entity gets drawn. Entities without this component are visible
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Visibility {
+	#[default]
+	Visible,
+	Hidden,
+}
+
 /// Plugin responsible for initializing the [`RenderBackend`](MqdRenderingBackend)
 pub struct RenderBackendPlugin {
 	/// Controls whether to turn on Quadify's default rendering like Mesh, Materials etc.
@@ -456,6 +553,11 @@ impl bevy_app::Plugin for RenderBackendPlugin {
 				.add_systems(state::MiniquadPrepareDraw, apply_clear_color)
 				.add_systems(state::MiniquadEndDraw, commit_frame);
 		}
+
+		app.add_systems(state::MiniquadPrepareDraw, camera::resize_render_targets.before(apply_clear_color))
+			.add_event::<screenshot::Screenshot>()
+			.add_event::<screenshot::ScreenshotCaptured>()
+			.add_systems(state::MiniquadEndDraw, screenshot::capture_screenshots.before(commit_frame));
 	}
 }
 
@@ -487,7 +589,17 @@ fn apply_clear_color(
 	render_ctx.end_render_pass();
 }
 
+/// Reverses the rows of an RGBA8 image, OpenGL reads pixels starting from the bottom row
+pub(crate) fn flip_rows(bytes: Vec<u8>, width: u32) -> Vec<u8> {
+	let row = width as usize * 4;
+	if row == 0 {
+		return bytes;
+	}
+
+	bytes.chunks_exact(row).rev().flatten().copied().collect()
+}
+
 /// Commit the rendered frame
-fn commit_frame(mut render_ctx: NonSendMut<RenderingBackend>) {
+pub(crate) fn commit_frame(mut render_ctx: NonSendMut<RenderingBackend>) {
 	render_ctx.commit_frame();
 }
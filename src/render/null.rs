@@ -0,0 +1,399 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use miniquad::*;
+
+/// Turns an index into one of `miniquad`'s opaque `usize` handles
+fn handle<T: Copy>(index: usize) -> T {
+	assert_eq!(std::mem::size_of::<T>(), std::mem::size_of::<usize>());
+	// SAFETY: `BufferId`, `ShaderId`, `Pipeline` and `RenderPass` are `usize` newtypes, without public constructors
+	unsafe { std::mem::transmute_copy(&index) }
+}
+
+/// A draw issued to a [`NullBackend`], with the state applied at the time
+#[derive(Debug, Clone)]
+pub struct RecordedDraw {
+	/// Render pass drawn into, `None` being the window
+	pub render_pass: Option<RenderPass>,
+	pub pipeline: Option<Pipeline>,
+	pub vertex_buffers: Vec<BufferId>,
+	pub index_buffer: Option<BufferId>,
+	pub textures: Vec<TextureId>,
+	pub viewport: Option<(i32, i32, i32, i32)>,
+	pub scissor: Option<(i32, i32, i32, i32)>,
+	/// Raw bytes of the last applied uniforms
+	pub uniforms: Vec<u8>,
+	pub base_element: i32,
+	pub num_elements: i32,
+	pub num_instances: i32,
+}
+
+/// A pass cleared by a [`NullBackend`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordedClear {
+	/// Render pass cleared, `None` being the window
+	pub render_pass: Option<RenderPass>,
+	pub color: (f32, f32, f32, f32),
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+	buffers: usize,
+	textures: usize,
+	shaders: usize,
+	pipelines: usize,
+	render_passes: usize,
+	frames: usize,
+	frame: Frame,
+	last_frame: Frame,
+}
+
+#[derive(Debug, Default)]
+struct Frame {
+	clears: Vec<RecordedClear>,
+	draws: Vec<RecordedDraw>,
+}
+
+/// Shared view into what a [`NullBackend`] has done, live object counts and the draws of the last committed frame
+#[derive(Debug, Clone, Default)]
+pub struct RenderRecord(Rc<RefCell<Recording>>);
+
+impl RenderRecord {
+	/// Number of live buffers
+	pub fn buffers(&self) -> usize {
+		self.0.borrow().buffers
+	}
+
+	/// Number of live textures, including render targets
+	pub fn textures(&self) -> usize {
+		self.0.borrow().textures
+	}
+
+	pub fn shaders(&self) -> usize {
+		self.0.borrow().shaders
+	}
+
+	pub fn pipelines(&self) -> usize {
+		self.0.borrow().pipelines
+	}
+
+	pub fn render_passes(&self) -> usize {
+		self.0.borrow().render_passes
+	}
+
+	/// Number of committed frames
+	pub fn frames(&self) -> usize {
+		self.0.borrow().frames
+	}
+
+	/// Draws of the last committed frame, in submission order
+	pub fn draw_calls(&self) -> Vec<RecordedDraw> {
+		self.0.borrow().last_frame.draws.clone()
+	}
+
+	/// Draws submitted since the last committed frame
+	pub fn pending_draw_calls(&self) -> Vec<RecordedDraw> {
+		self.0.borrow().frame.draws.clone()
+	}
+
+	/// Passes cleared with a color during the last committed frame
+	pub fn clears(&self) -> Vec<RecordedClear> {
+		self.0.borrow().last_frame.clears.clone()
+	}
+}
+
+#[derive(Debug)]
+pub(super) struct NullTexture {
+	raw: u32,
+	pub(super) params: TextureParams,
+	pub(super) bytes: Vec<u8>,
+}
+
+/// A [`miniquad::RenderingBackend`] that draws nothing. It keeps track of every object created, and records draw calls into a [`RenderRecord`].
+///
+/// Used by [`HeadlessPlugin`](crate::prelude::HeadlessPlugin) to run apps without a window or a GPU
+#[derive(Debug, Default)]
+pub struct NullBackend {
+	record: RenderRecord,
+	next_id: usize,
+
+	buffers: HashMap<BufferId, usize>,
+	pub(super) textures: HashMap<TextureId, NullTexture>,
+	shaders: HashSet<ShaderId>,
+	pub(super) pipelines: HashMap<Pipeline, PipelineParams>,
+	/// Color and depth attachments, `RenderPass` isn't hashable
+	pub(super) render_passes: Vec<(RenderPass, Vec<TextureId>, Option<TextureId>)>,
+
+	pub(super) render_pass: Option<RenderPass>,
+	pub(super) pipeline: Option<Pipeline>,
+	pub(super) bindings: Option<Bindings>,
+	pub(super) viewport: Option<(i32, i32, i32, i32)>,
+	pub(super) scissor: Option<(i32, i32, i32, i32)>,
+	pub(super) uniforms: Vec<u8>,
+}
+
+impl NullBackend {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Handle to what this backend records, it stays valid after the backend is boxed into a [`RenderingBackend`](super::RenderingBackend)
+	pub fn record(&self) -> RenderRecord {
+		self.record.clone()
+	}
+
+	fn next_id(&mut self) -> usize {
+		self.next_id += 1;
+		self.next_id
+	}
+
+	fn update_counts(&self) {
+		let mut record = self.record.0.borrow_mut();
+		record.buffers = self.buffers.len();
+		record.textures = self.textures.len();
+		record.shaders = self.shaders.len();
+		record.pipelines = self.pipelines.len();
+		record.render_passes = self.render_passes.len();
+	}
+}
+
+impl miniquad::RenderingBackend for NullBackend {
+	fn info(&self) -> ContextInfo {
+		ContextInfo {
+			backend: Backend::OpenGl,
+			gl_version_string: String::from("Null"),
+			glsl_support: GlslSupport { v100: true, ..Default::default() },
+			features: Features::default(),
+		}
+	}
+
+	fn new_shader(&mut self, _shader: ShaderSource, _meta: ShaderMeta) -> Result<ShaderId, ShaderError> {
+		let id = handle(self.next_id());
+		self.shaders.insert(id);
+		self.update_counts();
+		Ok(id)
+	}
+
+	fn new_texture(&mut self, _access: TextureAccess, data: TextureSource, params: TextureParams) -> TextureId {
+		let size = params.format.size(params.width, params.height) as usize;
+		let mut bytes = match data {
+			TextureSource::Bytes(bytes) => bytes.to_vec(),
+			TextureSource::Array(faces) => faces.first().and_then(|levels| levels.first()).map_or_else(Vec::new, |bytes| bytes.to_vec()),
+			TextureSource::Empty => Vec::new(),
+		};
+		bytes.resize(size, 0);
+
+		let raw = self.next_id() as u32;
+		let id = TextureId::from_raw_id(RawId::OpenGl(raw));
+		self.textures.insert(id, NullTexture { raw, params, bytes });
+		self.update_counts();
+		id
+	}
+
+	fn texture_params(&self, texture: TextureId) -> TextureParams {
+		self.textures[&texture].params
+	}
+
+	unsafe fn texture_raw_id(&self, texture: TextureId) -> RawId {
+		RawId::OpenGl(self.textures[&texture].raw)
+	}
+
+	fn texture_set_min_filter(&mut self, texture: TextureId, filter: FilterMode, mipmap_filter: MipmapFilterMode) {
+		if let Some(texture) = self.textures.get_mut(&texture) {
+			texture.params.min_filter = filter;
+			texture.params.mipmap_filter = mipmap_filter;
+		}
+	}
+
+	fn texture_set_mag_filter(&mut self, texture: TextureId, filter: FilterMode) {
+		if let Some(texture) = self.textures.get_mut(&texture) {
+			texture.params.mag_filter = filter;
+		}
+	}
+
+	fn texture_set_wrap(&mut self, texture: TextureId, wrap_x: TextureWrap, _wrap_y: TextureWrap) {
+		if let Some(texture) = self.textures.get_mut(&texture) {
+			texture.params.wrap = wrap_x;
+		}
+	}
+
+	fn texture_generate_mipmaps(&mut self, _texture: TextureId) {}
+
+	fn texture_resize(&mut self, texture: TextureId, width: u32, height: u32, bytes: Option<&[u8]>) {
+		if let Some(texture) = self.textures.get_mut(&texture) {
+			texture.params.width = width;
+			texture.params.height = height;
+			texture.bytes = bytes.map_or_else(Vec::new, <[u8]>::to_vec);
+			texture.bytes.resize(texture.params.format.size(width, height) as usize, 0);
+		}
+	}
+
+	fn texture_read_pixels(&mut self, texture: TextureId, bytes: &mut [u8]) {
+		let texture = &self.textures[&texture];
+		let len = bytes.len().min(texture.bytes.len());
+		bytes[..len].copy_from_slice(&texture.bytes[..len]);
+	}
+
+	fn texture_update_part(&mut self, texture: TextureId, x_offset: i32, y_offset: i32, width: i32, height: i32, bytes: &[u8]) {
+		let Some(texture) = self.textures.get_mut(&texture) else {
+			return;
+		};
+
+		let pixel = texture.params.format.size(1, 1) as usize;
+		let stride = texture.params.width as usize * pixel;
+		let row = width as usize * pixel;
+
+		for y in 0..height as usize {
+			let start = (y_offset as usize + y) * stride + x_offset as usize * pixel;
+			if let (Some(target), Some(source)) = (texture.bytes.get_mut(start..start + row), bytes.get(y * row..(y + 1) * row)) {
+				target.copy_from_slice(source);
+			}
+		}
+	}
+
+	fn new_render_pass_mrt(&mut self, color_img: &[TextureId], _resolve_img: Option<&[TextureId]>, depth_img: Option<TextureId>) -> RenderPass {
+		let id = handle(self.next_id());
+		self.render_passes.push((id, color_img.to_vec(), depth_img));
+		self.update_counts();
+		id
+	}
+
+	fn render_pass_color_attachments(&self, render_pass: RenderPass) -> &[TextureId] {
+		self.render_passes.iter().find(|(pass, ..)| *pass == render_pass).map_or(&[], |(_, textures, _)| textures)
+	}
+
+	/// Deletes the attachments too, like the OpenGL backend
+	fn delete_render_pass(&mut self, render_pass: RenderPass) {
+		if let Some(index) = self.render_passes.iter().position(|(pass, ..)| *pass == render_pass) {
+			let (_, color, depth) = self.render_passes.remove(index);
+			for texture in color.iter().chain(&depth) {
+				self.textures.remove(texture);
+			}
+		}
+		self.update_counts();
+	}
+
+	fn new_pipeline(&mut self, _buffer_layout: &[BufferLayout], _attributes: &[VertexAttribute], _shader: ShaderId, params: PipelineParams) -> Pipeline {
+		let id = handle(self.next_id());
+		self.pipelines.insert(id, params);
+		self.update_counts();
+		id
+	}
+
+	fn apply_pipeline(&mut self, pipeline: &Pipeline) {
+		self.pipeline = Some(*pipeline);
+	}
+
+	fn delete_pipeline(&mut self, pipeline: Pipeline) {
+		self.pipelines.remove(&pipeline);
+		self.update_counts();
+	}
+
+	fn new_buffer(&mut self, _type: BufferType, _usage: BufferUsage, data: BufferSource) -> BufferId {
+		// The contents of slices can't be read outside of miniquad, so only the size of empty buffers is known
+		let size = match data {
+			BufferSource::Empty { size, .. } => size,
+			BufferSource::Slice(_) => 0,
+		};
+
+		let id = handle(self.next_id());
+		self.buffers.insert(id, size);
+		self.update_counts();
+		id
+	}
+
+	fn buffer_update(&mut self, _buffer: BufferId, _data: BufferSource) {}
+
+	fn buffer_size(&mut self, buffer: BufferId) -> usize {
+		self.buffers.get(&buffer).copied().unwrap_or(0)
+	}
+
+	fn delete_buffer(&mut self, buffer: BufferId) {
+		self.buffers.remove(&buffer);
+		self.update_counts();
+	}
+
+	fn delete_texture(&mut self, texture: TextureId) {
+		self.textures.remove(&texture);
+		self.update_counts();
+	}
+
+	fn delete_shader(&mut self, program: ShaderId) {
+		self.shaders.remove(&program);
+		self.update_counts();
+	}
+
+	fn apply_viewport(&mut self, x: i32, y: i32, w: i32, h: i32) {
+		self.viewport = Some((x, y, w, h));
+	}
+
+	fn apply_scissor_rect(&mut self, x: i32, y: i32, w: i32, h: i32) {
+		self.scissor = Some((x, y, w, h));
+	}
+
+	fn apply_bindings_from_slice(&mut self, vertex_buffers: &[BufferId], index_buffer: BufferId, textures: &[TextureId]) {
+		self.bindings = Some(Bindings {
+			vertex_buffers: vertex_buffers.to_vec(),
+			index_buffer,
+			images: textures.to_vec(),
+		});
+	}
+
+	#[allow(clippy::not_unsafe_ptr_arg_deref)]
+	fn apply_uniforms_from_bytes(&mut self, uniform_ptr: *const u8, size: usize) {
+		self.uniforms.clear();
+		if !uniform_ptr.is_null() && size > 0 {
+			// SAFETY: callers pass a pointer to `size` readable bytes, the same contract as the GL backend
+			self.uniforms.extend_from_slice(unsafe { std::slice::from_raw_parts(uniform_ptr, size) });
+		}
+	}
+
+	fn clear(&mut self, color: Option<(f32, f32, f32, f32)>, _depth: Option<f32>, _stencil: Option<i32>) {
+		if let Some(color) = color {
+			self.record.0.borrow_mut().frame.clears.push(RecordedClear { render_pass: self.render_pass, color });
+		}
+	}
+
+	fn begin_default_pass(&mut self, action: PassAction) {
+		self.begin_pass(None, action);
+	}
+
+	fn begin_pass(&mut self, pass: Option<RenderPass>, action: PassAction) {
+		self.render_pass = pass;
+		self.viewport = None;
+		self.scissor = None;
+
+		if let PassAction::Clear { color, depth, stencil } = action {
+			self.clear(color, depth, stencil);
+		}
+	}
+
+	fn end_render_pass(&mut self) {
+		self.render_pass = None;
+	}
+
+	fn commit_frame(&mut self) {
+		let mut record = self.record.0.borrow_mut();
+		record.last_frame = std::mem::take(&mut record.frame);
+		record.frames += 1;
+	}
+
+	fn draw(&self, base_element: i32, num_elements: i32, num_instances: i32) {
+		let bindings = self.bindings.as_ref();
+
+		self.record.0.borrow_mut().frame.draws.push(RecordedDraw {
+			render_pass: self.render_pass,
+			pipeline: self.pipeline,
+			vertex_buffers: bindings.map_or_else(Vec::new, |b| b.vertex_buffers.clone()),
+			index_buffer: bindings.map(|b| b.index_buffer),
+			textures: bindings.map_or_else(Vec::new, |b| b.images.clone()),
+			viewport: self.viewport,
+			scissor: self.scissor,
+			uniforms: self.uniforms.clone(),
+			base_element,
+			num_elements,
+			num_instances,
+		});
+	}
+}
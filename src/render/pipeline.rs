@@ -4,9 +4,16 @@ use glam::{vec2, vec3};
 use miniquad::*;
 use std::collections::BTreeMap;
 
-#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct GlPipeline(usize);
 
+impl GlPipeline {
+	/// Default pipelines share a single shader, and live as long as the backend itself
+	pub(crate) fn is_default(self) -> bool {
+		self.0 <= PipelineStorage::LINES_DEPTH_PIPELINE.0
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DrawMode {
 	Triangles,
@@ -37,7 +44,6 @@ pub struct DrawCall {
 	pub pipeline: GlPipeline,
 	pub uniforms: Option<Vec<u8>>,
 	pub render_pass: Option<RenderPass>,
-	pub capture: bool,
 }
 
 impl DrawCall {
@@ -64,7 +70,6 @@ impl DrawCall {
 			pipeline,
 			uniforms,
 			render_pass,
-			capture: false,
 		}
 	}
 
@@ -116,6 +121,7 @@ impl GlState {
 #[derive(Clone)]
 pub struct PipelineExt {
 	pub pipeline: miniquad::Pipeline,
+	pub shader: ShaderId,
 	pub uniforms: Vec<Uniform>,
 	pub uniforms_data: Vec<u8>,
 	pub textures: Vec<String>,
@@ -272,6 +278,7 @@ impl PipelineStorage {
 
 		self.pipelines[id] = Some(PipelineExt {
 			pipeline,
+			shader,
 			uniforms,
 			uniforms_data: vec![0; max_offset],
 			textures,
@@ -294,8 +301,15 @@ impl PipelineStorage {
 		self.pipelines[pip.0].as_mut().unwrap()
 	}
 
-	pub fn delete_pipeline(&mut self, pip: GlPipeline) {
-		self.pipelines[pip.0] = None;
+	pub fn delete_pipeline(&mut self, ctx: &mut dyn RenderingBackend, pip: GlPipeline) {
+		if pip.is_default() {
+			return;
+		}
+
+		if let Some(pipeline) = self.pipelines[pip.0].take() {
+			ctx.delete_pipeline(pipeline.pipeline);
+			ctx.delete_shader(pipeline.shader);
+		}
 	}
 }
 
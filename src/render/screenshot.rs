@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use bevy_ecs::{
+	entity::Entity,
+	event::{Event, EventReader, EventWriter},
+	system::{NonSendMut, Query},
+};
+
+use super::{camera::RenderTarget, RenderingBackend};
+
+/// Requests a capture of the current frame, delivered as a [`ScreenshotCaptured`] event once the frame is drawn.
+///
+/// Send it before [`MiniquadEndDraw`](crate::window::state::MiniquadEndDraw), usually from `Update`
+#[derive(Debug, Clone, Default, Event)]
+pub struct Screenshot {
+	/// Camera whose [`RenderTarget`] is captured, the window when `None`
+	pub camera: Option<Entity>,
+	/// Also saves the capture as a PNG file, needs the `png` feature
+	pub path: Option<PathBuf>,
+}
+
+impl Screenshot {
+	/// Captures the window
+	pub fn window() -> Self {
+		Self::default()
+	}
+
+	/// Captures the render target of a camera
+	pub fn camera(camera: Entity) -> Self {
+		Self { camera: Some(camera), path: None }
+	}
+
+	pub fn save_to(mut self, path: impl Into<PathBuf>) -> Self {
+		self.path = Some(path.into());
+		self
+	}
+}
+
+/// A frame captured for a [`Screenshot`] request
+#[derive(Debug, Clone, Event)]
+pub struct ScreenshotCaptured {
+	/// Camera whose render target was captured, the window when `None`
+	pub camera: Option<Entity>,
+	/// Pixels as seen on screen, top row first
+	pub image: image::RgbaImage,
+}
+
+/// Reads back the requested frames, after everything was drawn but before the frame is committed
+pub(crate) fn capture_screenshots(mut requests: EventReader<Screenshot>, mut captured: EventWriter<ScreenshotCaptured>, mut render_ctx: NonSendMut<RenderingBackend>, targets: Query<&RenderTarget>) {
+	for request in requests.read() {
+		let pixels = match request.camera.map(|camera| targets.get(camera)) {
+			None | Some(Ok(RenderTarget::Window)) => render_ctx.read_screen_pixels(),
+			Some(Ok(RenderTarget::Texture { colour_texture, .. })) => read_texture_pixels(&mut render_ctx, *colour_texture),
+			Some(Err(_err)) => {
+				#[cfg(feature = "log")]
+				bevy_log::error!("Failed to capture a screenshot, the camera has no render target: {}", _err);
+				continue;
+			}
+		};
+
+		let Some(image) = pixels.and_then(|(width, height, bytes)| image::RgbaImage::from_raw(width, height, bytes)) else {
+			#[cfg(feature = "log")]
+			bevy_log::error!("Failed to capture a screenshot, the rendering backend can't read it back");
+			continue;
+		};
+
+		if let Some(path) = &request.path {
+			if let Err(_err) = image.save_with_format(path, image::ImageFormat::Png) {
+				#[cfg(feature = "log")]
+				bevy_log::error!("Failed to save a screenshot to {}: {}", path.display(), _err);
+			}
+		}
+
+		captured.send(ScreenshotCaptured { camera: request.camera, image });
+	}
+}
+
+/// Reads an `RGBA8` render target, flipped so the top row comes first like on screen
+fn read_texture_pixels(render_ctx: &mut RenderingBackend, texture: miniquad::TextureId) -> Option<(u32, u32, Vec<u8>)> {
+	let params = render_ctx.texture_params(texture);
+	if params.format != miniquad::TextureFormat::RGBA8 {
+		return None;
+	}
+
+	let mut bytes = vec![0; params.width as usize * params.height as usize * 4];
+	render_ctx.texture_read_pixels(texture, &mut bytes);
+
+	Some((params.width, params.height, super::flip_rows(bytes, params.width)))
+}
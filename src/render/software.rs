@@ -0,0 +1,735 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use glam::{vec2, vec4, Mat4, Vec2, Vec4};
+use miniquad::*;
+
+use super::geometry::Vertex;
+use super::null::{NullBackend, NullTexture, RenderRecord};
+
+/// Copies of the geometry [`RenderingBackend`](super::RenderingBackend) uploads, since `miniquad` buffers can't be read back
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GeometryMirror(Rc<RefCell<Geometry>>);
+
+#[derive(Debug, Default)]
+struct Geometry {
+	vertices: HashMap<BufferId, Vec<Vertex>>,
+	indices: HashMap<BufferId, Vec<u16>>,
+}
+
+impl GeometryMirror {
+	pub(crate) fn upload(&self, vertex_buffer: BufferId, vertices: &[Vertex], index_buffer: BufferId, indices: &[u16]) {
+		let mut geometry = self.0.borrow_mut();
+		geometry.vertices.insert(vertex_buffer, vertices.to_vec());
+		geometry.indices.insert(index_buffer, indices.to_vec());
+	}
+}
+
+#[derive(Debug)]
+struct Pixels {
+	width: u32,
+	height: u32,
+	/// RGBA8, bottom row first like OpenGL
+	bytes: Vec<u8>,
+}
+
+/// Shared handle to the window framebuffer of a [`SoftwareBackend`]
+#[derive(Debug, Clone)]
+pub struct SoftwareScreen(Rc<RefCell<Pixels>>);
+
+impl SoftwareScreen {
+	pub fn size(&self) -> (u32, u32) {
+		let pixels = self.0.borrow();
+		(pixels.width, pixels.height)
+	}
+
+	/// RGBA8 pixels of the screen, top row first. Draws are only visible once their render pass ended
+	pub fn read_pixels(&self) -> Vec<u8> {
+		let pixels = self.0.borrow();
+		super::flip_rows(pixels.bytes.clone(), pixels.width)
+	}
+
+	pub fn to_image(&self) -> image::RgbaImage {
+		let (width, height) = self.size();
+		image::RgbaImage::from_raw(width, height, self.read_pixels()).expect("Screen size matches its pixels")
+	}
+
+	/// Saves the screen as a PNG image
+	#[cfg(feature = "png")]
+	pub fn save_png(&self, path: impl AsRef<std::path::Path>) -> image::ImageResult<()> {
+		self.to_image().save_with_format(path, image::ImageFormat::Png)
+	}
+}
+
+/// A draw waiting to be rasterized, `miniquad` only gives shared access to the backend when drawing
+struct PendingDraw {
+	render_pass: Option<RenderPass>,
+	params: PipelineParams,
+	transform: Mat4,
+	vertices: Vec<Vertex>,
+	indices: Vec<u16>,
+	texture: Option<TextureId>,
+	viewport: Option<(i32, i32, i32, i32)>,
+	scissor: Option<(i32, i32, i32, i32)>,
+}
+
+/// A [`miniquad::RenderingBackend`] rasterizing on the CPU, for pixel-exact tests on machines without a GPU.
+///
+/// Follows the semantics of quadify's default shader: vertex positions are transformed by the `Projection` and `Model` uniforms,
+/// and colors are the vertex color times the `Texture` sample. Custom shaders are drawn the same way.
+/// Supports triangles and lines, `RGBA8`, `RGB8` and `Alpha` textures with nearest or linear filtering, blending, color masks, culling,
+/// viewports and scissor rectangles. Depth and stencil tests are ignored.
+///
+/// Only geometry uploaded by [`RenderingBackend`](super::RenderingBackend) itself is drawn, as `miniquad` buffers can't be read back.
+/// Objects and draw calls are also tracked by an inner [`NullBackend`]
+pub struct SoftwareBackend {
+	null: NullBackend,
+	geometry: GeometryMirror,
+	screen: SoftwareScreen,
+	pending: RefCell<Vec<PendingDraw>>,
+}
+
+impl SoftwareBackend {
+	/// A backend drawing into a `width` by `height` screen, cleared to transparent black
+	pub fn new(width: u32, height: u32) -> Self {
+		Self {
+			null: NullBackend::new(),
+			geometry: GeometryMirror::default(),
+			screen: SoftwareScreen(Rc::new(RefCell::new(Pixels {
+				width,
+				height,
+				bytes: vec![0; width as usize * height as usize * 4],
+			}))),
+			pending: RefCell::new(Vec::new()),
+		}
+	}
+
+	/// Handle to the screen, it stays valid after the backend is boxed into a [`RenderingBackend`](super::RenderingBackend)
+	pub fn screen(&self) -> SoftwareScreen {
+		self.screen.clone()
+	}
+
+	/// Handle to the recorded objects and draw calls
+	pub fn record(&self) -> RenderRecord {
+		self.null.record()
+	}
+
+	pub(crate) fn geometry(&self) -> GeometryMirror {
+		self.geometry.clone()
+	}
+
+	/// Fills the color attachment of a render pass, `None` being the screen
+	fn fill(&mut self, render_pass: Option<RenderPass>, color: (f32, f32, f32, f32)) {
+		let color = to_bytes(Vec4::from(color));
+
+		match render_pass {
+			None => self.screen.0.borrow_mut().bytes.chunks_exact_mut(4).for_each(|pixel| pixel.copy_from_slice(&color)),
+			Some(pass) => {
+				if let Some(texture) = self.color_attachment(pass).and_then(|texture| self.null.textures.get_mut(&texture)) {
+					if texture.params.format == TextureFormat::RGBA8 {
+						texture.bytes.chunks_exact_mut(4).for_each(|pixel| pixel.copy_from_slice(&color));
+					}
+				}
+			}
+		}
+	}
+
+	fn color_attachment(&self, render_pass: RenderPass) -> Option<TextureId> {
+		self.null
+			.render_passes
+			.iter()
+			.find(|(pass, ..)| *pass == render_pass)
+			.and_then(|(_, textures, _)| textures.first().copied())
+	}
+
+	/// Rasterizes pending draws
+	fn flush(&mut self) {
+		let pending = std::mem::take(self.pending.get_mut());
+
+		for draw in pending {
+			match draw.render_pass {
+				None => {
+					let screen = self.screen.clone();
+					let mut screen = screen.0.borrow_mut();
+					let (width, height) = (screen.width, screen.height);
+					rasterize(&draw, &mut screen.bytes, width, height, &self.null.textures);
+				}
+				Some(pass) => {
+					let Some(target) = self.color_attachment(pass) else {
+						continue;
+					};
+
+					// Taken out of the texture map, so other textures can be sampled while drawing
+					let Some((mut bytes, params)) = self.null.textures.get_mut(&target).map(|texture| (std::mem::take(&mut texture.bytes), texture.params)) else {
+						continue;
+					};
+
+					if params.format == TextureFormat::RGBA8 {
+						rasterize(&draw, &mut bytes, params.width, params.height, &self.null.textures);
+					}
+
+					if let Some(texture) = self.null.textures.get_mut(&target) {
+						texture.bytes = bytes;
+					}
+				}
+			}
+		}
+	}
+}
+
+impl miniquad::RenderingBackend for SoftwareBackend {
+	fn info(&self) -> ContextInfo {
+		ContextInfo {
+			gl_version_string: String::from("Software"),
+			..self.null.info()
+		}
+	}
+
+	fn new_shader(&mut self, shader: ShaderSource, meta: ShaderMeta) -> Result<ShaderId, ShaderError> {
+		self.null.new_shader(shader, meta)
+	}
+
+	fn new_texture(&mut self, access: TextureAccess, data: TextureSource, params: TextureParams) -> TextureId {
+		self.null.new_texture(access, data, params)
+	}
+
+	fn texture_params(&self, texture: TextureId) -> TextureParams {
+		self.null.texture_params(texture)
+	}
+
+	unsafe fn texture_raw_id(&self, texture: TextureId) -> RawId {
+		self.null.texture_raw_id(texture)
+	}
+
+	fn texture_set_min_filter(&mut self, texture: TextureId, filter: FilterMode, mipmap_filter: MipmapFilterMode) {
+		self.flush();
+		self.null.texture_set_min_filter(texture, filter, mipmap_filter);
+	}
+
+	fn texture_set_mag_filter(&mut self, texture: TextureId, filter: FilterMode) {
+		self.flush();
+		self.null.texture_set_mag_filter(texture, filter);
+	}
+
+	fn texture_set_wrap(&mut self, texture: TextureId, wrap_x: TextureWrap, wrap_y: TextureWrap) {
+		self.flush();
+		self.null.texture_set_wrap(texture, wrap_x, wrap_y);
+	}
+
+	fn texture_generate_mipmaps(&mut self, texture: TextureId) {
+		self.null.texture_generate_mipmaps(texture);
+	}
+
+	fn texture_resize(&mut self, texture: TextureId, width: u32, height: u32, bytes: Option<&[u8]>) {
+		self.flush();
+		self.null.texture_resize(texture, width, height, bytes);
+	}
+
+	fn texture_read_pixels(&mut self, texture: TextureId, bytes: &mut [u8]) {
+		self.flush();
+		self.null.texture_read_pixels(texture, bytes);
+	}
+
+	fn texture_update_part(&mut self, texture: TextureId, x_offset: i32, y_offset: i32, width: i32, height: i32, bytes: &[u8]) {
+		self.flush();
+		self.null.texture_update_part(texture, x_offset, y_offset, width, height, bytes);
+	}
+
+	fn new_render_pass_mrt(&mut self, color_img: &[TextureId], resolve_img: Option<&[TextureId]>, depth_img: Option<TextureId>) -> RenderPass {
+		self.null.new_render_pass_mrt(color_img, resolve_img, depth_img)
+	}
+
+	fn render_pass_color_attachments(&self, render_pass: RenderPass) -> &[TextureId] {
+		self.null.render_pass_color_attachments(render_pass)
+	}
+
+	fn delete_render_pass(&mut self, render_pass: RenderPass) {
+		self.flush();
+		self.null.delete_render_pass(render_pass);
+	}
+
+	fn new_pipeline(&mut self, buffer_layout: &[BufferLayout], attributes: &[VertexAttribute], shader: ShaderId, params: PipelineParams) -> Pipeline {
+		self.null.new_pipeline(buffer_layout, attributes, shader, params)
+	}
+
+	fn apply_pipeline(&mut self, pipeline: &Pipeline) {
+		self.null.apply_pipeline(pipeline);
+	}
+
+	fn delete_pipeline(&mut self, pipeline: Pipeline) {
+		self.null.delete_pipeline(pipeline);
+	}
+
+	fn new_buffer(&mut self, type_: BufferType, usage: BufferUsage, data: BufferSource) -> BufferId {
+		self.null.new_buffer(type_, usage, data)
+	}
+
+	fn buffer_update(&mut self, buffer: BufferId, data: BufferSource) {
+		self.null.buffer_update(buffer, data);
+	}
+
+	fn buffer_size(&mut self, buffer: BufferId) -> usize {
+		self.null.buffer_size(buffer)
+	}
+
+	fn delete_buffer(&mut self, buffer: BufferId) {
+		let mut geometry = self.geometry.0.borrow_mut();
+		geometry.vertices.remove(&buffer);
+		geometry.indices.remove(&buffer);
+		drop(geometry);
+
+		self.null.delete_buffer(buffer);
+	}
+
+	fn delete_texture(&mut self, texture: TextureId) {
+		self.flush();
+		self.null.delete_texture(texture);
+	}
+
+	fn delete_shader(&mut self, program: ShaderId) {
+		self.null.delete_shader(program);
+	}
+
+	fn apply_viewport(&mut self, x: i32, y: i32, w: i32, h: i32) {
+		self.null.apply_viewport(x, y, w, h);
+	}
+
+	fn apply_scissor_rect(&mut self, x: i32, y: i32, w: i32, h: i32) {
+		self.null.apply_scissor_rect(x, y, w, h);
+	}
+
+	fn apply_bindings_from_slice(&mut self, vertex_buffers: &[BufferId], index_buffer: BufferId, textures: &[TextureId]) {
+		self.null.apply_bindings_from_slice(vertex_buffers, index_buffer, textures);
+	}
+
+	#[allow(clippy::not_unsafe_ptr_arg_deref)]
+	fn apply_uniforms_from_bytes(&mut self, uniform_ptr: *const u8, size: usize) {
+		self.null.apply_uniforms_from_bytes(uniform_ptr, size);
+	}
+
+	fn clear(&mut self, color: Option<(f32, f32, f32, f32)>, depth: Option<f32>, stencil: Option<i32>) {
+		self.flush();
+		self.null.clear(color, depth, stencil);
+
+		if let Some(color) = color {
+			self.fill(self.null.render_pass, color);
+		}
+	}
+
+	fn begin_default_pass(&mut self, action: PassAction) {
+		self.begin_pass(None, action);
+	}
+
+	fn begin_pass(&mut self, pass: Option<RenderPass>, action: PassAction) {
+		self.flush();
+		self.null.begin_pass(pass, PassAction::Nothing);
+
+		if let PassAction::Clear { color, depth, stencil } = action {
+			self.clear(color, depth, stencil);
+		}
+	}
+
+	fn end_render_pass(&mut self) {
+		self.flush();
+		self.null.end_render_pass();
+	}
+
+	fn commit_frame(&mut self) {
+		self.flush();
+		self.null.commit_frame();
+	}
+
+	fn draw(&self, base_element: i32, num_elements: i32, num_instances: i32) {
+		self.null.draw(base_element, num_elements, num_instances);
+
+		let (Some(bindings), Some(params)) = (&self.null.bindings, self.null.pipeline.and_then(|pipeline| self.null.pipelines.get(&pipeline))) else {
+			return;
+		};
+
+		let geometry = self.geometry.0.borrow();
+		let (Some(vertices), Some(indices)) = (
+			bindings.vertex_buffers.first().and_then(|buffer| geometry.vertices.get(buffer)),
+			geometry.indices.get(&bindings.index_buffer),
+		) else {
+			return;
+		};
+
+		let start = (base_element.max(0) as usize).min(indices.len());
+		let end = (start + num_elements.max(0) as usize).min(indices.len());
+
+		self.pending.borrow_mut().push(PendingDraw {
+			render_pass: self.null.render_pass,
+			params: *params,
+			transform: uniform_matrix(&self.null.uniforms, 0) * uniform_matrix(&self.null.uniforms, 1),
+			vertices: vertices.clone(),
+			indices: indices[start..end].to_vec(),
+			texture: bindings.images.first().copied(),
+			viewport: self.null.viewport,
+			scissor: self.null.scissor,
+		});
+	}
+}
+
+/// Reads the `index`th matrix of the uniforms, `Projection` and `Model` always come first
+fn uniform_matrix(uniforms: &[u8], index: usize) -> Mat4 {
+	let mut floats = [0.0; 16];
+	match uniforms.get(index * 64..(index + 1) * 64) {
+		Some(bytes) => {
+			for (float, bytes) in floats.iter_mut().zip(bytes.chunks_exact(4)) {
+				*float = f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+			}
+			Mat4::from_cols_array(&floats)
+		}
+		None => Mat4::IDENTITY,
+	}
+}
+
+fn to_bytes(color: Vec4) -> [u8; 4] {
+	let color = (color.clamp(Vec4::ZERO, Vec4::ONE) * 255.0).round();
+	[color.x as u8, color.y as u8, color.z as u8, color.w as u8]
+}
+
+/// A vertex after transformation, in window coordinates with attributes divided by `w`
+#[derive(Clone, Copy)]
+struct ScreenVertex {
+	position: Vec2,
+	z: f32,
+	inv_w: f32,
+	uv: Vec2,
+	color: Vec4,
+}
+
+impl ScreenVertex {
+	fn lerp(&self, other: &Self, t: f32) -> Self {
+		Self {
+			position: self.position.lerp(other.position, t),
+			z: self.z + (other.z - self.z) * t,
+			inv_w: self.inv_w + (other.inv_w - self.inv_w) * t,
+			uv: self.uv.lerp(other.uv, t),
+			color: self.color.lerp(other.color, t),
+		}
+	}
+}
+
+/// Pixels a draw may write to, as `[min, max)` in window coordinates
+#[derive(Clone, Copy)]
+struct Bounds {
+	min_x: i32,
+	min_y: i32,
+	max_x: i32,
+	max_y: i32,
+}
+
+impl Bounds {
+	fn intersect(self, (x, y, w, h): (i32, i32, i32, i32)) -> Self {
+		Self {
+			min_x: self.min_x.max(x),
+			min_y: self.min_y.max(y),
+			max_x: self.max_x.min(x + w),
+			max_y: self.max_y.min(y + h),
+		}
+	}
+}
+
+struct Target<'a> {
+	bytes: &'a mut [u8],
+	width: u32,
+	bounds: Bounds,
+	params: &'a PipelineParams,
+	blend: Option<(Blend, Blend)>,
+	texture: Option<Sampler<'a>>,
+}
+
+impl Target<'_> {
+	fn shade(&mut self, x: i32, y: i32, vertex: &ScreenVertex) {
+		if x < self.bounds.min_x || x >= self.bounds.max_x || y < self.bounds.min_y || y >= self.bounds.max_y {
+			return;
+		}
+
+		// Outside of the clip volume
+		let w = 1.0 / vertex.inv_w;
+		let z = vertex.z * w;
+		if !(-1.0..=1.0).contains(&z) {
+			return;
+		}
+
+		let (uv, color) = (vertex.uv * w, vertex.color * w);
+		let source = color * self.texture.as_ref().map_or(Vec4::ONE, |texture| texture.sample(uv));
+
+		let index = (y as usize * self.width as usize + x as usize) * 4;
+		let pixel = &mut self.bytes[index..index + 4];
+		let destination = Vec4::from_array(std::array::from_fn(|i| pixel[i] as f32 / 255.0));
+
+		let result = match self.blend {
+			Some((color, alpha)) => {
+				let rgb = color.apply(source, destination);
+				let a = alpha.apply(source, destination);
+				vec4(rgb.x, rgb.y, rgb.z, a.w)
+			}
+			None => source,
+		};
+
+		let result = to_bytes(result);
+		let (r, g, b, a) = self.params.color_write;
+		for (channel, write) in [r, g, b, a].into_iter().enumerate() {
+			if write {
+				pixel[channel] = result[channel];
+			}
+		}
+	}
+}
+
+fn rasterize(draw: &PendingDraw, bytes: &mut [u8], width: u32, height: u32, textures: &HashMap<TextureId, NullTexture>) {
+	let viewport = draw.viewport.unwrap_or((0, 0, width as i32, height as i32));
+	let mut bounds = Bounds {
+		min_x: 0,
+		min_y: 0,
+		max_x: width as i32,
+		max_y: height as i32,
+	}
+	.intersect(viewport);
+	if let Some(scissor) = draw.scissor {
+		bounds = bounds.intersect(scissor);
+	}
+
+	let vertices: Vec<_> = draw
+		.vertices
+		.iter()
+		.map(|vertex| {
+			let clip = draw.transform * vertex.position.extend(1.0);
+			let inv_w = 1.0 / clip.w;
+			let ndc = clip.truncate() * inv_w;
+
+			ScreenVertex {
+				position: vec2(viewport.0 as f32 + (ndc.x + 1.0) * viewport.2 as f32 / 2.0, viewport.1 as f32 + (ndc.y + 1.0) * viewport.3 as f32 / 2.0),
+				z: ndc.z * inv_w,
+				inv_w,
+				uv: vertex.uv * inv_w,
+				color: vertex.color.to_float() * inv_w,
+			}
+		})
+		.collect();
+
+	let mut target = Target {
+		bytes,
+		width,
+		bounds,
+		params: &draw.params,
+		blend: draw.params.color_blend.map(|color| (Blend::from(color), Blend::from(draw.params.alpha_blend.unwrap_or(color)))),
+		texture: draw.texture.and_then(|texture| textures.get(&texture)).map(|texture| Sampler {
+			bytes: &texture.bytes,
+			params: texture.params,
+		}),
+	};
+
+	let vertex = |index: &u16| vertices.get(*index as usize);
+	match draw.params.primitive_type {
+		PrimitiveType::Triangles => {
+			for triangle in draw.indices.chunks_exact(3) {
+				if let (Some(a), Some(b), Some(c)) = (vertex(&triangle[0]), vertex(&triangle[1]), vertex(&triangle[2])) {
+					triangle_fill(&mut target, [a, b, c]);
+				}
+			}
+		}
+		PrimitiveType::Lines => {
+			for line in draw.indices.chunks_exact(2) {
+				if let (Some(a), Some(b)) = (vertex(&line[0]), vertex(&line[1])) {
+					line_fill(&mut target, a, b);
+				}
+			}
+		}
+		PrimitiveType::Points => {}
+	}
+}
+
+/// Fills the pixels whose centers are inside the triangle, following OpenGL's top-left rule so shared edges are drawn once
+fn triangle_fill(target: &mut Target, [a, b, c]: [&ScreenVertex; 3]) {
+	if a.inv_w <= 0.0 || b.inv_w <= 0.0 || c.inv_w <= 0.0 {
+		return;
+	}
+
+	let edge = |from: Vec2, to: Vec2, point: Vec2| (to - from).perp_dot(point - from);
+	let area = edge(a.position, b.position, c.position);
+	if area == 0.0 {
+		return;
+	}
+
+	let counter_clockwise = area > 0.0;
+	let front = counter_clockwise == (target.params.front_face_order == FrontFaceOrder::CounterClockwise);
+	match target.params.cull_face {
+		CullFace::Back if !front => return,
+		CullFace::Front if front => return,
+		_ => {}
+	}
+
+	// Wound counter-clockwise from here on
+	let (b, c) = if counter_clockwise { (b, c) } else { (c, b) };
+	let area = area.abs();
+
+	// Edges on the top or the left of the triangle own the pixels exactly on them
+	let owns = |from: Vec2, to: Vec2| {
+		let delta = to - from;
+		(delta.y == 0.0 && delta.x < 0.0) || delta.y < 0.0
+	};
+	let edges = [(b.position, c.position), (c.position, a.position), (a.position, b.position)];
+
+	let min = a.position.min(b.position).min(c.position);
+	let max = a.position.max(b.position).max(c.position);
+	let bounds = target.bounds;
+
+	for y in (min.y.floor() as i32).max(bounds.min_y)..(max.y.ceil() as i32).min(bounds.max_y) {
+		for x in (min.x.floor() as i32).max(bounds.min_x)..(max.x.ceil() as i32).min(bounds.max_x) {
+			let point = vec2(x as f32 + 0.5, y as f32 + 0.5);
+			let weights = edges.map(|(from, to)| edge(from, to, point));
+
+			if edges.iter().zip(weights).any(|(&(from, to), weight)| weight < 0.0 || (weight == 0.0 && !owns(from, to))) {
+				continue;
+			}
+
+			let [wa, wb, wc] = weights.map(|weight| weight / area);
+			let interpolated = ScreenVertex {
+				position: point,
+				z: a.z * wa + b.z * wb + c.z * wc,
+				inv_w: a.inv_w * wa + b.inv_w * wb + c.inv_w * wc,
+				uv: a.uv * wa + b.uv * wb + c.uv * wc,
+				color: a.color * wa + b.color * wb + c.color * wc,
+			};
+
+			target.shade(x, y, &interpolated);
+		}
+	}
+}
+
+/// One pixel wide line, the last pixel is left out so connected lines don't overlap
+fn line_fill(target: &mut Target, a: &ScreenVertex, b: &ScreenVertex) {
+	if a.inv_w <= 0.0 || b.inv_w <= 0.0 {
+		return;
+	}
+
+	let delta = b.position - a.position;
+	let steps = delta.x.abs().max(delta.y.abs()).round() as usize;
+
+	for step in 0..steps {
+		let point = a.lerp(b, (step as f32 + 0.5) / steps as f32);
+		target.shade(point.position.x.floor() as i32, point.position.y.floor() as i32, &point);
+	}
+}
+
+/// Blend equation and factors of a [`BlendState`]
+#[derive(Clone, Copy)]
+struct Blend {
+	equation: Equation,
+	source: BlendFactor,
+	destination: BlendFactor,
+}
+
+impl From<BlendState> for Blend {
+	fn from(state: BlendState) -> Self {
+		const VALUES: [BlendValue; 4] = [BlendValue::SourceColor, BlendValue::SourceAlpha, BlendValue::DestinationColor, BlendValue::DestinationAlpha];
+		let factors = [BlendFactor::Zero, BlendFactor::One, BlendFactor::SourceAlphaSaturate]
+			.into_iter()
+			.chain(VALUES.map(BlendFactor::Value))
+			.chain(VALUES.map(BlendFactor::OneMinusValue));
+
+		// The fields of `BlendState` are private, it's matched against every combination instead
+		for equation in [Equation::Add, Equation::Subtract, Equation::ReverseSubtract] {
+			for source in factors.clone() {
+				for destination in factors.clone() {
+					if BlendState::new(equation, source, destination) == state {
+						return Self { equation, source, destination };
+					}
+				}
+			}
+		}
+
+		unreachable!("Every blend state is a combination of its equation and factors")
+	}
+}
+
+impl Blend {
+	fn apply(&self, source: Vec4, destination: Vec4) -> Vec4 {
+		let factor = |factor: BlendFactor| {
+			let value = |value: BlendValue| match value {
+				BlendValue::SourceColor => source,
+				BlendValue::SourceAlpha => Vec4::splat(source.w),
+				BlendValue::DestinationColor => destination,
+				BlendValue::DestinationAlpha => Vec4::splat(destination.w),
+			};
+
+			match factor {
+				BlendFactor::Zero => Vec4::ZERO,
+				BlendFactor::One => Vec4::ONE,
+				BlendFactor::Value(v) => value(v),
+				BlendFactor::OneMinusValue(v) => Vec4::ONE - value(v),
+				BlendFactor::SourceAlphaSaturate => {
+					let f = source.w.min(1.0 - destination.w);
+					vec4(f, f, f, 1.0)
+				}
+			}
+		};
+
+		let (source, destination) = (source * factor(self.source), destination * factor(self.destination));
+		match self.equation {
+			Equation::Add => source + destination,
+			Equation::Subtract => source - destination,
+			Equation::ReverseSubtract => destination - source,
+		}
+	}
+}
+
+/// Samples a texture like OpenGL would, without mipmaps
+struct Sampler<'a> {
+	bytes: &'a [u8],
+	params: TextureParams,
+}
+
+impl Sampler<'_> {
+	fn sample(&self, uv: Vec2) -> Vec4 {
+		let (width, height) = (self.params.width as i32, self.params.height as i32);
+		if width == 0 || height == 0 {
+			return Vec4::ZERO;
+		}
+
+		let position = uv * vec2(width as f32, height as f32);
+		match self.params.mag_filter {
+			FilterMode::Nearest => self.texel(position.x.floor() as i32, position.y.floor() as i32),
+			FilterMode::Linear => {
+				let position = position - 0.5;
+				let (x, y) = (position.x.floor(), position.y.floor());
+				let (tx, ty) = (position.x - x, position.y - y);
+				let (x, y) = (x as i32, y as i32);
+
+				let top = self.texel(x, y).lerp(self.texel(x + 1, y), tx);
+				let bottom = self.texel(x, y + 1).lerp(self.texel(x + 1, y + 1), tx);
+				top.lerp(bottom, ty)
+			}
+		}
+	}
+
+	fn texel(&self, x: i32, y: i32) -> Vec4 {
+		let wrap = |coordinate: i32, size: i32| match self.params.wrap {
+			TextureWrap::Clamp => coordinate.clamp(0, size - 1),
+			TextureWrap::Repeat => coordinate.rem_euclid(size),
+			TextureWrap::Mirror => {
+				let coordinate = coordinate.rem_euclid(size * 2);
+				if coordinate < size {
+					coordinate
+				} else {
+					size * 2 - 1 - coordinate
+				}
+			}
+		};
+
+		let (x, y) = (wrap(x, self.params.width as i32) as usize, wrap(y, self.params.height as i32) as usize);
+		let index = y * self.params.width as usize + x;
+		let byte = |offset: usize| self.bytes.get(offset).map_or(0.0, |byte| *byte as f32 / 255.0);
+
+		match self.params.format {
+			TextureFormat::RGBA8 => vec4(byte(index * 4), byte(index * 4 + 1), byte(index * 4 + 2), byte(index * 4 + 3)),
+			TextureFormat::RGB8 => vec4(byte(index * 3), byte(index * 3 + 1), byte(index * 3 + 2), 1.0),
+			TextureFormat::Alpha => vec4(1.0, 1.0, 1.0, byte(index)),
+			_ => Vec4::ZERO,
+		}
+	}
+}
@@ -0,0 +1,59 @@
+use bevy_ecs::component::Component;
+use glam::{vec2, Mat4, Quat, Vec2, Vec3};
+
+/// Position, rotation and scale of a 2D entity. `translation.z` is only used to sort entities, higher values are drawn on top
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct Transform2D {
+	pub translation: Vec3,
+	/// Rotation in radians, counter-clockwise
+	pub rotation: f32,
+	pub scale: Vec2,
+}
+
+impl Default for Transform2D {
+	fn default() -> Self {
+		Self {
+			translation: Vec3::ZERO,
+			rotation: 0.0,
+			scale: Vec2::ONE,
+		}
+	}
+}
+
+impl Transform2D {
+	pub fn from_xy(x: f32, y: f32) -> Self {
+		Self::from_translation(Vec3::new(x, y, 0.0))
+	}
+
+	pub fn from_translation(translation: Vec3) -> Self {
+		Self { translation, ..Default::default() }
+	}
+
+	pub fn with_rotation(mut self, rotation: f32) -> Self {
+		self.rotation = rotation;
+		self
+	}
+
+	pub fn with_scale(mut self, scale: Vec2) -> Self {
+		self.scale = scale;
+		self
+	}
+
+	pub fn with_z(mut self, z: f32) -> Self {
+		self.translation.z = z;
+		self
+	}
+
+	/// Applies scale, rotation and translation (in that order) to a local point
+	pub fn transform_point(&self, point: Vec2) -> Vec2 {
+		let (sin, cos) = self.rotation.sin_cos();
+		let scaled = point * self.scale;
+
+		vec2(scaled.x * cos - scaled.y * sin, scaled.x * sin + scaled.y * cos) + self.translation.truncate()
+	}
+
+	/// Model matrix of this transform, with the z component dropped
+	pub fn matrix(&self) -> Mat4 {
+		Mat4::from_scale_rotation_translation(self.scale.extend(1.0), Quat::from_rotation_z(self.rotation), self.translation.truncate().extend(0.0))
+	}
+}
@@ -0,0 +1,177 @@
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::{
+	component::Component,
+	system::{Local, Query},
+};
+use bevy_reflect::TypePath;
+use glam::{vec2, Vec2};
+
+use crate::render::geometry::Rect;
+
+/// Regions of a sprite sheet, in pixels. Pair it with an [`AtlasIndex`] to draw one of the regions with a [`Sprite`](super::Sprite)
+#[derive(Asset, TypePath, Debug, Clone, Default, PartialEq)]
+pub struct TextureAtlasLayout {
+	pub textures: Vec<Rect>,
+}
+
+impl TextureAtlasLayout {
+	/// Makes a layout of equally sized tiles, ordered from the top-left tile, row by row.
+	///
+	/// `padding` is the gap between neighbouring tiles, and `offset` the position of the first tile
+	pub fn from_grid(tile_size: Vec2, columns: u32, rows: u32, padding: Option<Vec2>, offset: Option<Vec2>) -> Self {
+		let padding = padding.unwrap_or(Vec2::ZERO);
+		let offset = offset.unwrap_or(Vec2::ZERO);
+
+		let mut textures = Vec::with_capacity((columns * rows) as usize);
+		for y in 0..rows {
+			for x in 0..columns {
+				let min = offset + vec2(x as f32, y as f32) * (tile_size + padding);
+				textures.push(Rect { min, max: min + tile_size });
+			}
+		}
+
+		Self { textures }
+	}
+
+	/// Makes a layout out of arbitrary regions, indices follow the order of `rects`
+	pub fn from_rects(rects: impl IntoIterator<Item = Rect>) -> Self {
+		Self {
+			textures: rects.into_iter().collect(),
+		}
+	}
+
+	/// Adds a new region to the layout, returning its index
+	pub fn add_texture(&mut self, rect: Rect) -> usize {
+		self.textures.push(rect);
+		self.textures.len() - 1
+	}
+
+	pub fn get(&self, index: usize) -> Option<Rect> {
+		self.textures.get(index).copied()
+	}
+
+	pub fn len(&self) -> usize {
+		self.textures.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.textures.is_empty()
+	}
+}
+
+/// Selects the region of a [`TextureAtlasLayout`] a [`Sprite`](super::Sprite) draws. Takes priority over [`Sprite::rect`](super::Sprite::rect)
+#[derive(Component, Debug, Clone, Default)]
+pub struct AtlasIndex {
+	pub layout: Handle<TextureAtlasLayout>,
+	pub index: usize,
+}
+
+impl AtlasIndex {
+	pub fn new(layout: Handle<TextureAtlasLayout>, index: usize) -> Self {
+		Self { layout, index }
+	}
+
+	/// Region of the texture this index points to, if the layout is loaded
+	pub fn rect(&self, layouts: &Assets<TextureAtlasLayout>) -> Option<Rect> {
+		layouts.get(&self.layout).and_then(|layout| layout.get(self.index))
+	}
+}
+
+/// What a [`SpriteAnimation`] does after its last frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnimationMode {
+	/// Starts again from the first frame
+	#[default]
+	Loop,
+	/// Stays on the last frame
+	Once,
+}
+
+/// Steps through atlas indices at a fixed rate, updating the entity's [`AtlasIndex`]
+#[derive(Component, Debug, Clone)]
+pub struct SpriteAnimation {
+	/// Atlas indices to play, in order
+	pub frames: Vec<usize>,
+	/// How long each frame is shown, in seconds
+	pub frame_time: f32,
+	pub mode: AnimationMode,
+	pub playing: bool,
+
+	current: usize,
+	elapsed: f32,
+}
+
+impl SpriteAnimation {
+	pub fn new(frames: impl IntoIterator<Item = usize>, frame_time: f32) -> Self {
+		Self {
+			frames: frames.into_iter().collect(),
+			frame_time,
+			mode: AnimationMode::Loop,
+			playing: true,
+			current: 0,
+			elapsed: 0.0,
+		}
+	}
+
+	/// Makes an animation with the given amount of frames per second
+	pub fn from_fps(frames: impl IntoIterator<Item = usize>, fps: f32) -> Self {
+		Self::new(frames, 1.0 / fps)
+	}
+
+	pub fn with_mode(mut self, mode: AnimationMode) -> Self {
+		self.mode = mode;
+		self
+	}
+
+	/// The atlas index of the current frame
+	pub fn frame(&self) -> usize {
+		self.frames.get(self.current).copied().unwrap_or(0)
+	}
+
+	/// Whether a [`AnimationMode::Once`] animation reached its last frame
+	pub fn finished(&self) -> bool {
+		self.mode == AnimationMode::Once && self.current + 1 >= self.frames.len()
+	}
+
+	/// Jumps back to the first frame
+	pub fn restart(&mut self) {
+		self.current = 0;
+		self.elapsed = 0.0;
+	}
+
+	/// Advances the animation by `delta` seconds
+	pub fn tick(&mut self, delta: f32) {
+		if !self.playing || self.frames.is_empty() || self.frame_time <= 0.0 {
+			return;
+		}
+
+		self.elapsed += delta;
+		while self.elapsed >= self.frame_time {
+			self.elapsed -= self.frame_time;
+
+			if self.current + 1 < self.frames.len() {
+				self.current += 1;
+			} else if self.mode == AnimationMode::Loop {
+				self.current = 0;
+			} else {
+				self.elapsed = 0.0;
+				break;
+			}
+		}
+	}
+}
+
+pub(crate) fn animate_sprites(mut last_time: Local<Option<f64>>, mut animations: Query<(&mut SpriteAnimation, &mut AtlasIndex)>) {
+	let now = miniquad::date::now();
+	let delta = last_time.map_or(0.0, |last| (now - last) as f32);
+	*last_time = Some(now);
+
+	for (mut animation, mut atlas) in animations.iter_mut() {
+		animation.tick(delta);
+
+		let frame = animation.frame();
+		if atlas.index != frame {
+			atlas.index = frame;
+		}
+	}
+}
@@ -0,0 +1,231 @@
+use bevy_app::{App, Plugin, Update};
+use bevy_asset::{AssetApp, AssetId, Assets, Handle};
+use bevy_ecs::{
+	component::Component,
+	entity::Entity,
+	system::{Local, NonSendMut, Query, Res, ResMut, Resource},
+};
+use glam::{vec2, vec3, Vec2};
+use miniquad::TextureId;
+
+use crate::asset::Texture;
+use crate::render::{
+	camera::{Camera2D, CurrentCameraTag, RenderTarget},
+	geometry::{Rect, Vertex},
+	pipeline::DrawMode,
+	rgba::{self, Rgba},
+	transform::Transform2D,
+	RenderingBackend, Visibility,
+};
+use crate::window::state::MiniquadDraw;
+
+pub mod atlas;
+pub use atlas::*;
+
+/// Draws all [`Sprite`]s through the current camera, batching sprites that share a texture. Also runs [`SpriteAnimation`]s.
+///
+/// Other 2D drawables, like [`Text2D`](crate::text::Text2D), are depth sorted and drawn along with sprites
+pub struct SpritePlugin;
+
+impl Plugin for SpritePlugin {
+	fn build(&self, app: &mut App) {
+		app.init_asset::<TextureAtlasLayout>()
+			.init_resource::<QueuedQuads>()
+			.add_systems(Update, atlas::animate_sprites)
+			.add_systems(MiniquadDraw, draw_sprites);
+	}
+}
+
+/// Point of the sprite that's placed at its [`Transform2D`] translation, and around which it rotates
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Anchor {
+	#[default]
+	Center,
+	BottomLeft,
+	BottomCenter,
+	BottomRight,
+	CenterLeft,
+	CenterRight,
+	TopLeft,
+	TopCenter,
+	TopRight,
+	/// Custom anchor, where `(-0.5, -0.5)` is the bottom-left corner and `(0.5, 0.5)` the top-right one
+	Custom(Vec2),
+}
+
+impl Anchor {
+	pub fn as_vec(&self) -> Vec2 {
+		match self {
+			Anchor::Center => vec2(0.0, 0.0),
+			Anchor::BottomLeft => vec2(-0.5, -0.5),
+			Anchor::BottomCenter => vec2(0.0, -0.5),
+			Anchor::BottomRight => vec2(0.5, -0.5),
+			Anchor::CenterLeft => vec2(-0.5, 0.0),
+			Anchor::CenterRight => vec2(0.5, 0.0),
+			Anchor::TopLeft => vec2(-0.5, 0.5),
+			Anchor::TopCenter => vec2(0.0, 0.5),
+			Anchor::TopRight => vec2(0.5, 0.5),
+			Anchor::Custom(point) => *point,
+		}
+	}
+}
+
+/// A textured quad, positioned by its [`Transform2D`]
+#[derive(Debug, Clone, Component)]
+#[require(Transform2D)]
+pub struct Sprite {
+	pub texture: Handle<Texture>,
+	/// Multiplied with the texture's colour
+	pub color: Rgba,
+	pub flip_x: bool,
+	pub flip_y: bool,
+	/// Size of the sprite in world units. Defaults to the size of [`rect`](Sprite::rect), or the whole texture
+	pub custom_size: Option<Vec2>,
+	pub anchor: Anchor,
+	/// Region of the texture to draw in pixels. Draws the whole texture if `None`
+	pub rect: Option<Rect>,
+}
+
+impl Default for Sprite {
+	fn default() -> Self {
+		Self {
+			texture: Handle::default(),
+			color: rgba::WHITE,
+			flip_x: false,
+			flip_y: false,
+			custom_size: None,
+			anchor: Anchor::Center,
+			rect: None,
+		}
+	}
+}
+
+impl Sprite {
+	pub fn new(texture: Handle<Texture>) -> Self {
+		Self { texture, ..Default::default() }
+	}
+
+	/// Builds the sprite's quad, given the region of the texture to draw and the texture's size.
+	/// Textures stored `upside_down` are sampled mirrored, `rect` still being measured from their top
+	pub(crate) fn vertices(&self, transform: &Transform2D, rect: Option<Rect>, texture_size: Vec2, upside_down: bool) -> [Vertex; 4] {
+		let rect = rect.unwrap_or(Rect { min: Vec2::ZERO, max: texture_size });
+		let size = self.custom_size.unwrap_or(rect.size());
+		let anchor = self.anchor.as_vec();
+
+		let (mut u0, mut u1) = (rect.min.x / texture_size.x, rect.max.x / texture_size.x);
+		let (mut v0, mut v1) = (rect.min.y / texture_size.y, rect.max.y / texture_size.y);
+		if upside_down {
+			(v0, v1) = (1.0 - v0, 1.0 - v1);
+		}
+		if self.flip_x {
+			std::mem::swap(&mut u0, &mut u1);
+		}
+		if self.flip_y {
+			std::mem::swap(&mut v0, &mut v1);
+		}
+
+		// Same layout as `Mesh::quad`, the top of the texture is at +y
+		let corner = |x: f32, y: f32, uv: Vec2| {
+			let local = (vec2(x, y) - anchor) * size;
+			let position = transform.transform_point(local);
+			Vertex::new(vec3(position.x, position.y, 0.0), uv, self.color)
+		};
+
+		[
+			corner(-0.5, 0.5, vec2(u0, v0)),  // top-left
+			corner(0.5, 0.5, vec2(u1, v0)),   // top-right
+			corner(-0.5, -0.5, vec2(u0, v1)), // bottom-left
+			corner(0.5, -0.5, vec2(u1, v1)),  // bottom-right
+		]
+	}
+}
+
+pub(crate) const QUAD_INDICES: [u16; 6] = [0, 1, 2, 1, 2, 3];
+
+/// Textured quads queued by other 2D drawables before [`draw_sprites`], which sorts them by depth along with sprites. Emptied every frame
+#[derive(Resource, Default)]
+pub(crate) struct QueuedQuads(Vec<(f32, TextureId, [Vertex; 4])>);
+
+impl QueuedQuads {
+	#[cfg(feature = "text")]
+	pub(crate) fn push(&mut self, z: f32, texture: TextureId, vertices: [Vertex; 4]) {
+		self.0.push((z, texture, vertices));
+	}
+}
+
+/// Something drawn by [`draw_sprites`]. At the same depth, sprites are grouped by texture and drawn before queued quads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Batched {
+	Sprite(AssetId<Texture>, Entity),
+	/// Index into [`QueuedQuads`], queued quads keep their order
+	Queued(usize),
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn draw_sprites(
+	mut backend: NonSendMut<RenderingBackend>,
+	mut order: Local<Vec<(f32, Batched)>>,
+	mut queued: ResMut<QueuedQuads>,
+	current_camera: Res<CurrentCameraTag>,
+	cameras: Query<(&Camera2D, &RenderTarget)>,
+	sprites: Query<(Entity, &Sprite, &Transform2D, Option<&Visibility>, Option<&AtlasIndex>)>,
+	textures: Res<Assets<Texture>>,
+	layouts: Res<Assets<TextureAtlasLayout>>,
+) {
+	let Ok((camera, target)) = cameras.get(current_camera.0) else {
+		queued.0.clear();
+		return;
+	};
+
+	// Sort by depth first, then group by texture so that batches break as rarely as possible
+	order.clear();
+	order.extend(
+		sprites
+			.iter()
+			.filter(|(_, _, _, visibility, _)| !matches!(visibility, Some(Visibility::Hidden)))
+			.map(|(entity, sprite, transform, _, _)| (transform.translation.z, Batched::Sprite(sprite.texture.id(), entity))),
+	);
+	order.extend(queued.0.iter().enumerate().map(|(index, (z, _, _))| (*z, Batched::Queued(index))));
+	order.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+	if order.is_empty() {
+		return;
+	}
+
+	backend.render_pass(target.render_pass());
+	backend.viewport(camera.viewport);
+	backend.pipeline(None);
+	backend.draw_mode(DrawMode::Triangles);
+
+	for (_, batched) in order.iter() {
+		let (texture, vertices) = match *batched {
+			Batched::Sprite(_, entity) => {
+				let (_, sprite, transform, _, atlas) = sprites.get(entity).unwrap();
+				let Some(texture) = textures.get(&sprite.texture) else {
+					continue;
+				};
+
+				let rect = match atlas {
+					// Layout is still loading, or the index is out of bounds
+					Some(atlas) => match atlas.rect(&layouts) {
+						Some(rect) => Some(rect),
+						None => continue,
+					},
+					None => sprite.rect,
+				};
+				let (width, height) = backend.texture_size(texture.id());
+				(texture.id(), sprite.vertices(transform, rect, vec2(width, height), texture.is_upside_down()))
+			}
+			Batched::Queued(index) => {
+				let (_, texture, vertices) = queued.0[index];
+				(texture, vertices)
+			}
+		};
+
+		backend.texture(Some(&texture));
+		backend.geometry(&vertices, &QUAD_INDICES);
+	}
+
+	queued.0.clear();
+	backend.draw(camera.matrix());
+}
@@ -0,0 +1,73 @@
+use bevy_asset::io::Reader;
+use bevy_asset::{Asset, AssetLoader, LoadContext};
+use bevy_reflect::TypePath;
+
+/// A TTF or OTF font, used by [`Text2D`](super::Text2D). Glyphs are rasterized on demand, at the sizes they're drawn at
+#[derive(Asset, TypePath)]
+pub struct Font {
+	pub(crate) font: fontdue::Font,
+}
+
+impl Font {
+	/// Parses a TTF or OTF font
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, FontLoaderError> {
+		let font = fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default()).map_err(FontLoaderError::Font)?;
+		Ok(Self { font })
+	}
+
+	/// Distance from the baseline to the top of the tallest glyphs, at the given size
+	pub fn ascent(&self, size: f32) -> f32 {
+		self.font.horizontal_line_metrics(size).map_or(size, |metrics| metrics.ascent)
+	}
+
+	/// Distance between two consecutive baselines, at the given size
+	pub fn line_size(&self, size: f32) -> f32 {
+		self.font.horizontal_line_metrics(size).map_or(size, |metrics| metrics.new_line_size)
+	}
+}
+
+#[derive(Debug)]
+pub enum FontLoaderError {
+	/// Failed to read the font bytes
+	Io(std::io::Error),
+	/// The file isn't a valid TTF or OTF font
+	Font(&'static str),
+}
+
+impl std::fmt::Display for FontLoaderError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io(err) => write!(f, "Failed to read font: {}", err),
+			Self::Font(err) => write!(f, "Failed to parse font: {}", err),
+		}
+	}
+}
+
+impl std::error::Error for FontLoaderError {}
+
+impl From<std::io::Error> for FontLoaderError {
+	fn from(value: std::io::Error) -> Self {
+		Self::Io(value)
+	}
+}
+
+/// Loads `.ttf` and `.otf` files as [`Font`]s
+#[derive(Default)]
+pub struct FontLoader;
+
+impl AssetLoader for FontLoader {
+	type Asset = Font;
+	type Settings = ();
+	type Error = FontLoaderError;
+
+	async fn load(&self, reader: &mut dyn Reader, _settings: &(), _load_context: &mut LoadContext<'_>) -> Result<Font, Self::Error> {
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes).await?;
+
+		Font::from_bytes(&bytes)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["ttf", "otf"]
+	}
+}
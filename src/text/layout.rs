@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+use glam::{vec2, Vec2};
+
+use super::Font;
+use crate::render::geometry::Rect;
+
+/// Horizontal alignment of each line, relative to the text's origin
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextAlignment {
+	/// Lines start at the origin
+	#[default]
+	Left,
+	/// Lines are centered on the origin
+	Center,
+	/// Lines end at the origin
+	Right,
+}
+
+/// A character placed by a [`TextLayout`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+	pub character: char,
+	/// Byte offset of the character in the measured text
+	pub byte_index: usize,
+	/// Pen position on the baseline. The origin is the top of the first line, with y pointing down
+	pub position: Vec2,
+	/// Horizontal distance to the next character, without kerning
+	pub advance: f32,
+	/// Index of the line the character was placed on
+	pub line: usize,
+}
+
+/// A line of a [`TextLayout`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+	/// Indices of the line's glyphs in [`TextLayout::glyphs`]
+	pub glyphs: Range<usize>,
+	/// Horizontal position of the line's start, after alignment
+	pub x: f32,
+	/// Width of the line, trailing whitespace excluded
+	pub width: f32,
+	/// Distance from the top of the text to the line's baseline
+	pub baseline: f32,
+}
+
+/// Glyph positions, line breaks and bounds of a piece of text. Computed on the CPU only, see [`measure_text`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextLayout {
+	pub glyphs: Vec<PositionedGlyph>,
+	pub lines: Vec<TextLine>,
+	/// Area taken by all lines, in the same space as glyph positions
+	pub bounds: Rect,
+}
+
+/// Lays out `text` on the left aligned lines of a [`Text2D`](super::Text2D), wrapping lines longer than `max_width`
+pub fn measure_text(font: &Font, text: &str, size: f32, max_width: Option<f32>) -> TextLayout {
+	TextLayout::new(font, text, size, 1.0, max_width, TextAlignment::Left)
+}
+
+impl TextLayout {
+	/// Lays out `text`, breaking lines on `\n` and, if `max_width` is set, between words that don't fit. `line_height` is relative to the font's line size
+	pub fn new(font: &Font, text: &str, size: f32, line_height: f32, max_width: Option<f32>, alignment: TextAlignment) -> Self {
+		let mut layout = Self::default();
+		layout.compute(font, text, size, line_height, max_width, alignment);
+		layout
+	}
+
+	/// Same as [`TextLayout::new`], but reuses the layout's allocations
+	pub(crate) fn compute(&mut self, font: &Font, text: &str, size: f32, line_height: f32, max_width: Option<f32>, alignment: TextAlignment) {
+		let line_advance = font.line_size(size) * line_height;
+		let mut baseline = font.ascent(size);
+
+		self.glyphs.clear();
+		self.lines.clear();
+
+		let mut line: Vec<PositionedGlyph> = Vec::new();
+		let mut paragraph_start = 0;
+
+		for paragraph in text.split('\n') {
+			let mut pen = 0.0;
+			let mut last_break = None;
+			let mut previous = None;
+
+			for (index, character) in paragraph.strip_suffix('\r').unwrap_or(paragraph).char_indices() {
+				let advance = font.font.metrics(character, size).advance_width;
+				pen += previous.and_then(|previous| font.font.horizontal_kern(previous, character, size)).unwrap_or(0.0);
+				previous = Some(character);
+
+				if character.is_whitespace() {
+					last_break = Some(line.len());
+				} else if max_width.is_some_and(|max| pen + advance > max) && !line.is_empty() {
+					// Move the word being typed to the next line. Words wider than a line are split where they overflow
+					let rest = match last_break.take() {
+						Some(index) => line.split_off(index + 1),
+						None => Vec::new(),
+					};
+
+					self.finish_line(&mut line, baseline, alignment);
+					baseline += line_advance;
+
+					let shift = rest.first().map_or(pen, |glyph| glyph.position.x);
+					line.extend(rest.into_iter().map(|glyph| PositionedGlyph {
+						position: glyph.position - vec2(shift, 0.0),
+						..glyph
+					}));
+					pen -= shift;
+				}
+
+				line.push(PositionedGlyph {
+					character,
+					byte_index: paragraph_start + index,
+					position: vec2(pen, 0.0),
+					advance,
+					line: 0,
+				});
+				pen += advance;
+			}
+
+			self.finish_line(&mut line, baseline, alignment);
+			baseline += line_advance;
+			paragraph_start += paragraph.len() + 1;
+		}
+
+		let min_x = self.lines.iter().map(|line| line.x).fold(f32::INFINITY, f32::min);
+		let max_x = self.lines.iter().map(|line| line.x + line.width).fold(f32::NEG_INFINITY, f32::max);
+		self.bounds = Rect::from_corners(vec2(min_x, 0.0), vec2(max_x, self.lines.len() as f32 * line_advance));
+	}
+
+	fn finish_line(&mut self, line: &mut Vec<PositionedGlyph>, baseline: f32, alignment: TextAlignment) {
+		// Trailing whitespace doesn't count towards the line's width
+		let width = line.iter().rev().find(|glyph| !glyph.character.is_whitespace()).map_or(0.0, |glyph| glyph.position.x + glyph.advance);
+		let x = match alignment {
+			TextAlignment::Left => 0.0,
+			TextAlignment::Center => -width / 2.0,
+			TextAlignment::Right => -width,
+		};
+
+		let start = self.glyphs.len();
+		let index = self.lines.len();
+		self.glyphs.extend(line.drain(..).map(|glyph| PositionedGlyph {
+			position: vec2(glyph.position.x + x, baseline),
+			line: index,
+			..glyph
+		}));
+
+		self.lines.push(TextLine {
+			glyphs: start..self.glyphs.len(),
+			x,
+			width,
+			baseline,
+		});
+	}
+
+	/// Width and height of the text
+	pub fn size(&self) -> Vec2 {
+		self.bounds.size()
+	}
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{AssetApp, AssetEvent, AssetId, Assets, Handle};
+use bevy_ecs::{
+	component::Component,
+	event::EventReader,
+	schedule::IntoSystemConfigs,
+	system::{Local, NonSendMut, Query, Res, ResMut, Resource},
+};
+use glam::{vec2, vec3, Vec2};
+use miniquad::TextureId;
+
+use crate::asset::Texture;
+use crate::render::{
+	dynamic_atlas::{AtlasRegion, DynamicTextureAtlas},
+	geometry::{Rect, Vertex},
+	rgba::{self, Rgba},
+	transform::Transform2D,
+	RenderingBackend, Visibility,
+};
+use crate::sprite::{draw_sprites, QueuedQuads};
+use crate::window::state::{MiniquadDraw, MiniquadPrepareDraw};
+
+pub mod font;
+pub mod layout;
+
+pub use font::*;
+pub use layout::*;
+
+/// Loads [`Font`]s and draws all [`Text2D`]s through the current camera. Glyphs are depth sorted and batched along with sprites,
+/// so the [`SpritePlugin`](crate::sprite::SpritePlugin) is needed too
+pub struct TextPlugin;
+
+impl Plugin for TextPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_asset::<Font>()
+			.init_asset_loader::<FontLoader>()
+			.init_resource::<GlyphCache>()
+			.init_resource::<QueuedQuads>()
+			.add_systems(MiniquadPrepareDraw, evict_glyphs)
+			.add_systems(MiniquadDraw, queue_text.before(draw_sprites));
+	}
+}
+
+/// A block of text, positioned by its [`Transform2D`]. The translation is the top-left of the first line for left aligned text
+#[derive(Debug, Clone, Component)]
+#[require(Transform2D)]
+pub struct Text2D {
+	pub text: String,
+	pub font: Handle<Font>,
+	/// Size of the text in world units. Glyphs are rasterized at this size, so scaling the transform will blur them
+	pub font_size: f32,
+	pub color: Rgba,
+	pub alignment: TextAlignment,
+	/// Distance between lines, relative to the font's own line size
+	pub line_height: f32,
+	/// Lines longer than this are wrapped between words, in world units
+	pub max_width: Option<f32>,
+}
+
+impl Default for Text2D {
+	fn default() -> Self {
+		Self {
+			text: String::new(),
+			font: Handle::default(),
+			font_size: 24.0,
+			color: rgba::WHITE,
+			alignment: TextAlignment::Left,
+			line_height: 1.0,
+			max_width: None,
+		}
+	}
+}
+
+impl Text2D {
+	pub fn new(text: impl Into<String>, font: Handle<Font>) -> Self {
+		Self {
+			text: text.into(),
+			font,
+			..Default::default()
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+	font: AssetId<Font>,
+	character: char,
+	/// Bits of the font size, sizes are rasterized exactly
+	size: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CachedGlyph {
+	/// `None` for glyphs without pixels, like spaces
+	region: Option<AtlasRegion>,
+	/// Top-left corner of the bitmap, relative to the pen position (y pointing down)
+	offset: Vec2,
+}
+
+/// Rasterized glyphs of every font and size drawn so far, packed into a [`DynamicTextureAtlas`].
+/// Once the atlas grows past [`MAX_GLYPH_PAGES`] pages, all glyphs are dropped at the start of the next frame and rasterized again as needed.
+/// Scenes that fit in fewer pages settle after that, instead of evicting every frame
+/// Pages of `2048x2048` pixels the [`GlyphCache`] can grow to before it's emptied
+pub(crate) const MAX_GLYPH_PAGES: usize = 4;
+
+#[derive(Resource)]
+pub(crate) struct GlyphCache {
+	atlas: DynamicTextureAtlas,
+	glyphs: HashMap<GlyphKey, CachedGlyph>,
+}
+
+impl Default for GlyphCache {
+	fn default() -> Self {
+		Self {
+			atlas: DynamicTextureAtlas::new(256, 2048),
+			glyphs: HashMap::new(),
+		}
+	}
+}
+
+impl GlyphCache {
+	fn glyph(&mut self, textures: &Assets<Texture>, font_id: AssetId<Font>, font: &Font, character: char, size: f32) -> CachedGlyph {
+		let key = GlyphKey {
+			font: font_id,
+			character,
+			size: size.to_bits(),
+		};
+
+		*self.glyphs.entry(key).or_insert_with(|| {
+			let (metrics, coverage) = font.font.rasterize(character, size);
+			let rgba: Vec<u8> = coverage.iter().flat_map(|&alpha| [255, 255, 255, alpha]).collect();
+
+			let region = match metrics.width * metrics.height {
+				0 => None,
+				_ => self.atlas.add(textures, metrics.width as u32, metrics.height as u32, &rgba),
+			};
+
+			CachedGlyph {
+				region,
+				offset: vec2(metrics.xmin as f32, -(metrics.ymin as f32 + metrics.height as f32)),
+			}
+		})
+	}
+
+	/// Rasterizes the visible glyphs of `layout`, returning their atlas regions and top-left corners in layout space
+	pub(crate) fn glyphs<'a>(&'a mut self, textures: &'a Assets<Texture>, font_id: AssetId<Font>, font: &'a Font, layout: &'a TextLayout, size: f32) -> impl Iterator<Item = (AtlasRegion, Vec2)> + 'a {
+		layout.glyphs.iter().filter_map(move |glyph| {
+			let cached = self.glyph(textures, font_id, font, glyph.character, size);
+			cached.region.map(|region| (region, glyph.position + cached.offset))
+		})
+	}
+
+	/// Drops every glyph if the atlas grew past [`MAX_GLYPH_PAGES`]. The first page is kept for reuse
+	fn evict_if_full(&mut self) {
+		if self.atlas.pages() > MAX_GLYPH_PAGES {
+			self.atlas.truncate(1);
+			self.atlas.clear();
+			self.glyphs.clear();
+		}
+	}
+
+	/// Uploads newly rasterized glyphs to GPU
+	pub(crate) fn flush(&mut self, backend: &mut RenderingBackend, textures: &mut Assets<Texture>) {
+		self.atlas.flush(backend, textures);
+	}
+
+	/// Texture and vertices of a glyph, once the atlas is flushed
+	pub(crate) fn geometry(&self, textures: &Assets<Texture>, quad: &GlyphQuad) -> Option<(TextureId, [Vertex; 4])> {
+		let texture = textures.get(self.atlas.texture(quad.page)?)?;
+		let page_size = self.atlas.page_size(quad.page)?.as_vec2();
+
+		let (uv_min, uv_max) = (quad.rect.min / page_size, quad.rect.max / page_size);
+		let [top_left, top_right, bottom_left, bottom_right] = quad.corners;
+		let vertex = |position: Vec2, u: f32, v: f32| Vertex::new(vec3(position.x, position.y, 0.0), vec2(u, v), quad.color);
+
+		let vertices = [
+			vertex(top_left, uv_min.x, uv_min.y),
+			vertex(top_right, uv_max.x, uv_min.y),
+			vertex(bottom_left, uv_min.x, uv_max.y),
+			vertex(bottom_right, uv_max.x, uv_max.y),
+		];
+
+		Some((texture.id(), vertices))
+	}
+}
+
+/// A glyph ready to be drawn, once the atlas is flushed
+pub(crate) struct GlyphQuad {
+	page: usize,
+	rect: Rect,
+	/// Top-left, top-right, bottom-left and bottom-right corners
+	corners: [Vec2; 4],
+	color: Rgba,
+}
+
+impl GlyphQuad {
+	/// Places a glyph of size `region` at `min` in layout space, `transform` maps layout space to the space it's drawn in
+	pub(crate) fn new(region: AtlasRegion, min: Vec2, color: Rgba, transform: impl Fn(Vec2) -> Vec2) -> Self {
+		let max = min + region.rect.size();
+
+		Self {
+			page: region.page,
+			rect: region.rect,
+			corners: [transform(min), transform(vec2(max.x, min.y)), transform(vec2(min.x, max.y)), transform(max)],
+			color,
+		}
+	}
+}
+
+/// Frees the glyphs of removed fonts, and empties the cache once it's full
+pub(crate) fn evict_glyphs(mut cache: ResMut<GlyphCache>, mut events: EventReader<AssetEvent<Font>>) {
+	for event in events.read() {
+		if let AssetEvent::Removed { id } = event {
+			cache.glyphs.retain(|key, _| key.font != *id);
+		}
+	}
+
+	cache.evict_if_full();
+}
+
+/// Rasterizes the glyphs of all [`Text2D`]s, and queues them to be drawn with sprites
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn queue_text(
+	mut backend: NonSendMut<RenderingBackend>,
+	mut cache: ResMut<GlyphCache>,
+	mut queued: ResMut<QueuedQuads>,
+	mut layout: Local<TextLayout>,
+	mut quads: Local<Vec<(f32, GlyphQuad)>>,
+	texts: Query<(&Text2D, &Transform2D, Option<&Visibility>)>,
+	fonts: Res<Assets<Font>>,
+	mut textures: ResMut<Assets<Texture>>,
+) {
+	// Rasterize missing glyphs first, so the atlas is uploaded once per frame
+	quads.clear();
+	for (text, transform, visibility) in texts.iter() {
+		if matches!(visibility, Some(Visibility::Hidden)) {
+			continue;
+		}
+
+		let Some(font) = fonts.get(&text.font) else {
+			continue;
+		};
+
+		layout.compute(font, &text.text, text.font_size, text.line_height, text.max_width, text.alignment);
+
+		// Layout space points down, world space up
+		let z = transform.translation.z;
+		let transform = |point: Vec2| transform.transform_point(vec2(point.x, -point.y));
+		for (region, min) in cache.glyphs(&textures, text.font.id(), font, &layout, text.font_size) {
+			quads.push((z, GlyphQuad::new(region, min, text.color, transform)));
+		}
+	}
+
+	cache.flush(&mut backend, &mut textures);
+
+	for (z, quad) in quads.iter() {
+		if let Some((texture, vertices)) = cache.geometry(&textures, quad) {
+			queued.push(*z, texture, vertices);
+		}
+	}
+}
@@ -0,0 +1,99 @@
+use bevy_asset::Assets;
+use bevy_ecs::system::{Local, NonSendMut, Res, ResMut};
+use glam::{vec3, Mat4, Vec2};
+
+use super::{UiShape, UiState, UiStyle};
+use crate::asset::Texture;
+use crate::render::{
+	geometry::{Rect, Vertex},
+	pipeline::DrawMode,
+	rgba::Rgba,
+	RenderingBackend,
+};
+use crate::sprite::QUAD_INDICES;
+use crate::text::{Font, GlyphCache, GlyphQuad, TextAlignment, TextLayout};
+use crate::window::events::WindowProperties;
+
+/// A UI command, with its text turned into glyphs
+pub(crate) enum Primitive {
+	Rect(Rect, Rgba),
+	Glyph(GlyphQuad),
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn draw_ui(
+	mut backend: NonSendMut<RenderingBackend>,
+	mut cache: ResMut<GlyphCache>,
+	mut layout: Local<TextLayout>,
+	mut primitives: Local<Vec<(Option<Rect>, Primitive)>>,
+	mut state: ResMut<UiState>,
+	style: Res<UiStyle>,
+	window: Res<WindowProperties>,
+	fonts: Res<Assets<Font>>,
+	mut textures: ResMut<Assets<Texture>>,
+) {
+	if state.commands.is_empty() {
+		return;
+	}
+
+	// Windows are drawn in the order they were raised, widgets outside of windows first
+	state.commands.sort_by_key(|command| command.layer);
+
+	primitives.clear();
+	let font = fonts.get(&style.font);
+
+	for command in state.commands.iter() {
+		match &command.shape {
+			UiShape::Rect(rect) => primitives.push((command.clip, Primitive::Rect(*rect, command.color))),
+			UiShape::Text(position, text) => {
+				let Some(font) = font else {
+					continue;
+				};
+
+				layout.compute(font, text, style.font_size, 1.0, None, TextAlignment::Left);
+				for (region, min) in cache.glyphs(&textures, style.font.id(), font, &layout, style.font_size) {
+					// Snap to pixels, so glyphs stay sharp
+					let quad = GlyphQuad::new(region, (*position + min).round(), command.color, |point| point);
+					primitives.push((command.clip, Primitive::Glyph(quad)));
+				}
+			}
+		}
+	}
+
+	cache.flush(&mut backend, &mut textures);
+
+	backend.render_pass(None);
+	backend.viewport(None);
+	backend.pipeline(None);
+	backend.draw_mode(DrawMode::Triangles);
+
+	for (clip, primitive) in primitives.iter() {
+		let (texture, vertices) = match primitive {
+			Primitive::Rect(rect, color) => {
+				let vertex = |x: f32, y: f32| Vertex::new(vec3(x, y, 0.0), Vec2::ZERO, *color);
+				(
+					None,
+					[
+						vertex(rect.min.x, rect.min.y),
+						vertex(rect.max.x, rect.min.y),
+						vertex(rect.min.x, rect.max.y),
+						vertex(rect.max.x, rect.max.y),
+					],
+				)
+			}
+			Primitive::Glyph(quad) => match cache.geometry(&textures, quad) {
+				Some((texture, vertices)) => (Some(texture), vertices),
+				None => continue,
+			},
+		};
+
+		backend.scissor(clip.map(|clip| (clip.min.x as i32, clip.min.y as i32, clip.width() as i32, clip.height() as i32)));
+		backend.texture(texture.as_ref());
+		backend.geometry(&vertices, &QUAD_INDICES);
+	}
+
+	backend.scissor(None);
+
+	// Screen space, in pixels from the top-left corner
+	backend.draw(Mat4::orthographic_rh_gl(0.0, window.width as f32, window.height as f32, 0.0, -1.0, 1.0));
+}
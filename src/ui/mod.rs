@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+	event::EventReader,
+	schedule::IntoSystemConfigs,
+	system::{Res, ResMut, Resource, SystemParam},
+};
+use glam::{vec2, Vec2};
+use miniquad::MouseButton;
+
+use crate::render::{
+	geometry::Rect,
+	rgba::{self, Rgba},
+};
+use crate::sprite::draw_sprites;
+use crate::text::{measure_text, Font};
+use crate::window::clipboard::Clipboard;
+use crate::window::events::{CursorProperties, MouseButtonEvent, MouseWheelEvent, WindowProperties};
+use crate::window::state::MiniquadDraw;
+use crate::window::text_input::{KeyboardInput, KeyboardInputs, OnScreenKeyboard, TextInput};
+
+mod draw;
+mod widgets;
+
+/// Immediate-mode GUI, for debug tools and simple menus. Widgets are declared every frame through the [`Ui`] system param,
+/// and drawn in screen space after all sprites and text.
+///
+/// *Note: set [`UiStyle::font`], text isn't drawn without a font*
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<UiStyle>()
+			.init_resource::<UiInput>()
+			.init_resource::<UiState>()
+			.add_systems(PreUpdate, begin_ui)
+			.add_systems(MiniquadDraw, draw::draw_ui.after(draw_sprites));
+	}
+}
+
+/// Look of all [`Ui`] widgets. Sizes are in pixels
+#[derive(Debug, Clone, Resource)]
+pub struct UiStyle {
+	pub font: Handle<Font>,
+	pub font_size: f32,
+	/// Space between a widget's border and its contents
+	pub padding: f32,
+	/// Space between consecutive widgets
+	pub spacing: f32,
+	pub text_color: Rgba,
+	pub window_color: Rgba,
+	pub title_color: Rgba,
+	pub widget_color: Rgba,
+	pub hovered_color: Rgba,
+	pub active_color: Rgba,
+	/// Colour of checked boxes, slider fills and text carets
+	pub accent_color: Rgba,
+}
+
+impl Default for UiStyle {
+	fn default() -> Self {
+		Self {
+			font: Handle::default(),
+			font_size: 16.0,
+			padding: 4.0,
+			spacing: 4.0,
+			text_color: rgba::WHITE,
+			window_color: Rgba::new(30, 30, 36, 230),
+			title_color: Rgba::new(50, 50, 70, 255),
+			widget_color: Rgba::new(60, 60, 72, 255),
+			hovered_color: Rgba::new(80, 80, 100, 255),
+			active_color: Rgba::new(100, 100, 130, 255),
+			accent_color: rgba::SKYBLUE,
+		}
+	}
+}
+
+/// Mouse and keyboard input of the current frame, collected from window events
+#[derive(Debug, Default, Resource)]
+pub(crate) struct UiInput {
+	mouse: Vec2,
+	mouse_delta: Vec2,
+	/// Left mouse button state
+	down: bool,
+	pressed: bool,
+	released: bool,
+	wheel: f32,
+	keyboard: Vec<KeyboardInput>,
+}
+
+type WidgetId = u64;
+
+#[derive(Debug, Clone, Copy)]
+struct WindowState {
+	rect: Rect,
+	/// Windows with higher values are drawn on top
+	z: u32,
+	/// Whether the window was declared this frame
+	visible: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ScrollState {
+	offset: f32,
+	/// Height of the contents in the previous frame
+	content_height: f32,
+}
+
+/// Area widgets are placed in, top to bottom
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+	/// Widget ids are hashed with the id of the area they're in
+	id: WidgetId,
+	x: f32,
+	width: f32,
+	cursor: f32,
+	clip: Option<Rect>,
+	layer: u32,
+	/// Whether widgets in this area react to the mouse, false if another window covers it
+	interactive: bool,
+}
+
+pub(crate) enum UiShape {
+	Rect(Rect),
+	/// Single line of text, placed by its top-left corner
+	Text(Vec2, String),
+}
+
+pub(crate) struct UiCommand {
+	layer: u32,
+	clip: Option<Rect>,
+	color: Rgba,
+	shape: UiShape,
+}
+
+/// Persistent state of the UI: which widget is pressed or focused, window positions and what to draw this frame
+#[derive(Default, Resource)]
+pub(crate) struct UiState {
+	/// Widget held down with the mouse
+	active: Option<WidgetId>,
+	/// Text input receiving keyboard input
+	focused: Option<WidgetId>,
+	/// Editor of the focused text input
+	editor: TextInput,
+	windows: HashMap<WidgetId, WindowState>,
+	top_z: u32,
+	/// Topmost window under the mouse, based on last frame's window rectangles
+	hovered_window: Option<WidgetId>,
+	scroll_areas: HashMap<WidgetId, ScrollState>,
+	layouts: Vec<Layout>,
+	commands: Vec<UiCommand>,
+}
+
+fn begin_ui(
+	mut input: ResMut<UiInput>,
+	mut state: ResMut<UiState>,
+	style: Res<UiStyle>,
+	cursor: Res<CursorProperties>,
+	window: Res<WindowProperties>,
+	(mut buttons, mut keyboard, mut wheel): (EventReader<MouseButtonEvent>, KeyboardInputs, EventReader<MouseWheelEvent>),
+) {
+	input.mouse_delta = cursor.position - input.mouse;
+	input.mouse = cursor.position;
+	input.pressed = false;
+	input.released = false;
+
+	for event in buttons.read().filter(|event| event.button == MouseButton::Left) {
+		input.down = !event.released;
+		input.pressed |= !event.released;
+		input.released |= event.released;
+	}
+
+	input.keyboard = keyboard.read();
+	input.wheel = wheel.read().map(|event| event.delta.y).sum();
+
+	let state = state.as_mut();
+	state.hovered_window = state
+		.windows
+		.iter()
+		.filter(|(_, window)| window.visible && window.rect.contains(input.mouse))
+		.max_by_key(|(_, window)| window.z)
+		.map(|(id, _)| *id);
+
+	if input.pressed {
+		// Clicking anywhere unfocuses text inputs, the clicked one takes focus back while it's declared
+		state.focused = None;
+
+		if let Some(window) = state.hovered_window.and_then(|id| state.windows.get_mut(&id)) {
+			state.top_z += 1;
+			window.z = state.top_z;
+		}
+	}
+
+	if !input.down && !input.released {
+		state.active = None;
+	}
+
+	for window in state.windows.values_mut() {
+		window.visible = false;
+	}
+
+	state.commands.clear();
+	state.layouts.clear();
+	state.layouts.push(Layout {
+		id: 0,
+		x: style.spacing,
+		width: window.width as f32 - style.spacing * 2.0,
+		cursor: style.spacing,
+		clip: None,
+		layer: 0,
+		interactive: state.hovered_window.is_none(),
+	});
+}
+
+/// What happened to a widget this frame
+#[derive(Debug, Clone, Copy, Default)]
+struct Interaction {
+	hovered: bool,
+	/// The mouse was pressed on the widget this frame
+	pressed: bool,
+	/// The mouse is held down, after being pressed on the widget
+	held: bool,
+	/// The mouse was pressed and released on the widget
+	clicked: bool,
+}
+
+/// Immediate-mode GUI. Every call declares a widget for the current frame, returning how the user interacted with it.
+///
+/// Widgets are stacked top to bottom in the current area: the screen, a [`window`](Ui::window) or a [`scroll_area`](Ui::scroll_area).
+/// Widgets are identified by their label, append `##suffix` to the label to tell apart widgets with the same text.
+#[derive(SystemParam)]
+pub struct Ui<'w> {
+	state: ResMut<'w, UiState>,
+	input: Res<'w, UiInput>,
+	style: Res<'w, UiStyle>,
+	fonts: Res<'w, Assets<Font>>,
+	clipboard: Clipboard<'w>,
+	on_screen_keyboard: ResMut<'w, OnScreenKeyboard>,
+}
+
+impl Ui<'_> {
+	/// Whether the mouse is over a window, or interacting with a widget. Use it to ignore clicks meant for the UI
+	pub fn wants_pointer(&self) -> bool {
+		self.state.hovered_window.is_some() || self.state.active.is_some()
+	}
+
+	/// Whether a text input is focused. Use it to ignore key presses meant for the UI
+	pub fn wants_keyboard(&self) -> bool {
+		self.state.focused.is_some()
+	}
+
+	pub fn style(&self) -> &UiStyle {
+		&self.style
+	}
+
+	fn layout(&self) -> &Layout {
+		self.state.layouts.last().expect("Ui used outside of a frame")
+	}
+
+	fn id(&self, label: &str) -> WidgetId {
+		let mut hasher = DefaultHasher::new();
+		self.layout().id.hash(&mut hasher);
+		label.hash(&mut hasher);
+		hasher.finish()
+	}
+
+	/// Size of a line of text, estimated until the font is loaded
+	fn text_size(&self, text: &str) -> Vec2 {
+		match self.fonts.get(&self.style.font) {
+			Some(font) => vec2(measure_text(font, text, self.style.font_size, None).size().x, font.line_size(self.style.font_size)),
+			None => vec2(text.chars().count() as f32 * self.style.font_size * 0.5, self.style.font_size * 1.2),
+		}
+	}
+
+	/// Places a widget of the given height below the previous one, taking the whole width of the area
+	fn allocate(&mut self, height: f32) -> Rect {
+		let spacing = self.style.spacing;
+		let layout = self.state.layouts.last_mut().expect("Ui used outside of a frame");
+
+		let rect = Rect::new(layout.x, layout.cursor, layout.width, height);
+		layout.cursor += height + spacing;
+		rect
+	}
+
+	fn interact(&mut self, id: WidgetId, rect: Rect) -> Interaction {
+		let layout = *self.layout();
+		let input = &self.input;
+
+		let under_mouse = layout.interactive && rect.contains(input.mouse) && layout.clip.is_none_or(|clip| clip.contains(input.mouse));
+		let hovered = under_mouse && self.state.active.is_none_or(|active| active == id);
+
+		let pressed = hovered && input.pressed;
+		if pressed {
+			self.state.active = Some(id);
+		}
+
+		let held = self.state.active == Some(id);
+		let clicked = held && input.released && under_mouse;
+		if held && input.released {
+			self.state.active = None;
+		}
+
+		Interaction {
+			hovered,
+			pressed,
+			held: held && input.down,
+			clicked,
+		}
+	}
+
+	fn push(&mut self, color: Rgba, shape: UiShape) {
+		let layout = self.layout();
+		let command = UiCommand {
+			layer: layout.layer,
+			clip: layout.clip,
+			color,
+			shape,
+		};
+
+		self.state.commands.push(command);
+	}
+
+	fn widget_color(&self, interaction: Interaction) -> Rgba {
+		if interaction.held {
+			self.style.active_color
+		} else if interaction.hovered {
+			self.style.hovered_color
+		} else {
+			self.style.widget_color
+		}
+	}
+}
+
+/// Text shown for a label, everything before `##`
+fn display_text(label: &str) -> &str {
+	label.split_once("##").map_or(label, |(text, _)| text)
+}
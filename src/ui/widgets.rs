@@ -0,0 +1,252 @@
+use std::ops::RangeInclusive;
+
+use glam::{vec2, Vec2};
+use miniquad::KeyCode;
+
+use super::{display_text, Layout, Ui, UiShape, WindowState};
+use crate::render::geometry::Rect;
+use crate::window::text_input::{KeyboardInput, TextInput};
+
+impl Ui<'_> {
+	/// A line of text
+	pub fn label(&mut self, text: &str) {
+		let size = self.text_size(text);
+		let rect = self.allocate(size.y);
+
+		self.push(self.style.text_color, UiShape::Text(rect.min, text.to_string()));
+	}
+
+	/// A clickable button, returns `true` when clicked
+	pub fn button(&mut self, label: &str) -> bool {
+		let id = self.id(label);
+		let text = display_text(label);
+		let padding = self.style.padding;
+
+		let size = self.text_size(text) + padding * 2.0;
+		let mut rect = self.allocate(size.y);
+		rect.max.x = rect.min.x + size.x;
+
+		let interaction = self.interact(id, rect);
+		self.push(self.widget_color(interaction), UiShape::Rect(rect));
+		self.push(self.style.text_color, UiShape::Text(rect.min + padding, text.to_string()));
+
+		interaction.clicked
+	}
+
+	/// A box toggling `value` when clicked, returns `true` if `value` changed
+	pub fn checkbox(&mut self, label: &str, value: &mut bool) -> bool {
+		let id = self.id(label);
+		let text = display_text(label);
+		let (padding, spacing) = (self.style.padding, self.style.spacing);
+
+		let text_size = self.text_size(text);
+		let mut rect = self.allocate(text_size.y);
+		rect.max.x = rect.min.x + text_size.y + spacing + text_size.x;
+
+		let interaction = self.interact(id, rect);
+		if interaction.clicked {
+			*value = !*value;
+		}
+
+		let check = Rect::new(rect.min.x, rect.min.y, text_size.y, text_size.y);
+		self.push(self.widget_color(interaction), UiShape::Rect(check));
+		if *value {
+			self.push(self.style.accent_color, UiShape::Rect(Rect::from_corners(check.min + padding, check.max - padding)));
+		}
+		self.push(self.style.text_color, UiShape::Text(vec2(check.max.x + spacing, rect.min.y), text.to_string()));
+
+		interaction.clicked
+	}
+
+	/// A draggable bar picking a value in `range`, returns `true` if `value` changed
+	pub fn slider(&mut self, label: &str, value: &mut f32, range: RangeInclusive<f32>) -> bool {
+		let id = self.id(label);
+		let (start, end) = (*range.start(), *range.end());
+
+		let text = format!("{}: {:.2}", display_text(label), value);
+		let text_size = self.text_size(&text);
+		let rect = self.allocate(text_size.y);
+		let track = Rect::from_corners(rect.min, vec2(rect.max.x - text_size.x - self.style.spacing, rect.max.y));
+
+		let interaction = self.interact(id, track);
+		let previous = *value;
+		if interaction.held && track.width() > 0.0 {
+			let t = ((self.input.mouse.x - track.min.x) / track.width()).clamp(0.0, 1.0);
+			*value = start + (end - start) * t;
+		}
+
+		let t = if end != start { ((*value - start) / (end - start)).clamp(0.0, 1.0) } else { 0.0 };
+		let fill = Rect::from_corners(track.min, vec2(track.min.x + track.width() * t, track.max.y));
+
+		self.push(self.widget_color(interaction), UiShape::Rect(track));
+		self.push(self.style.accent_color, UiShape::Rect(fill));
+		self.push(self.style.text_color, UiShape::Text(vec2(track.max.x + self.style.spacing, rect.min.y), text));
+
+		*value != previous
+	}
+
+	/// A single line text field, focused by clicking on it and edited like a [`TextInput`]. Returns `true` if `text` changed
+	pub fn text_input(&mut self, label: &str, text: &mut String) -> bool {
+		let id = self.id(label);
+		let display = display_text(label);
+		let padding = self.style.padding;
+
+		let label_size = self.text_size(display);
+		let rect = self.allocate(label_size.y + padding * 2.0);
+		let field = Rect::from_corners(rect.min, vec2(rect.max.x - label_size.x - self.style.spacing, rect.max.y));
+
+		let interaction = self.interact(id, field);
+		if interaction.pressed {
+			self.state.focused = Some(id);
+			self.state.editor = TextInput::new(text.as_str());
+		}
+
+		let mut changed = false;
+		if self.state.focused == Some(id) {
+			let state = self.state.as_mut();
+			self.on_screen_keyboard.requested = true;
+
+			// The text was changed outside of the widget
+			if state.editor.text() != text {
+				state.editor.set_text(text.as_str());
+			}
+
+			for event in self.input.keyboard.iter() {
+				match event {
+					KeyboardInput::Key(key) if !key.released && matches!(key.keycode, KeyCode::Enter | KeyCode::KpEnter | KeyCode::Escape | KeyCode::Tab) => {
+						state.focused = None;
+						break;
+					}
+					KeyboardInput::Key(key) => state.editor.handle_key(key, &mut self.clipboard),
+					KeyboardInput::Char(char) => state.editor.handle_char(char),
+				};
+			}
+
+			if state.editor.text() != text {
+				*text = state.editor.text().to_string();
+				changed = true;
+			}
+		}
+
+		let focused = self.state.focused == Some(id);
+		let color = if focused { self.style.active_color } else { self.widget_color(interaction) };
+		self.push(color, UiShape::Rect(field));
+
+		// Keep the text inside the field
+		let clip = self.layout().clip.map_or(field, |clip| clip.intersect(field));
+		let parent_clip = self.state.layouts.last_mut().unwrap().clip.replace(clip);
+
+		let text_position = field.min + padding;
+		if focused {
+			let offset = |ui: &Self, index: usize| text_position.x + ui.text_size(&text[..index]).x;
+
+			if let Some(selection) = self.state.editor.selection() {
+				let (start, end) = (offset(self, selection.start), offset(self, selection.end));
+				self.push(self.style.hovered_color, UiShape::Rect(Rect::new(start, text_position.y, end - start, label_size.y)));
+			}
+
+			let caret_x = offset(self, self.state.editor.cursor());
+			self.push(self.style.accent_color, UiShape::Rect(Rect::new(caret_x, text_position.y, 1.0, label_size.y)));
+		}
+		self.push(self.style.text_color, UiShape::Text(text_position, text.clone()));
+
+		self.state.layouts.last_mut().unwrap().clip = parent_clip;
+		self.push(self.style.text_color, UiShape::Text(vec2(field.max.x + self.style.spacing, rect.min.y + padding), display.to_string()));
+
+		changed
+	}
+
+	/// A movable window, drawn on top of widgets outside of windows. `position` and `size` are only used the first time the window is declared
+	pub fn window(&mut self, title: &str, position: Vec2, size: Vec2, contents: impl FnOnce(&mut Self)) {
+		let id = self.id(title);
+		let (padding, spacing) = (self.style.padding, self.style.spacing);
+		let title_height = self.text_size(title).y + padding * 2.0;
+
+		let state = self.state.as_mut();
+		let window = *state.windows.entry(id).or_insert_with(|| {
+			state.top_z += 1;
+			WindowState {
+				rect: Rect::new(position.x, position.y, size.x, size.y),
+				z: state.top_z,
+				visible: true,
+			}
+		});
+
+		// Drag the window around by its title bar
+		let mut rect = window.rect;
+		let title_bar = Rect::new(rect.min.x, rect.min.y, rect.width(), title_height);
+		let input = &self.input;
+
+		if state.hovered_window == Some(id) && input.pressed && state.active.is_none() && title_bar.contains(input.mouse) {
+			state.active = Some(id);
+		}
+		if state.active == Some(id) {
+			if input.down {
+				rect.min += input.mouse_delta;
+				rect.max += input.mouse_delta;
+			} else {
+				state.active = None;
+			}
+		}
+
+		state.windows.insert(id, WindowState { rect, visible: true, ..window });
+		state.layouts.push(Layout {
+			id,
+			x: rect.min.x + padding,
+			width: rect.width() - padding * 2.0,
+			cursor: rect.min.y + title_height + spacing,
+			clip: Some(rect),
+			layer: window.z,
+			interactive: state.hovered_window == Some(id),
+		});
+
+		self.push(self.style.window_color, UiShape::Rect(rect));
+		self.push(self.style.title_color, UiShape::Rect(Rect::new(rect.min.x, rect.min.y, rect.width(), title_height)));
+		self.push(self.style.text_color, UiShape::Text(rect.min + padding, display_text(title).to_string()));
+
+		contents(self);
+		self.state.layouts.pop();
+	}
+
+	/// An area of fixed height, scrolled with the mouse wheel when its contents don't fit
+	pub fn scroll_area(&mut self, label: &str, height: f32, contents: impl FnOnce(&mut Self)) {
+		let id = self.id(label);
+		let padding = self.style.padding;
+		let scrollbar_width = padding * 2.0;
+
+		let parent = *self.layout();
+		let rect = self.allocate(height);
+		let mut scroll = self.state.scroll_areas.get(&id).copied().unwrap_or_default();
+
+		let hovered = parent.interactive && rect.contains(self.input.mouse) && parent.clip.is_none_or(|clip| clip.contains(self.input.mouse));
+		if hovered {
+			scroll.offset -= self.input.wheel * self.style.font_size * 3.0;
+		}
+		scroll.offset = scroll.offset.clamp(0.0, (scroll.content_height - height).max(0.0));
+
+		self.push(self.style.widget_color, UiShape::Rect(rect));
+
+		let top = rect.min.y + padding - scroll.offset;
+		self.state.layouts.push(Layout {
+			id,
+			x: rect.min.x + padding,
+			width: rect.width() - padding * 2.0 - scrollbar_width,
+			cursor: top,
+			clip: Some(parent.clip.map_or(rect, |clip| clip.intersect(rect))),
+			layer: parent.layer,
+			interactive: hovered,
+		});
+
+		contents(self);
+
+		let layout = self.state.layouts.pop().unwrap();
+		scroll.content_height = layout.cursor - top + padding;
+		self.state.scroll_areas.insert(id, scroll);
+
+		if scroll.content_height > height {
+			let thumb_height = height * height / scroll.content_height;
+			let thumb_y = rect.min.y + (height - thumb_height) * scroll.offset / (scroll.content_height - height);
+			self.push(self.style.hovered_color, UiShape::Rect(Rect::new(rect.max.x - scrollbar_width, thumb_y, scrollbar_width, thumb_height)));
+		}
+	}
+}
@@ -0,0 +1,36 @@
+use bevy_ecs::system::{ResMut, Resource, SystemParam};
+
+/// Backing storage of [`Clipboard`]
+#[derive(Debug, Default, Resource)]
+pub struct ClipboardState {
+	/// Set once the `miniquad` event loop is running, the OS clipboard can't be used before
+	pub(crate) system: bool,
+	/// In-process clipboard, used when the OS one isn't available
+	contents: Option<String>,
+}
+
+/// Access to the OS clipboard, through `miniquad`.
+/// Outside of the `miniquad` event loop (tests, headless apps) the clipboard is kept in memory instead
+#[derive(SystemParam)]
+pub struct Clipboard<'w> {
+	state: ResMut<'w, ClipboardState>,
+}
+
+impl Clipboard<'_> {
+	/// Current text in the clipboard, if any
+	pub fn get(&self) -> Option<String> {
+		if self.state.system {
+			miniquad::window::clipboard_get()
+		} else {
+			self.state.contents.clone()
+		}
+	}
+
+	pub fn set(&mut self, text: &str) {
+		if self.state.system {
+			miniquad::window::clipboard_set(text);
+		} else {
+			self.state.contents = Some(text.to_string());
+		}
+	}
+}
@@ -127,7 +127,7 @@ impl std::ops::Deref for TouchEvent {
 	}
 }
 
-#[derive(Debug, Event)]
+#[derive(Debug, Clone, Event)]
 pub struct KeyCodeEvent {
 	pub keycode: miniquad::KeyCode,
 	pub mods: miniquad::KeyMods,
@@ -143,7 +143,7 @@ impl std::ops::Deref for KeyCodeEvent {
 	}
 }
 
-#[derive(Debug, Event)]
+#[derive(Debug, Clone, Event)]
 pub struct KeyCharEvent {
 	pub character: char,
 	pub mods: miniquad::KeyMods,
@@ -164,3 +164,56 @@ pub struct DroppedFileEvent {
 	pub path: PathBuf,
 	pub bytes: Option<Vec<u8>>,
 }
+
+/// Buttons of a standard gamepad, named after their position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadButton {
+	/// Bottom face button, `A` on Xbox controllers
+	South,
+	/// Right face button, `B` on Xbox controllers
+	East,
+	/// Top face button, `Y` on Xbox controllers
+	North,
+	/// Left face button, `X` on Xbox controllers
+	West,
+	LeftBumper,
+	LeftTrigger,
+	RightBumper,
+	RightTrigger,
+	Select,
+	Start,
+	Mode,
+	LeftThumb,
+	RightThumb,
+	DPadUp,
+	DPadDown,
+	DPadLeft,
+	DPadRight,
+}
+
+/// Analog axes of a standard gamepad, in the `-1.0..=1.0` range. Y axes point up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadAxis {
+	LeftStickX,
+	LeftStickY,
+	RightStickX,
+	RightStickY,
+	LeftTrigger,
+	RightTrigger,
+}
+
+#[derive(Debug, Clone, Event)]
+pub struct GamepadButtonEvent {
+	pub gamepad: usize,
+	pub button: GamepadButton,
+	pub released: bool,
+}
+
+#[derive(Debug, Clone, Event)]
+pub struct GamepadAxisEvent {
+	pub gamepad: usize,
+	pub axis: GamepadAxis,
+	pub value: f32,
+}
@@ -0,0 +1,352 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+	event::{Event, EventWriter},
+	schedule::{IntoSystemConfigs, SystemSet},
+	system::{Local, NonSendMut, Res, ResMut, Resource},
+};
+
+use super::events::{GamepadAxis, GamepadAxisEvent, GamepadButton, GamepadButtonEvent};
+use super::input::ButtonState;
+
+/// A gamepad was plugged in. Ids of disconnected gamepads may be reused
+#[derive(Debug, Clone, Event)]
+pub struct GamepadConnected {
+	pub gamepad: usize,
+	pub name: String,
+}
+
+#[derive(Debug, Clone, Event)]
+pub struct GamepadDisconnected {
+	pub gamepad: usize,
+}
+
+/// Label of the system updating [`Gamepads`] and sending gamepad events, in [`PreUpdate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
+pub struct GamepadSystem;
+
+/// Unfiltered input reported by a [`GamepadBackend`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawGamepadEvent {
+	Connected { gamepad: usize, name: String },
+	Disconnected { gamepad: usize },
+	Button { gamepad: usize, button: GamepadButton, pressed: bool },
+	Axis { gamepad: usize, axis: GamepadAxis, value: f32 },
+}
+
+/// Source of gamepad input, polled once per frame on the main thread
+pub trait GamepadBackend: 'static {
+	/// Appends the events that happened since the previous poll to `events`
+	fn poll(&mut self, events: &mut Vec<RawGamepadEvent>);
+}
+
+/// A connected gamepad
+#[derive(Debug, Clone)]
+pub struct Gamepad {
+	name: String,
+	buttons: ButtonState<GamepadButton>,
+	axes: HashMap<GamepadAxis, f32>,
+}
+
+impl Gamepad {
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Held buttons, and the ones that changed this frame
+	pub fn buttons(&self) -> &ButtonState<GamepadButton> {
+		&self.buttons
+	}
+
+	/// Value of an axis, with the deadzone applied
+	pub fn axis(&self, axis: GamepadAxis) -> f32 {
+		self.axes.get(&axis).copied().unwrap_or(0.0)
+	}
+}
+
+/// Every connected gamepad, by id
+#[derive(Debug, Clone, Default, Resource)]
+pub struct Gamepads {
+	gamepads: BTreeMap<usize, Gamepad>,
+}
+
+impl Gamepads {
+	pub fn get(&self, gamepad: usize) -> Option<&Gamepad> {
+		self.gamepads.get(&gamepad)
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (usize, &Gamepad)> {
+		self.gamepads.iter().map(|(id, gamepad)| (*id, gamepad))
+	}
+
+	pub fn len(&self) -> usize {
+		self.gamepads.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.gamepads.is_empty()
+	}
+}
+
+/// How raw axis values are filtered
+#[derive(Debug, Clone, Resource)]
+pub struct GamepadSettings {
+	/// Axis values closer to zero than this are ignored, the rest is rescaled to still reach `1.0`
+	pub deadzone: f32,
+	/// Overrides of `deadzone` for specific axes
+	pub axis_deadzones: HashMap<GamepadAxis, f32>,
+}
+
+impl Default for GamepadSettings {
+	fn default() -> Self {
+		Self {
+			deadzone: 0.1,
+			axis_deadzones: HashMap::new(),
+		}
+	}
+}
+
+impl GamepadSettings {
+	/// Applies the deadzone of `axis` to a raw value
+	pub fn filter(&self, axis: GamepadAxis, value: f32) -> f32 {
+		let deadzone = self.axis_deadzones.get(&axis).copied().unwrap_or(self.deadzone).clamp(0.0, 0.99);
+		let value = value.clamp(-1.0, 1.0);
+
+		if value.abs() <= deadzone {
+			0.0
+		} else {
+			value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+		}
+	}
+}
+
+/// Reads gamepads through a [`GamepadBackend`], into [`Gamepads`] and gamepad events.
+/// By default it uses `gilrs` when the `gilrs` feature is enabled, and reports no gamepads otherwise
+#[derive(Default)]
+pub struct GamepadPlugin {
+	backend: Mutex<Option<Box<dyn GamepadBackend + Send>>>,
+}
+
+impl GamepadPlugin {
+	/// Uses a custom backend, like [`MockGamepadBackend`]
+	pub fn new(backend: impl GamepadBackend + Send) -> Self {
+		Self {
+			backend: Mutex::new(Some(Box::new(backend))),
+		}
+	}
+}
+
+/// The backend in use, a non-send resource since some backends are bound to their thread
+pub(crate) struct ActiveGamepadBackend(Box<dyn GamepadBackend>);
+
+impl Plugin for GamepadPlugin {
+	fn build(&self, app: &mut App) {
+		let backend: Option<Box<dyn GamepadBackend>> = match self.backend.lock().unwrap_or_else(PoisonError::into_inner).take() {
+			Some(backend) => Some(backend),
+			None => default_backend(),
+		};
+
+		if let Some(backend) = backend {
+			app.insert_non_send_resource(ActiveGamepadBackend(backend));
+		}
+
+		app.add_event::<GamepadConnected>()
+			.add_event::<GamepadDisconnected>()
+			.init_resource::<Gamepads>()
+			.init_resource::<GamepadSettings>()
+			.add_systems(PreUpdate, poll_gamepads.in_set(GamepadSystem));
+	}
+}
+
+#[cfg(feature = "gilrs")]
+fn default_backend() -> Option<Box<dyn GamepadBackend>> {
+	match gilrs::Gilrs::new() {
+		Ok(gilrs) => Some(Box::new(GilrsBackend(gilrs))),
+		Err(_err) => {
+			#[cfg(feature = "log")]
+			bevy_log::warn!("Failed to initialize gamepads: {}", _err);
+			None
+		}
+	}
+}
+
+#[cfg(not(feature = "gilrs"))]
+fn default_backend() -> Option<Box<dyn GamepadBackend>> {
+	None
+}
+
+#[allow(clippy::too_many_arguments)]
+fn poll_gamepads(
+	backend: Option<NonSendMut<ActiveGamepadBackend>>,
+	mut raw: Local<Vec<RawGamepadEvent>>,
+	mut gamepads: ResMut<Gamepads>,
+	settings: Res<GamepadSettings>,
+	mut connected: EventWriter<GamepadConnected>,
+	mut disconnected: EventWriter<GamepadDisconnected>,
+	mut buttons: EventWriter<GamepadButtonEvent>,
+	mut axes: EventWriter<GamepadAxisEvent>,
+) {
+	for gamepad in gamepads.gamepads.values_mut() {
+		gamepad.buttons.clear();
+	}
+
+	let Some(mut backend) = backend else {
+		return;
+	};
+
+	raw.clear();
+	backend.0.poll(&mut raw);
+
+	for event in raw.drain(..) {
+		match event {
+			RawGamepadEvent::Connected { gamepad, name } => {
+				gamepads.gamepads.insert(
+					gamepad,
+					Gamepad {
+						name: name.clone(),
+						buttons: ButtonState::default(),
+						axes: HashMap::new(),
+					},
+				);
+				connected.send(GamepadConnected { gamepad, name });
+			}
+			RawGamepadEvent::Disconnected { gamepad } => {
+				// Release everything, so readers of button events don't keep stale presses
+				if let Some(state) = gamepads.gamepads.remove(&gamepad) {
+					buttons.send_batch(state.buttons.get_pressed().map(|&button| GamepadButtonEvent { gamepad, button, released: true }));
+					axes.send_batch(state.axes.keys().map(|&axis| GamepadAxisEvent { gamepad, axis, value: 0.0 }));
+				}
+				disconnected.send(GamepadDisconnected { gamepad });
+			}
+			RawGamepadEvent::Button { gamepad, button, pressed } => {
+				let Some(state) = gamepads.gamepads.get_mut(&gamepad) else {
+					continue;
+				};
+
+				if pressed != state.buttons.pressed(button) {
+					if pressed {
+						state.buttons.press(button);
+					} else {
+						state.buttons.release(button);
+					}
+					buttons.send(GamepadButtonEvent { gamepad, button, released: !pressed });
+				}
+			}
+			RawGamepadEvent::Axis { gamepad, axis, value } => {
+				let Some(state) = gamepads.gamepads.get_mut(&gamepad) else {
+					continue;
+				};
+
+				let value = settings.filter(axis, value);
+				let previous = state.axes.insert(axis, value).unwrap_or(0.0);
+				if value != previous {
+					axes.send(GamepadAxisEvent { gamepad, axis, value });
+				}
+			}
+		}
+	}
+}
+
+/// A backend replaying scripted events, for tests. Clones share the same queue, so events can be pushed after the plugin took it
+#[derive(Debug, Clone, Default)]
+pub struct MockGamepadBackend {
+	queue: Arc<Mutex<VecDeque<Vec<RawGamepadEvent>>>>,
+}
+
+impl MockGamepadBackend {
+	/// Queues events to be reported by the next poll that doesn't already have events
+	pub fn push_frame(&self, events: impl IntoIterator<Item = RawGamepadEvent>) {
+		self.queue.lock().unwrap_or_else(PoisonError::into_inner).push_back(events.into_iter().collect());
+	}
+
+	/// Queues a single event for the next poll
+	pub fn push(&self, event: RawGamepadEvent) {
+		self.push_frame([event]);
+	}
+}
+
+impl GamepadBackend for MockGamepadBackend {
+	fn poll(&mut self, events: &mut Vec<RawGamepadEvent>) {
+		if let Some(frame) = self.queue.lock().unwrap_or_else(PoisonError::into_inner).pop_front() {
+			events.extend(frame);
+		}
+	}
+}
+
+/// Desktop and Web gamepads through `gilrs`
+#[cfg(feature = "gilrs")]
+pub struct GilrsBackend(pub gilrs::Gilrs);
+
+#[cfg(feature = "gilrs")]
+impl GamepadBackend for GilrsBackend {
+	fn poll(&mut self, events: &mut Vec<RawGamepadEvent>) {
+		use gilrs::{Axis, Button, EventType};
+
+		let button = |button: Button| {
+			Some(match button {
+				Button::South => GamepadButton::South,
+				Button::East => GamepadButton::East,
+				Button::North => GamepadButton::North,
+				Button::West => GamepadButton::West,
+				Button::LeftTrigger => GamepadButton::LeftBumper,
+				Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+				Button::RightTrigger => GamepadButton::RightBumper,
+				Button::RightTrigger2 => GamepadButton::RightTrigger,
+				Button::Select => GamepadButton::Select,
+				Button::Start => GamepadButton::Start,
+				Button::Mode => GamepadButton::Mode,
+				Button::LeftThumb => GamepadButton::LeftThumb,
+				Button::RightThumb => GamepadButton::RightThumb,
+				Button::DPadUp => GamepadButton::DPadUp,
+				Button::DPadDown => GamepadButton::DPadDown,
+				Button::DPadLeft => GamepadButton::DPadLeft,
+				Button::DPadRight => GamepadButton::DPadRight,
+				_ => return None,
+			})
+		};
+
+		while let Some(gilrs::Event { id, event, .. }) = self.0.next_event() {
+			let gamepad = usize::from(id);
+
+			match event {
+				EventType::Connected => events.push(RawGamepadEvent::Connected {
+					gamepad,
+					name: self.0.gamepad(id).name().to_string(),
+				}),
+				EventType::Disconnected => events.push(RawGamepadEvent::Disconnected { gamepad }),
+				EventType::ButtonPressed(pressed, _) | EventType::ButtonReleased(pressed, _) => {
+					if let Some(button) = button(pressed) {
+						let pressed = matches!(event, EventType::ButtonPressed(..));
+						events.push(RawGamepadEvent::Button { gamepad, button, pressed });
+					}
+				}
+				// Analog triggers are reported as button values
+				EventType::ButtonChanged(Button::LeftTrigger2, value, _) => events.push(RawGamepadEvent::Axis {
+					gamepad,
+					axis: GamepadAxis::LeftTrigger,
+					value,
+				}),
+				EventType::ButtonChanged(Button::RightTrigger2, value, _) => events.push(RawGamepadEvent::Axis {
+					gamepad,
+					axis: GamepadAxis::RightTrigger,
+					value,
+				}),
+				EventType::AxisChanged(axis, value, _) => {
+					let axis = match axis {
+						Axis::LeftStickX => GamepadAxis::LeftStickX,
+						Axis::LeftStickY => GamepadAxis::LeftStickY,
+						Axis::RightStickX => GamepadAxis::RightStickX,
+						Axis::RightStickY => GamepadAxis::RightStickY,
+						Axis::LeftZ => GamepadAxis::LeftTrigger,
+						Axis::RightZ => GamepadAxis::RightTrigger,
+						_ => continue,
+					};
+					events.push(RawGamepadEvent::Axis { gamepad, axis, value });
+				}
+				_ => {}
+			}
+		}
+	}
+}
@@ -0,0 +1,88 @@
+use bevy_app::{App, AppExit, Plugin, PluginsState};
+use miniquad::EventHandler;
+
+use super::events::WindowProperties;
+use super::state::{Headless, QuadifyState};
+use crate::render::{null::NullBackend, software::SoftwareBackend, RenderingBackend};
+
+/// Runs the app without a window or a GPU, for tests and CI.
+///
+/// Rendering goes to a [`NullBackend`], what it records is available as the [`RenderRecord`](crate::prelude::RenderRecord) non-send resource.
+/// With `rasterize`, a [`SoftwareBackend`] also draws the frames, its screen is available as the [`SoftwareScreen`](crate::prelude::SoftwareScreen) non-send resource.
+/// Each frame runs `Update` and the draw schedules, the same as the `miniquad` event loop would.
+/// Replaces the runner of the [`WindowPlugin`](super::WindowPlugin), so it has to be added after it
+pub struct HeadlessPlugin {
+	/// Number of frames to run, unless an [`AppExit`] is sent first
+	pub frames: u32,
+	/// Size of the screen drawn into
+	pub width: u32,
+	pub height: u32,
+	/// Draws the frames on the CPU, for comparing them against golden images
+	pub rasterize: bool,
+}
+
+impl Default for HeadlessPlugin {
+	fn default() -> Self {
+		let conf = miniquad::conf::Conf::default();
+
+		Self {
+			frames: 1,
+			width: conf.window_width,
+			height: conf.window_height,
+			rasterize: false,
+		}
+	}
+}
+
+impl HeadlessPlugin {
+	/// Runs `frames` frames with the default screen size
+	pub fn new(frames: u32) -> Self {
+		Self { frames, ..Default::default() }
+	}
+}
+
+impl Plugin for HeadlessPlugin {
+	fn build(&self, app: &mut App) {
+		// Inserted right away, so apps can also be stepped manually with `App::update` and `World::run_schedule`
+		if self.rasterize {
+			let backend = SoftwareBackend::new(self.width, self.height);
+			app.insert_non_send_resource(backend.record())
+				.insert_non_send_resource(backend.screen())
+				.insert_non_send_resource(RenderingBackend::software(backend));
+		} else {
+			let backend = NullBackend::new();
+			app.insert_non_send_resource(backend.record())
+				.insert_non_send_resource(RenderingBackend::headless(Box::new(backend), self.width, self.height));
+		}
+		app.insert_resource(Headless);
+
+		// Widgets and egui lay themselves out in the window's size
+		if let Some(mut window) = app.world_mut().get_resource_mut::<WindowProperties>() {
+			window.width = self.width;
+			window.height = self.height;
+		}
+
+		let frames = self.frames;
+		app.set_runner(move |mut app| {
+			while app.plugins_state() == PluginsState::Adding {
+				#[cfg(not(target_arch = "wasm32"))]
+				bevy_tasks::tick_global_task_pools_on_main_thread();
+			}
+			app.finish();
+			app.cleanup();
+
+			let mut state = QuadifyState::headless(app);
+			for _ in 0..frames {
+				state.update();
+				state.draw();
+
+				if let Some(exit) = state.should_exit() {
+					return exit;
+				}
+			}
+
+			state.save_recording();
+			AppExit::Success
+		});
+	}
+}
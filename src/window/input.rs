@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use bevy_ecs::system::Resource;
+use glam::Vec2;
+use miniquad::{KeyCode, MouseButton};
+
+/// Tracks which buttons are held, and which changed this frame. `just_pressed` and `just_released` are cleared at the end of every frame
+#[derive(Debug, Clone, Resource)]
+pub struct ButtonState<T: Copy + Eq + Hash + Send + Sync + 'static> {
+	pressed: HashSet<T>,
+	just_pressed: HashSet<T>,
+	just_released: HashSet<T>,
+}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Default for ButtonState<T> {
+	fn default() -> Self {
+		Self {
+			pressed: HashSet::new(),
+			just_pressed: HashSet::new(),
+			just_released: HashSet::new(),
+		}
+	}
+}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> ButtonState<T> {
+	/// Registers a press. Repeated presses of a held button are ignored
+	pub fn press(&mut self, button: T) {
+		if self.pressed.insert(button) {
+			self.just_pressed.insert(button);
+		}
+	}
+
+	pub fn release(&mut self, button: T) {
+		if self.pressed.remove(&button) {
+			self.just_released.insert(button);
+		}
+	}
+
+	/// Releases every held button, like when the window loses focus
+	pub fn release_all(&mut self) {
+		self.just_released.extend(self.pressed.drain());
+	}
+
+	/// Forgets the buttons that changed this frame, without releasing anything
+	pub fn clear(&mut self) {
+		self.just_pressed.clear();
+		self.just_released.clear();
+	}
+
+	/// Releases everything and clears this frame's changes
+	pub fn reset_all(&mut self) {
+		self.pressed.clear();
+		self.clear();
+	}
+
+	pub fn pressed(&self, button: T) -> bool {
+		self.pressed.contains(&button)
+	}
+
+	pub fn just_pressed(&self, button: T) -> bool {
+		self.just_pressed.contains(&button)
+	}
+
+	pub fn just_released(&self, button: T) -> bool {
+		self.just_released.contains(&button)
+	}
+
+	pub fn any_pressed(&self, buttons: impl IntoIterator<Item = T>) -> bool {
+		buttons.into_iter().any(|button| self.pressed(button))
+	}
+
+	pub fn all_pressed(&self, buttons: impl IntoIterator<Item = T>) -> bool {
+		buttons.into_iter().all(|button| self.pressed(button))
+	}
+
+	pub fn any_just_pressed(&self, buttons: impl IntoIterator<Item = T>) -> bool {
+		buttons.into_iter().any(|button| self.just_pressed(button))
+	}
+
+	pub fn any_just_released(&self, buttons: impl IntoIterator<Item = T>) -> bool {
+		buttons.into_iter().any(|button| self.just_released(button))
+	}
+
+	pub fn get_pressed(&self) -> impl ExactSizeIterator<Item = &T> {
+		self.pressed.iter()
+	}
+
+	pub fn get_just_pressed(&self) -> impl ExactSizeIterator<Item = &T> {
+		self.just_pressed.iter()
+	}
+
+	pub fn get_just_released(&self) -> impl ExactSizeIterator<Item = &T> {
+		self.just_released.iter()
+	}
+}
+
+/// Held keys, fed by the window's key events. Keys are released when the window is minimized, as their release events would be missed
+pub type KeyboardState = ButtonState<KeyCode>;
+
+/// Mouse buttons, position and motion, fed by the window's mouse events. Derefs to the [`ButtonState`] of its buttons
+#[derive(Debug, Clone, Default, Resource)]
+pub struct MouseState {
+	buttons: ButtonState<MouseButton>,
+	/// Cursor position in physical pixels, from the top-left corner of the window
+	pub position: Vec2,
+	/// Cursor position in logical pixels, the physical position divided by the DPI scale
+	pub logical_position: Vec2,
+	/// Sum of the motion this frame, in physical pixels
+	pub motion: Vec2,
+	/// Sum of the wheel scrolling this frame
+	pub wheel: Vec2,
+}
+
+impl MouseState {
+	/// Moves the cursor to `position` in physical pixels
+	pub fn move_to(&mut self, position: Vec2, dpi_scale: f32) {
+		self.position = position;
+		self.logical_position = position / dpi_scale;
+	}
+
+	/// Forgets this frame's button changes, motion and scrolling
+	pub fn clear(&mut self) {
+		self.buttons.clear();
+		self.motion = Vec2::ZERO;
+		self.wheel = Vec2::ZERO;
+	}
+}
+
+impl std::ops::Deref for MouseState {
+	type Target = ButtonState<MouseButton>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.buttons
+	}
+}
+
+impl std::ops::DerefMut for MouseState {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.buttons
+	}
+}
@@ -0,0 +1,312 @@
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+	entity::Entity,
+	event::{EventReader, EventWriter},
+	schedule::IntoSystemConfigs,
+};
+use bevy_input::{
+	keyboard::{Key, KeyCode, KeyboardFocusLost, KeyboardInput, NativeKey, NativeKeyCode},
+	mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+	touch::{TouchInput, TouchPhase},
+	ButtonState, InputPlugin, InputSystem,
+};
+use smol_str::SmolStr;
+
+use super::events;
+
+/// Adds [`bevy_input`]'s [`InputPlugin`], and translates quadify's input events into its events, [`ButtonInput`](bevy_input::ButtonInput)s and [`Touches`](bevy_input::touch::Touches).
+/// There's no window entity, so events point to [`Entity::PLACEHOLDER`]
+pub struct InputBridgePlugin;
+
+impl Plugin for InputBridgePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_plugins(InputPlugin).add_systems(PreUpdate, (bridge_keyboard, bridge_mouse, bridge_touches).before(InputSystem));
+	}
+}
+
+fn bridge_keyboard(
+	mut key_events: EventReader<events::KeyCodeEvent>,
+	mut window_events: EventReader<events::WindowEvent>,
+	mut keyboard: EventWriter<KeyboardInput>,
+	mut focus_lost: EventWriter<KeyboardFocusLost>,
+) {
+	for event in key_events.read() {
+		keyboard.send(KeyboardInput {
+			key_code: key_code(event.keycode),
+			logical_key: logical_key(event.keycode, event.mods.shift),
+			state: button_state(event.released),
+			repeat: event.repeat,
+			window: Entity::PLACEHOLDER,
+		});
+	}
+
+	if window_events.read().any(|event| matches!(event, events::WindowEvent::Minimized)) {
+		focus_lost.send(KeyboardFocusLost);
+	}
+}
+
+fn bridge_mouse(
+	mut button_events: EventReader<events::MouseButtonEvent>,
+	mut motion_events: EventReader<events::MouseMotionEvent>,
+	mut wheel_events: EventReader<events::MouseWheelEvent>,
+	mut buttons: EventWriter<MouseButtonInput>,
+	mut motion: EventWriter<MouseMotion>,
+	mut wheel: EventWriter<MouseWheel>,
+) {
+	for event in button_events.read() {
+		buttons.send(MouseButtonInput {
+			button: mouse_button(event.button),
+			state: button_state(event.released),
+			window: Entity::PLACEHOLDER,
+		});
+	}
+
+	motion.send_batch(motion_events.read().map(|event| MouseMotion { delta: event.delta }));
+	wheel.send_batch(wheel_events.read().map(|event| MouseWheel {
+		unit: MouseScrollUnit::Line,
+		x: event.delta.x,
+		y: event.delta.y,
+		window: Entity::PLACEHOLDER,
+	}));
+}
+
+fn bridge_touches(mut touch_events: EventReader<events::TouchEvent>, mut touches: EventWriter<TouchInput>) {
+	touches.send_batch(touch_events.read().map(|event| TouchInput {
+		phase: match event.phase {
+			miniquad::TouchPhase::Started => TouchPhase::Started,
+			miniquad::TouchPhase::Moved => TouchPhase::Moved,
+			miniquad::TouchPhase::Ended => TouchPhase::Ended,
+			miniquad::TouchPhase::Cancelled => TouchPhase::Canceled,
+		},
+		position: event.position,
+		window: Entity::PLACEHOLDER,
+		force: None,
+		id: event.id,
+	}));
+}
+
+fn button_state(released: bool) -> ButtonState {
+	if released {
+		ButtonState::Released
+	} else {
+		ButtonState::Pressed
+	}
+}
+
+fn mouse_button(button: miniquad::MouseButton) -> MouseButton {
+	match button {
+		miniquad::MouseButton::Left => MouseButton::Left,
+		miniquad::MouseButton::Right => MouseButton::Right,
+		miniquad::MouseButton::Middle => MouseButton::Middle,
+		miniquad::MouseButton::Unknown => MouseButton::Other(0),
+	}
+}
+
+/// Physical key, miniquad names keys after their US layout position as well
+pub(crate) fn key_code(key: miniquad::KeyCode) -> KeyCode {
+	use miniquad::KeyCode as Mq;
+
+	match key {
+		Mq::Space => KeyCode::Space,
+		Mq::Apostrophe => KeyCode::Quote,
+		Mq::Comma => KeyCode::Comma,
+		Mq::Minus => KeyCode::Minus,
+		Mq::Period => KeyCode::Period,
+		Mq::Slash => KeyCode::Slash,
+		Mq::Key0 => KeyCode::Digit0,
+		Mq::Key1 => KeyCode::Digit1,
+		Mq::Key2 => KeyCode::Digit2,
+		Mq::Key3 => KeyCode::Digit3,
+		Mq::Key4 => KeyCode::Digit4,
+		Mq::Key5 => KeyCode::Digit5,
+		Mq::Key6 => KeyCode::Digit6,
+		Mq::Key7 => KeyCode::Digit7,
+		Mq::Key8 => KeyCode::Digit8,
+		Mq::Key9 => KeyCode::Digit9,
+		Mq::Semicolon => KeyCode::Semicolon,
+		Mq::Equal => KeyCode::Equal,
+		Mq::A => KeyCode::KeyA,
+		Mq::B => KeyCode::KeyB,
+		Mq::C => KeyCode::KeyC,
+		Mq::D => KeyCode::KeyD,
+		Mq::E => KeyCode::KeyE,
+		Mq::F => KeyCode::KeyF,
+		Mq::G => KeyCode::KeyG,
+		Mq::H => KeyCode::KeyH,
+		Mq::I => KeyCode::KeyI,
+		Mq::J => KeyCode::KeyJ,
+		Mq::K => KeyCode::KeyK,
+		Mq::L => KeyCode::KeyL,
+		Mq::M => KeyCode::KeyM,
+		Mq::N => KeyCode::KeyN,
+		Mq::O => KeyCode::KeyO,
+		Mq::P => KeyCode::KeyP,
+		Mq::Q => KeyCode::KeyQ,
+		Mq::R => KeyCode::KeyR,
+		Mq::S => KeyCode::KeyS,
+		Mq::T => KeyCode::KeyT,
+		Mq::U => KeyCode::KeyU,
+		Mq::V => KeyCode::KeyV,
+		Mq::W => KeyCode::KeyW,
+		Mq::X => KeyCode::KeyX,
+		Mq::Y => KeyCode::KeyY,
+		Mq::Z => KeyCode::KeyZ,
+		Mq::LeftBracket => KeyCode::BracketLeft,
+		Mq::Backslash => KeyCode::Backslash,
+		Mq::RightBracket => KeyCode::BracketRight,
+		Mq::GraveAccent => KeyCode::Backquote,
+		Mq::World1 => KeyCode::IntlBackslash,
+		Mq::World2 => KeyCode::IntlRo,
+		Mq::Escape => KeyCode::Escape,
+		Mq::Enter => KeyCode::Enter,
+		Mq::Tab => KeyCode::Tab,
+		Mq::Backspace => KeyCode::Backspace,
+		Mq::Insert => KeyCode::Insert,
+		Mq::Delete => KeyCode::Delete,
+		Mq::Right => KeyCode::ArrowRight,
+		Mq::Left => KeyCode::ArrowLeft,
+		Mq::Down => KeyCode::ArrowDown,
+		Mq::Up => KeyCode::ArrowUp,
+		Mq::PageUp => KeyCode::PageUp,
+		Mq::PageDown => KeyCode::PageDown,
+		Mq::Home => KeyCode::Home,
+		Mq::End => KeyCode::End,
+		Mq::CapsLock => KeyCode::CapsLock,
+		Mq::ScrollLock => KeyCode::ScrollLock,
+		Mq::NumLock => KeyCode::NumLock,
+		Mq::PrintScreen => KeyCode::PrintScreen,
+		Mq::Pause => KeyCode::Pause,
+		Mq::F1 => KeyCode::F1,
+		Mq::F2 => KeyCode::F2,
+		Mq::F3 => KeyCode::F3,
+		Mq::F4 => KeyCode::F4,
+		Mq::F5 => KeyCode::F5,
+		Mq::F6 => KeyCode::F6,
+		Mq::F7 => KeyCode::F7,
+		Mq::F8 => KeyCode::F8,
+		Mq::F9 => KeyCode::F9,
+		Mq::F10 => KeyCode::F10,
+		Mq::F11 => KeyCode::F11,
+		Mq::F12 => KeyCode::F12,
+		Mq::F13 => KeyCode::F13,
+		Mq::F14 => KeyCode::F14,
+		Mq::F15 => KeyCode::F15,
+		Mq::F16 => KeyCode::F16,
+		Mq::F17 => KeyCode::F17,
+		Mq::F18 => KeyCode::F18,
+		Mq::F19 => KeyCode::F19,
+		Mq::F20 => KeyCode::F20,
+		Mq::F21 => KeyCode::F21,
+		Mq::F22 => KeyCode::F22,
+		Mq::F23 => KeyCode::F23,
+		Mq::F24 => KeyCode::F24,
+		Mq::F25 => KeyCode::F25,
+		Mq::Kp0 => KeyCode::Numpad0,
+		Mq::Kp1 => KeyCode::Numpad1,
+		Mq::Kp2 => KeyCode::Numpad2,
+		Mq::Kp3 => KeyCode::Numpad3,
+		Mq::Kp4 => KeyCode::Numpad4,
+		Mq::Kp5 => KeyCode::Numpad5,
+		Mq::Kp6 => KeyCode::Numpad6,
+		Mq::Kp7 => KeyCode::Numpad7,
+		Mq::Kp8 => KeyCode::Numpad8,
+		Mq::Kp9 => KeyCode::Numpad9,
+		Mq::KpDecimal => KeyCode::NumpadDecimal,
+		Mq::KpDivide => KeyCode::NumpadDivide,
+		Mq::KpMultiply => KeyCode::NumpadMultiply,
+		Mq::KpSubtract => KeyCode::NumpadSubtract,
+		Mq::KpAdd => KeyCode::NumpadAdd,
+		Mq::KpEnter => KeyCode::NumpadEnter,
+		Mq::KpEqual => KeyCode::NumpadEqual,
+		Mq::LeftShift => KeyCode::ShiftLeft,
+		Mq::LeftControl => KeyCode::ControlLeft,
+		Mq::LeftAlt => KeyCode::AltLeft,
+		Mq::LeftSuper => KeyCode::SuperLeft,
+		Mq::RightShift => KeyCode::ShiftRight,
+		Mq::RightControl => KeyCode::ControlRight,
+		Mq::RightAlt => KeyCode::AltRight,
+		Mq::RightSuper => KeyCode::SuperRight,
+		Mq::Menu => KeyCode::ContextMenu,
+		Mq::Back => KeyCode::BrowserBack,
+		Mq::Unknown => KeyCode::Unidentified(NativeKeyCode::Unidentified),
+	}
+}
+
+/// Logical key, assuming a US layout. Typed text still comes from [`KeyCharEvent`](events::KeyCharEvent)s
+pub(crate) fn logical_key(key: miniquad::KeyCode, shift: bool) -> Key {
+	use miniquad::KeyCode as Mq;
+
+	let character = |lower: char, upper: char| Key::Character(SmolStr::new(if shift { upper } else { lower }.encode_utf8(&mut [0; 4])));
+
+	match key {
+		Mq::Space => Key::Space,
+		Mq::Apostrophe => character('\'', '"'),
+		Mq::Comma => character(',', '<'),
+		Mq::Minus => character('-', '_'),
+		Mq::Period => character('.', '>'),
+		Mq::Slash => character('/', '?'),
+		Mq::Semicolon => character(';', ':'),
+		Mq::Equal => character('=', '+'),
+		Mq::LeftBracket => character('[', '{'),
+		Mq::Backslash => character('\\', '|'),
+		Mq::RightBracket => character(']', '}'),
+		Mq::GraveAccent => character('`', '~'),
+		Mq::Key0 | Mq::Key1 | Mq::Key2 | Mq::Key3 | Mq::Key4 | Mq::Key5 | Mq::Key6 | Mq::Key7 | Mq::Key8 | Mq::Key9 => {
+			let digit = char::from(b'0' + (key as u16 - Mq::Key0 as u16) as u8);
+			let symbol = b")!@#$%^&*("[(key as u16 - Mq::Key0 as u16) as usize] as char;
+			character(digit, symbol)
+		}
+		Mq::Kp0 | Mq::Kp1 | Mq::Kp2 | Mq::Kp3 | Mq::Kp4 | Mq::Kp5 | Mq::Kp6 | Mq::Kp7 | Mq::Kp8 | Mq::Kp9 => {
+			let digit = char::from(b'0' + (key as u16 - Mq::Kp0 as u16) as u8);
+			character(digit, digit)
+		}
+		_ if (Mq::A as u16..=Mq::Z as u16).contains(&(key as u16)) => {
+			let upper = char::from(key as u16 as u8);
+			character(upper.to_ascii_lowercase(), upper)
+		}
+		Mq::KpDecimal => character('.', '.'),
+		Mq::KpDivide => character('/', '/'),
+		Mq::KpMultiply => character('*', '*'),
+		Mq::KpSubtract => character('-', '-'),
+		Mq::KpAdd => character('+', '+'),
+		Mq::KpEqual => character('=', '='),
+		Mq::Escape => Key::Escape,
+		Mq::Enter | Mq::KpEnter => Key::Enter,
+		Mq::Tab => Key::Tab,
+		Mq::Backspace => Key::Backspace,
+		Mq::Insert => Key::Insert,
+		Mq::Delete => Key::Delete,
+		Mq::Right => Key::ArrowRight,
+		Mq::Left => Key::ArrowLeft,
+		Mq::Down => Key::ArrowDown,
+		Mq::Up => Key::ArrowUp,
+		Mq::PageUp => Key::PageUp,
+		Mq::PageDown => Key::PageDown,
+		Mq::Home => Key::Home,
+		Mq::End => Key::End,
+		Mq::CapsLock => Key::CapsLock,
+		Mq::ScrollLock => Key::ScrollLock,
+		Mq::NumLock => Key::NumLock,
+		Mq::PrintScreen => Key::PrintScreen,
+		Mq::Pause => Key::Pause,
+		Mq::F1 => Key::F1,
+		Mq::F2 => Key::F2,
+		Mq::F3 => Key::F3,
+		Mq::F4 => Key::F4,
+		Mq::F5 => Key::F5,
+		Mq::F6 => Key::F6,
+		Mq::F7 => Key::F7,
+		Mq::F8 => Key::F8,
+		Mq::F9 => Key::F9,
+		Mq::F10 => Key::F10,
+		Mq::F11 => Key::F11,
+		Mq::F12 => Key::F12,
+		Mq::LeftShift | Mq::RightShift => Key::Shift,
+		Mq::LeftControl | Mq::RightControl => Key::Control,
+		Mq::LeftAlt | Mq::RightAlt => Key::Alt,
+		Mq::LeftSuper | Mq::RightSuper => Key::Super,
+		Mq::Menu => Key::ContextMenu,
+		Mq::Back => Key::GoBack,
+		_ => Key::Unidentified(NativeKey::Unidentified),
+	}
+}
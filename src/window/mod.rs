@@ -1,10 +1,23 @@
-use bevy_app::{App, AppExit, Last, Plugin};
-use bevy_ecs::schedule::ExecutorKind;
+use bevy_app::{App, AppExit, Last, Plugin, PreUpdate};
+use bevy_ecs::schedule::{
+	common_conditions::{not, resource_exists},
+	ExecutorKind, IntoSystemConfigs,
+};
 use miniquad::conf::{Conf, PlatformSettings};
 
+pub(crate) mod clipboard;
 pub(crate) mod events;
+pub(crate) mod gamepad;
+#[cfg(feature = "headless")]
+pub(crate) mod headless;
 pub(crate) mod icon;
+pub(crate) mod input;
+#[cfg(feature = "bevy_input")]
+pub(crate) mod input_bridge;
+pub(crate) mod replay;
 pub(crate) mod state;
+pub(crate) mod text_input;
+pub(crate) mod touch;
 
 /// Initializes main window and starts the `miniquad` event loop.
 pub struct WindowPlugin {
@@ -82,8 +95,20 @@ impl Plugin for WindowPlugin {
 			.add_event::<events::MouseMotionEvent>()
 			.add_event::<events::MouseWheelEvent>()
 			.add_event::<events::TouchEvent>()
+			.add_event::<events::GamepadButtonEvent>()
+			.add_event::<events::GamepadAxisEvent>()
 			.insert_resource(window_properties)
 			.insert_resource(cursor_properties)
+			.init_resource::<input::KeyboardState>()
+			.init_resource::<input::MouseState>()
+			.init_resource::<touch::Touches>()
+			.init_resource::<touch::GestureSettings>()
+			.add_event::<touch::GestureEvent>()
+			.init_resource::<clipboard::ClipboardState>()
+			.init_resource::<text_input::KeystrokeOrder>()
+			.init_resource::<text_input::OnScreenKeyboard>()
+			.add_systems(PreUpdate, (touch::recognize_gestures, text_input::update_text_inputs))
+			.add_systems(Last, (text_input::clear_keystroke_order, text_input::show_on_screen_keyboard))
 			.insert_resource(state::QuitRequested { accept: true, status: 0 })
 			.init_schedule(state::MiniquadPrepareDraw)
 			.edit_schedule(state::MiniquadPrepareDraw, |s| {
@@ -105,7 +130,11 @@ impl Plugin for WindowPlugin {
 			.edit_schedule(state::MiniquadQuitRequestedSchedule, |s| {
 				s.set_executor_kind(ExecutorKind::SingleThreaded);
 			})
-			.add_systems(Last, (events::apply_window_properties, events::apply_cursor_properties, events::quit_on_app_exit));
+			.add_systems(
+				Last,
+				(events::apply_window_properties, events::apply_cursor_properties, events::quit_on_app_exit).run_if(not(resource_exists::<state::Headless>)),
+			)
+			.add_systems(Last, replay::save_recording_on_exit);
 
 		// Init Runner
 		app.set_runner(move |app| {
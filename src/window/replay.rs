@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use bevy_app::AppExit;
+use bevy_ecs::{event::EventReader, system::Res, system::Resource};
+use miniquad::{EventHandler, KeyCode, KeyMods, MouseButton, TouchPhase};
+
+#[cfg(feature = "serialize")]
+use crate::input_map::remote;
+
+/// A window or input event, as received by a `miniquad` [`EventHandler`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputEvent {
+	KeyDown {
+		#[cfg_attr(feature = "serialize", serde(with = "remote::KeyCodeDef"))]
+		keycode: KeyCode,
+		#[cfg_attr(feature = "serialize", serde(with = "remote::KeyModsDef"))]
+		mods: KeyMods,
+		repeat: bool,
+	},
+	KeyUp {
+		#[cfg_attr(feature = "serialize", serde(with = "remote::KeyCodeDef"))]
+		keycode: KeyCode,
+		#[cfg_attr(feature = "serialize", serde(with = "remote::KeyModsDef"))]
+		mods: KeyMods,
+	},
+	Char {
+		character: char,
+		#[cfg_attr(feature = "serialize", serde(with = "remote::KeyModsDef"))]
+		mods: KeyMods,
+		repeat: bool,
+	},
+	/// Absolute position of the cursor
+	MouseMotion {
+		x: f32,
+		y: f32,
+	},
+	MouseButtonDown {
+		#[cfg_attr(feature = "serialize", serde(with = "remote::MouseButtonDef"))]
+		button: MouseButton,
+		x: f32,
+		y: f32,
+	},
+	MouseButtonUp {
+		#[cfg_attr(feature = "serialize", serde(with = "remote::MouseButtonDef"))]
+		button: MouseButton,
+		x: f32,
+		y: f32,
+	},
+	MouseWheel {
+		x: f32,
+		y: f32,
+	},
+	Touch {
+		#[cfg_attr(feature = "serialize", serde(with = "remote::TouchPhaseDef"))]
+		phase: TouchPhase,
+		id: u64,
+		x: f32,
+		y: f32,
+	},
+	Resize {
+		width: f32,
+		height: f32,
+	},
+	Minimized,
+	Restored,
+	FilesDropped {
+		paths: Vec<PathBuf>,
+		bytes: Option<Vec<Vec<u8>>>,
+	},
+	/// The user asked to close the window, the app exits unless [`QuitRequested`](super::state::QuitRequested) refuses it
+	QuitRequested,
+}
+
+impl InputEvent {
+	/// Calls the matching method of `handler`
+	pub fn dispatch(self, handler: &mut impl EventHandler) {
+		match self {
+			InputEvent::KeyDown { keycode, mods, repeat } => handler.key_down_event(keycode, mods, repeat),
+			InputEvent::KeyUp { keycode, mods } => handler.key_up_event(keycode, mods),
+			InputEvent::Char { character, mods, repeat } => handler.char_event(character, mods, repeat),
+			InputEvent::MouseMotion { x, y } => handler.mouse_motion_event(x, y),
+			InputEvent::MouseButtonDown { button, x, y } => handler.mouse_button_down_event(button, x, y),
+			InputEvent::MouseButtonUp { button, x, y } => handler.mouse_button_up_event(button, x, y),
+			InputEvent::MouseWheel { x, y } => handler.mouse_wheel_event(x, y),
+			InputEvent::Touch { phase, id, x, y } => handler.touch_event(phase, id, x, y),
+			InputEvent::Resize { width, height } => handler.resize_event(width, height),
+			InputEvent::Minimized => handler.window_minimized_event(),
+			InputEvent::Restored => handler.window_restored_event(),
+			InputEvent::FilesDropped { paths, bytes } => handler.files_dropped_event(paths, bytes),
+			InputEvent::QuitRequested => {
+				handler.quit_requested_event();
+			}
+		}
+	}
+}
+
+/// An [`InputEvent`] received before the given frame's update
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedInputEvent {
+	/// Number of updates that ran before the event, counted from the start of the script
+	pub frame: u64,
+	pub event: InputEvent,
+}
+
+/// A timeline of input events, recorded by an [`InputRecorder`] or written by hand, and played back by an [`InputInjector`]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputScript {
+	events: Vec<TimedInputEvent>,
+}
+
+impl InputScript {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds an event before the update of `frame`, after any events already on that frame
+	pub fn push(&mut self, frame: u64, event: InputEvent) -> &mut Self {
+		let index = self.events.partition_point(|timed| timed.frame <= frame);
+		self.events.insert(index, TimedInputEvent { frame, event });
+		self
+	}
+
+	/// Events ordered by frame
+	pub fn events(&self) -> &[TimedInputEvent] {
+		&self.events
+	}
+
+	pub fn len(&self) -> usize {
+		self.events.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.events.is_empty()
+	}
+
+	/// Number of frames needed to play every event
+	pub fn frames(&self) -> u64 {
+		self.events.last().map_or(0, |timed| timed.frame + 1)
+	}
+}
+
+#[cfg(feature = "serialize")]
+impl InputScript {
+	pub fn to_ron(&self) -> Result<String, ron::Error> {
+		ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+	}
+
+	pub fn from_ron(ron: &str) -> Result<Self, ron::de::SpannedError> {
+		ron::from_str(ron)
+	}
+
+	pub fn to_json(&self) -> Result<String, serde_json::Error> {
+		serde_json::to_string_pretty(self)
+	}
+
+	pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+		serde_json::from_str(json)
+	}
+
+	/// Writes the script to a RON file
+	pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+		let ron = self.to_ron().map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+		std::fs::write(path, ron)
+	}
+
+	/// Reads a script saved with [`InputScript::save`]
+	pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+		let ron = std::fs::read_to_string(path)?;
+		Self::from_ron(&ron).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+	}
+}
+
+/// Feeds [`InputEvent`]s to the app, through the same code paths as the window's events.
+///
+/// Due events are dispatched at the start of every frame, before the app updates. Works in windowed and headless apps
+#[derive(Debug, Default, Resource)]
+pub struct InputInjector {
+	frame: u64,
+	pending: VecDeque<TimedInputEvent>,
+}
+
+impl InputInjector {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Number of frames that ran since the injector was added
+	pub fn frame(&self) -> u64 {
+		self.frame
+	}
+
+	/// Injects an event before the next update
+	pub fn push(&mut self, event: InputEvent) {
+		self.push_in(0, event);
+	}
+
+	/// Injects an event `frames` updates from now
+	pub fn push_in(&mut self, frames: u64, event: InputEvent) {
+		let frame = self.frame + frames;
+		let index = self.pending.partition_point(|timed| timed.frame <= frame);
+		self.pending.insert(index, TimedInputEvent { frame, event });
+	}
+
+	/// Plays a script, its first frame being the next update
+	pub fn play(&mut self, script: &InputScript) {
+		for timed in script.events() {
+			self.push_in(timed.frame, timed.event.clone());
+		}
+	}
+
+	/// True once every event was injected
+	pub fn is_finished(&self) -> bool {
+		self.pending.is_empty()
+	}
+
+	/// Takes the events due before the current frame's update
+	pub(crate) fn take_due(&mut self) -> Vec<InputEvent> {
+		let due = self.pending.partition_point(|timed| timed.frame <= self.frame);
+		self.pending.drain(..due).map(|timed| timed.event).collect()
+	}
+
+	pub(crate) fn advance(&mut self) {
+		self.frame += 1;
+	}
+}
+
+/// Records the events received by the app into an [`InputScript`], to be replayed with an [`InputInjector`].
+///
+/// Injected events are recorded too. With the `serialize` feature, the recording can be saved to a file when the app exits
+#[derive(Debug, Resource)]
+pub struct InputRecorder {
+	/// Events are only recorded while true
+	pub recording: bool,
+	frame: u64,
+	script: InputScript,
+	#[cfg(feature = "serialize")]
+	path: Option<PathBuf>,
+}
+
+impl Default for InputRecorder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl InputRecorder {
+	/// Starts recording right away
+	pub fn new() -> Self {
+		Self {
+			recording: true,
+			frame: 0,
+			script: InputScript::new(),
+			#[cfg(feature = "serialize")]
+			path: None,
+		}
+	}
+
+	/// Saves the recording to a RON file when the app exits, or when the window is closed
+	#[cfg(feature = "serialize")]
+	pub fn save_on_exit(mut self, path: impl Into<PathBuf>) -> Self {
+		self.path = Some(path.into());
+		self
+	}
+
+	pub fn script(&self) -> &InputScript {
+		&self.script
+	}
+
+	/// Takes the recording so far, the next events are recorded relative to the current frame
+	pub fn take(&mut self) -> InputScript {
+		self.frame = 0;
+		std::mem::take(&mut self.script)
+	}
+
+	pub(crate) fn record(&mut self, event: impl FnOnce() -> InputEvent) {
+		if self.recording {
+			self.script.push(self.frame, event());
+		}
+	}
+
+	pub(crate) fn advance(&mut self) {
+		self.frame += 1;
+	}
+
+	/// Writes the recording to its `save_on_exit` path, if any
+	pub(crate) fn save(&self) {
+		#[cfg(feature = "serialize")]
+		if let Some(path) = &self.path {
+			if let Err(_err) = self.script.save(path) {
+				#[cfg(feature = "log")]
+				bevy_log::error!("Failed to save the input recording to {}: {}", path.display(), _err);
+			}
+		}
+	}
+}
+
+pub(crate) fn save_recording_on_exit(exit: EventReader<AppExit>, recorder: Option<Res<InputRecorder>>) {
+	if let (false, Some(recorder)) = (exit.is_empty(), recorder) {
+		recorder.save();
+	}
+}
@@ -6,7 +6,7 @@ use bevy_ecs::{
 };
 use glam::vec2;
 
-use super::events;
+use super::{clipboard, events, input, replay, text_input, touch};
 use crate::render::RenderingBackend;
 
 /// General `miniquad` state handler for the entire app. It stores bevy's [`App`], manages its event loop and so on
@@ -18,8 +18,71 @@ impl QuadifyState {
 	/// Creates a new `QuadifyState` object
 	pub(crate) fn new(mut app: App) -> Self {
 		app.insert_non_send_resource(RenderingBackend::new());
+
+		// The OS clipboard is only available once the event loop is running
+		if let Some(mut clipboard) = app.world_mut().get_resource_mut::<clipboard::ClipboardState>() {
+			clipboard.system = true;
+		}
+		Self { app }
+	}
+
+	/// Wraps an app that already has a [`RenderingBackend`], to be driven outside of the `miniquad` event loop
+	#[cfg(feature = "headless")]
+	pub(crate) fn headless(app: App) -> Self {
 		Self { app }
 	}
+
+	#[cfg(feature = "headless")]
+	pub(crate) fn should_exit(&self) -> Option<AppExit> {
+		self.app.should_exit()
+	}
+
+	/// Updates an input state resource, if it exists
+	fn input<T: Resource>(&mut self, update: impl FnOnce(&mut T)) {
+		if let Some(mut state) = self.app.world_mut().get_resource_mut::<T>() {
+			update(&mut state);
+		}
+	}
+
+	/// Adds an event to the [`InputRecorder`](replay::InputRecorder), if there is one
+	fn record(&mut self, event: impl FnOnce() -> replay::InputEvent) {
+		self.input(|recorder: &mut replay::InputRecorder| recorder.record(event));
+	}
+
+	/// Dispatches the due events of the [`InputInjector`](replay::InputInjector), as if they came from the window
+	fn inject(&mut self) {
+		let Some(events) = self.app.world_mut().get_resource_mut::<replay::InputInjector>().map(|mut injector| injector.take_due()) else {
+			return;
+		};
+
+		for event in events {
+			match event {
+				// Nothing closes the window for injected requests, the app is asked to exit instead
+				replay::InputEvent::QuitRequested => {
+					if miniquad::EventHandler::quit_requested_event(self) {
+						let status = self.app.world().resource::<QuitRequested>().status;
+						self.app.world_mut().send_event(AppExit::from_code(status));
+					}
+				}
+				event => event.dispatch(self),
+			}
+		}
+	}
+
+	/// Saves the [`InputRecorder`](replay::InputRecorder)'s recording, for exits that skip the app's `Last` schedule
+	pub(crate) fn save_recording(&self) {
+		if let Some(recorder) = self.app.world().get_resource::<replay::InputRecorder>() {
+			recorder.save();
+		}
+	}
+
+	/// Headless apps have no window to query
+	fn dpi_scale(&self) -> f32 {
+		match self.app.world().contains_resource::<Headless>() {
+			true => 1.0,
+			false => miniquad::window::dpi_scale(),
+		}
+	}
 }
 
 /// Systems add to the [`MiniquadDraw`] schedule will be called from within the [`EventHandler::draw`] method
@@ -53,28 +116,56 @@ pub struct QuitRequested {
 	pub status: u8,
 }
 
+/// Marks apps running without a window, `miniquad`'s window functions are unavailable
+#[derive(Debug, Resource)]
+pub(crate) struct Headless;
+
 impl miniquad::EventHandler for QuadifyState {
 	// Called every frame
 	fn update(&mut self) {
+		self.inject();
+
+		let now = miniquad::date::now();
+		self.input(|touches: &mut touch::Touches| touches.advance(now));
+
 		self.app.update();
+
+		// Input events arrive between updates, so changes are kept for exactly one frame
+		self.input(input::KeyboardState::clear);
+		self.input(input::MouseState::clear);
+		self.input(touch::Touches::clear);
+
+		self.input(replay::InputInjector::advance);
+		self.input(replay::InputRecorder::advance);
 	}
 
 	// Called on every frame if App has an active surface
 	fn draw(&mut self) {
 		self.app.world_mut().run_schedule(MiniquadPrepareDraw);
 		self.app.world_mut().run_schedule(MiniquadDraw);
+		self.app.world_mut().run_schedule(MiniquadEndDraw);
 	}
 
 	// WM Events
 	fn window_minimized_event(&mut self) {
+		self.record(|| replay::InputEvent::Minimized);
+
+		// Buttons released while the window is minimized never send their release events
+		self.input(input::KeyboardState::release_all);
+		self.input(|mouse: &mut input::MouseState| mouse.release_all());
+
 		self.app.world_mut().send_event(events::WindowEvent::Minimized);
 	}
 
 	fn window_restored_event(&mut self) {
+		self.record(|| replay::InputEvent::Restored);
+
 		self.app.world_mut().send_event(events::WindowEvent::Restored);
 	}
 
 	fn resize_event(&mut self, width: f32, height: f32) {
+		self.record(|| replay::InputEvent::Resize { width, height });
+
 		if let Some(mut props) = self.app.world_mut().get_resource_mut::<events::WindowProperties>() {
 			// to avoid infinite looping once WindowProperties is applied to the miniquad::window
 			let props = props.bypass_change_detection();
@@ -88,6 +179,14 @@ impl miniquad::EventHandler for QuadifyState {
 
 	// Mouse Events
 	fn mouse_button_down_event(&mut self, button: miniquad::MouseButton, x: f32, y: f32) {
+		self.record(|| replay::InputEvent::MouseButtonDown { button, x, y });
+
+		let dpi_scale = self.dpi_scale();
+		self.input(|mouse: &mut input::MouseState| {
+			mouse.move_to(vec2(x, y), dpi_scale);
+			mouse.press(button);
+		});
+
 		self.app.world_mut().send_event(events::MouseButtonEvent {
 			position: glam::vec2(x, y),
 			button,
@@ -97,6 +196,14 @@ impl miniquad::EventHandler for QuadifyState {
 	}
 
 	fn mouse_button_up_event(&mut self, button: miniquad::MouseButton, x: f32, y: f32) {
+		self.record(|| replay::InputEvent::MouseButtonUp { button, x, y });
+
+		let dpi_scale = self.dpi_scale();
+		self.input(|mouse: &mut input::MouseState| {
+			mouse.move_to(vec2(x, y), dpi_scale);
+			mouse.release(button);
+		});
+
 		self.app.world_mut().send_event(events::MouseButtonEvent {
 			position: glam::vec2(x, y),
 			button,
@@ -106,6 +213,9 @@ impl miniquad::EventHandler for QuadifyState {
 	}
 
 	fn mouse_motion_event(&mut self, x: f32, y: f32) {
+		self.record(|| replay::InputEvent::MouseMotion { x, y });
+
+		let dpi_scale = self.dpi_scale();
 		let world_mut = self.app.world_mut();
 
 		// x and y are the absolute mouse position, not the delta
@@ -113,23 +223,36 @@ impl miniquad::EventHandler for QuadifyState {
 		let current = vec2(x, y);
 
 		// only send mouse motion events if the mouse has moved and not start of application
-		if current != previous && !first_run {
-			world_mut.send_event(events::MouseMotionEvent { delta: current - previous });
+		let delta = (current != previous && !first_run).then_some(current - previous);
+		if let Some(delta) = delta {
+			world_mut.send_event(events::MouseMotionEvent { delta });
 		}
 
 		// update MousePosition Resource
 		let mut cursor = world_mut.get_resource_mut::<events::CursorProperties>().unwrap();
 		cursor.position = current;
 
-		world_mut.run_schedule(MiniquadPrivilegedSchedule);
+		self.input(|mouse: &mut input::MouseState| {
+			mouse.motion += delta.unwrap_or_default();
+			mouse.move_to(current, dpi_scale);
+		});
+
+		self.app.world_mut().run_schedule(MiniquadPrivilegedSchedule);
 	}
 
 	fn mouse_wheel_event(&mut self, x: f32, y: f32) {
+		self.record(|| replay::InputEvent::MouseWheel { x, y });
+
+		self.input(|mouse: &mut input::MouseState| mouse.wheel += vec2(x, y));
 		self.app.world_mut().send_event(events::MouseWheelEvent { delta: glam::vec2(x, y) });
 	}
 
 	// Touch Events
 	fn touch_event(&mut self, phase: miniquad::TouchPhase, id: u64, x: f32, y: f32) {
+		self.record(|| replay::InputEvent::Touch { phase, id, x, y });
+
+		self.input(|touches: &mut touch::Touches| touches.process(phase, id, vec2(x, y), miniquad::date::now()));
+
 		self.app.world_mut().send_event(events::TouchEvent {
 			phase,
 			id,
@@ -139,30 +262,54 @@ impl miniquad::EventHandler for QuadifyState {
 
 	// Keyboard Events
 	fn char_event(&mut self, character: char, mods: miniquad::KeyMods, repeat: bool) {
-		self.app.world_mut().send_event(events::KeyCharEvent { character, repeat, mods });
+		self.record(|| replay::InputEvent::Char { character, mods, repeat });
+
+		let id = self.app.world_mut().send_event(events::KeyCharEvent { character, repeat, mods });
+		if let Some(id) = id {
+			self.input(|order: &mut text_input::KeystrokeOrder| order.char(id));
+		}
 	}
 
 	fn key_down_event(&mut self, keycode: miniquad::KeyCode, mods: miniquad::KeyMods, repeat: bool) {
-		self.app.world_mut().send_event(events::KeyCodeEvent {
+		self.record(|| replay::InputEvent::KeyDown { keycode, mods, repeat });
+
+		self.input(|keyboard: &mut input::KeyboardState| keyboard.press(keycode));
+
+		let id = self.app.world_mut().send_event(events::KeyCodeEvent {
 			keycode,
 			mods,
 			repeat,
 			released: false,
 		});
+		if let Some(id) = id {
+			self.input(|order: &mut text_input::KeystrokeOrder| order.key(id));
+		}
 		self.app.world_mut().run_schedule(MiniquadPrivilegedSchedule);
 	}
 
 	fn key_up_event(&mut self, keycode: miniquad::KeyCode, mods: miniquad::KeyMods) {
-		self.app.world_mut().send_event(events::KeyCodeEvent {
+		self.record(|| replay::InputEvent::KeyUp { keycode, mods });
+
+		self.input(|keyboard: &mut input::KeyboardState| keyboard.release(keycode));
+
+		let id = self.app.world_mut().send_event(events::KeyCodeEvent {
 			keycode,
 			mods,
 			repeat: false,
 			released: true,
 		});
+		if let Some(id) = id {
+			self.input(|order: &mut text_input::KeystrokeOrder| order.key(id));
+		}
 	}
 
 	// File Drag n' Drop
 	fn files_dropped_event(&mut self, paths: Vec<std::path::PathBuf>, bytes: Option<Vec<Vec<u8>>>) {
+		self.record(|| replay::InputEvent::FilesDropped {
+			paths: paths.clone(),
+			bytes: bytes.clone(),
+		});
+
 		let world = self.app.world_mut();
 		match bytes {
 			Some(s) => {
@@ -183,9 +330,18 @@ impl miniquad::EventHandler for QuadifyState {
 
 	// App Quit
 	fn quit_requested_event(&mut self) -> bool {
+		self.record(|| replay::InputEvent::QuitRequested);
+
 		self.app.world_mut().run_schedule(MiniquadQuitRequestedSchedule);
 
 		// extract results from schedule
-		self.app.world_mut().resource::<QuitRequested>().accept
+		let accept = self.app.world_mut().resource::<QuitRequested>().accept;
+
+		// The event loop stops without another update once the window closes
+		if accept {
+			self.save_recording();
+		}
+
+		accept
 	}
 }
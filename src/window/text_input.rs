@@ -0,0 +1,474 @@
+use std::ops::Range;
+
+use bevy_ecs::{
+	component::Component,
+	event::{EventId, EventReader},
+	system::{Local, Query, Res, ResMut, Resource, SystemParam},
+};
+use miniquad::{KeyCode, KeyMods};
+
+use super::clipboard::{Clipboard, ClipboardState};
+use super::events::{KeyCharEvent, KeyCodeEvent};
+
+/// Maximum number of undo steps kept by a [`TextInput`]
+const HISTORY_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+	text: String,
+	cursor: usize,
+	anchor: Option<usize>,
+}
+
+/// Kind of the last edit, consecutive typing is undone as a single step
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Edit {
+	None,
+	Typing,
+}
+
+/// An editable text buffer, fed from [`KeyCharEvent`]s and [`KeyCodeEvent`]s.
+///
+/// Supports cursor movement, shift selection, word jumps and deletion with ctrl, clipboard shortcuts and undo/redo.
+/// As a component, it's updated automatically while `focused`, and the on-screen keyboard is shown on platforms that have one.
+///
+/// *Note: `miniquad` doesn't report IME composition. Composed text arrives as [`KeyCharEvent`]s once committed, and there's no preedit text to display while composing*
+#[derive(Debug, Clone, Component)]
+pub struct TextInput {
+	/// Only focused inputs receive keyboard events
+	pub focused: bool,
+	/// Allows inserting line breaks with enter, and moving between lines with the arrow keys
+	pub multiline: bool,
+	text: String,
+	/// Byte index of the cursor
+	cursor: usize,
+	/// Byte index where the selection started, the selection spans to the cursor
+	anchor: Option<usize>,
+	undo: Vec<Snapshot>,
+	redo: Vec<Snapshot>,
+	last_edit: Edit,
+}
+
+impl Default for TextInput {
+	fn default() -> Self {
+		Self::new("")
+	}
+}
+
+impl TextInput {
+	/// A focused, single line input, with the cursor at the end of `text`
+	pub fn new(text: impl Into<String>) -> Self {
+		let text = text.into();
+		Self {
+			focused: true,
+			multiline: false,
+			cursor: text.len(),
+			text,
+			anchor: None,
+			undo: Vec::new(),
+			redo: Vec::new(),
+			last_edit: Edit::None,
+		}
+	}
+
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	/// Replaces the whole text, clearing the selection and the undo history
+	pub fn set_text(&mut self, text: impl Into<String>) {
+		self.text = text.into();
+		self.cursor = self.text.len();
+		self.anchor = None;
+		self.undo.clear();
+		self.redo.clear();
+		self.last_edit = Edit::None;
+	}
+
+	/// Byte index of the cursor in [`TextInput::text`]
+	pub fn cursor(&self) -> usize {
+		self.cursor
+	}
+
+	/// Selected byte range, if anything is selected
+	pub fn selection(&self) -> Option<Range<usize>> {
+		match self.anchor {
+			Some(anchor) if anchor != self.cursor => Some(anchor.min(self.cursor)..anchor.max(self.cursor)),
+			_ => None,
+		}
+	}
+
+	pub fn selected_text(&self) -> &str {
+		self.selection().map_or("", |range| &self.text[range])
+	}
+
+	/// Moves the cursor to a byte index, clamped to the text and snapped to a character boundary.
+	/// With `select` the selection is extended to the new position, otherwise it's cleared
+	pub fn set_cursor(&mut self, index: usize, select: bool) {
+		let mut index = index.min(self.text.len());
+		while !self.text.is_char_boundary(index) {
+			index -= 1;
+		}
+
+		if select {
+			self.anchor.get_or_insert(self.cursor);
+		} else {
+			self.anchor = None;
+		}
+
+		self.cursor = index;
+		self.last_edit = Edit::None;
+	}
+
+	pub fn select_all(&mut self) {
+		self.anchor = Some(0);
+		self.cursor = self.text.len();
+		self.last_edit = Edit::None;
+	}
+
+	/// Replaces the selection with `text`, or inserts it at the cursor
+	pub fn insert(&mut self, text: &str) {
+		let text = if self.multiline { text.replace('\r', "") } else { text.replace(['\r', '\n'], " ") };
+		if text.is_empty() && self.selection().is_none() {
+			return;
+		}
+
+		self.snapshot(Edit::None);
+		self.replace_selection(&text);
+	}
+
+	/// Deletes the selected text, returns false if nothing was selected
+	pub fn delete_selection(&mut self) -> bool {
+		if self.selection().is_none() {
+			return false;
+		}
+
+		self.snapshot(Edit::None);
+		self.replace_selection("");
+		true
+	}
+
+	/// Reverts the last edit, returns false if there was nothing to undo
+	pub fn undo(&mut self) -> bool {
+		match self.undo.pop() {
+			Some(snapshot) => {
+				let current = self.restore(snapshot);
+				self.redo.push(current);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Reapplies the last undone edit, returns false if there was nothing to redo
+	pub fn redo(&mut self) -> bool {
+		match self.redo.pop() {
+			Some(snapshot) => {
+				let current = self.restore(snapshot);
+				self.undo.push(current);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Inserts a typed character, returns true if the text changed.
+	/// Control characters and shortcuts (ctrl or logo held) are ignored, they are handled by [`TextInput::handle_key`].
+	/// Characters typed with AltGr, reported as ctrl and alt on Windows, are inserted
+	pub fn handle_char(&mut self, event: &KeyCharEvent) -> bool {
+		let KeyMods { ctrl, alt, logo, .. } = event.mods;
+		if event.character.is_control() || (ctrl && !alt) || logo {
+			return false;
+		}
+
+		// Words are undone one at a time
+		let edit = if event.character.is_whitespace() { Edit::None } else { Edit::Typing };
+		self.snapshot(edit);
+
+		let mut buffer = [0; 4];
+		self.replace_selection(event.character.encode_utf8(&mut buffer));
+		true
+	}
+
+	/// Applies an editing key or shortcut, returns true if the text changed.
+	/// Copy and paste go through `clipboard`
+	pub fn handle_key(&mut self, event: &KeyCodeEvent, clipboard: &mut Clipboard) -> bool {
+		if event.released {
+			return false;
+		}
+
+		let KeyMods { shift, ctrl, logo, .. } = event.mods;
+		let shortcut = ctrl || logo;
+
+		match event.keycode {
+			KeyCode::Left if !shift && !shortcut && self.selection().is_some() => {
+				let start = self.selection().unwrap().start;
+				self.set_cursor(start, false);
+			}
+			KeyCode::Right if !shift && !shortcut && self.selection().is_some() => {
+				let end = self.selection().unwrap().end;
+				self.set_cursor(end, false);
+			}
+			KeyCode::Left => {
+				let index = if shortcut { self.word_start(self.cursor) } else { self.previous_char(self.cursor) };
+				self.set_cursor(index, shift);
+			}
+			KeyCode::Right => {
+				let index = if shortcut { self.word_end(self.cursor) } else { self.next_char(self.cursor) };
+				self.set_cursor(index, shift);
+			}
+			KeyCode::Up if self.multiline => self.set_cursor(self.vertical(-1), shift),
+			KeyCode::Down if self.multiline => self.set_cursor(self.vertical(1), shift),
+			KeyCode::Home => {
+				let index = if shortcut { 0 } else { self.line_start(self.cursor) };
+				self.set_cursor(index, shift);
+			}
+			KeyCode::End => {
+				let index = if shortcut { self.text.len() } else { self.line_end(self.cursor) };
+				self.set_cursor(index, shift);
+			}
+			KeyCode::Backspace => {
+				if self.selection().is_none() {
+					let index = if shortcut { self.word_start(self.cursor) } else { self.previous_char(self.cursor) };
+					if index == self.cursor {
+						return false;
+					}
+					self.anchor = Some(index);
+				}
+				return self.delete_selection();
+			}
+			KeyCode::Delete => {
+				if self.selection().is_none() {
+					let index = if shortcut { self.word_end(self.cursor) } else { self.next_char(self.cursor) };
+					if index == self.cursor {
+						return false;
+					}
+					self.anchor = Some(index);
+				}
+				return self.delete_selection();
+			}
+			KeyCode::Enter | KeyCode::KpEnter if self.multiline => {
+				self.insert("\n");
+				return true;
+			}
+			KeyCode::A if shortcut => self.select_all(),
+			KeyCode::C if shortcut && self.selection().is_some() => clipboard.set(self.selected_text()),
+			KeyCode::X if shortcut && self.selection().is_some() => {
+				clipboard.set(self.selected_text());
+				return self.delete_selection();
+			}
+			KeyCode::V if shortcut => {
+				if let Some(text) = clipboard.get() {
+					self.insert(&text);
+					return true;
+				}
+			}
+			KeyCode::Z if shortcut && shift => return self.redo(),
+			KeyCode::Z if shortcut => return self.undo(),
+			KeyCode::Y if shortcut => return self.redo(),
+			_ => {}
+		}
+
+		false
+	}
+
+	/// Saves the current state before an edit, consecutive typing edits share a single snapshot
+	fn snapshot(&mut self, edit: Edit) {
+		if edit == Edit::None || self.last_edit != edit {
+			self.undo.push(Snapshot {
+				text: self.text.clone(),
+				cursor: self.cursor,
+				anchor: self.anchor,
+			});
+
+			if self.undo.len() > HISTORY_LIMIT {
+				self.undo.remove(0);
+			}
+		}
+
+		self.redo.clear();
+		self.last_edit = edit;
+	}
+
+	fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+		self.last_edit = Edit::None;
+		Snapshot {
+			text: std::mem::replace(&mut self.text, snapshot.text),
+			cursor: std::mem::replace(&mut self.cursor, snapshot.cursor),
+			anchor: std::mem::replace(&mut self.anchor, snapshot.anchor),
+		}
+	}
+
+	fn replace_selection(&mut self, text: &str) {
+		let range = self.selection().unwrap_or(self.cursor..self.cursor);
+		self.text.replace_range(range.clone(), text);
+		self.cursor = range.start + text.len();
+		self.anchor = None;
+	}
+
+	fn previous_char(&self, index: usize) -> usize {
+		self.text[..index].char_indices().next_back().map_or(0, |(i, _)| i)
+	}
+
+	fn next_char(&self, index: usize) -> usize {
+		self.text[index..].chars().next().map_or(index, |c| index + c.len_utf8())
+	}
+
+	/// Start of the word before `index`, skipping any whitespace and punctuation first
+	fn word_start(&self, index: usize) -> usize {
+		let mut chars = self.text[..index].char_indices().rev().skip_while(|(_, c)| !is_word(*c)).peekable();
+		let mut start = chars.peek().map_or(0, |(i, _)| *i);
+
+		for (i, c) in chars {
+			if !is_word(c) {
+				break;
+			}
+			start = i;
+		}
+
+		start
+	}
+
+	/// End of the word after `index`, skipping any whitespace and punctuation first
+	fn word_end(&self, index: usize) -> usize {
+		self.text[index..]
+			.char_indices()
+			.skip_while(|(_, c)| !is_word(*c))
+			.find(|(_, c)| !is_word(*c))
+			.map_or(self.text.len(), |(i, _)| index + i)
+	}
+
+	fn line_start(&self, index: usize) -> usize {
+		self.text[..index].rfind('\n').map_or(0, |i| i + 1)
+	}
+
+	fn line_end(&self, index: usize) -> usize {
+		self.text[index..].find('\n').map_or(self.text.len(), |i| index + i)
+	}
+
+	/// Cursor position on the previous (-1) or next (1) line, keeping the column when possible
+	fn vertical(&self, direction: i32) -> usize {
+		let start = self.line_start(self.cursor);
+		let column = self.text[start..self.cursor].chars().count();
+
+		let line = if direction < 0 {
+			if start == 0 {
+				return 0;
+			}
+			self.line_start(start - 1)
+		} else {
+			let end = self.line_end(self.cursor);
+			if end == self.text.len() {
+				return end;
+			}
+			end + 1
+		};
+
+		let line_end = self.line_end(line);
+		self.text[line..line_end].char_indices().nth(column).map_or(line_end, |(i, _)| line + i)
+	}
+}
+
+fn is_word(c: char) -> bool {
+	c.is_alphanumeric() || c == '_'
+}
+
+/// A keyboard event, as sent by the window
+#[derive(Debug, Clone)]
+pub(crate) enum KeyboardInput {
+	Key(KeyCodeEvent),
+	Char(KeyCharEvent),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Keystroke {
+	Key(EventId<KeyCodeEvent>),
+	Char(EventId<KeyCharEvent>),
+}
+
+/// Order in which the window sent [`KeyCodeEvent`]s and [`KeyCharEvent`]s this frame, which separate event queues lose
+#[derive(Debug, Default, Resource)]
+pub(crate) struct KeystrokeOrder(Vec<Keystroke>);
+
+impl KeystrokeOrder {
+	pub(crate) fn key(&mut self, id: EventId<KeyCodeEvent>) {
+		self.0.push(Keystroke::Key(id));
+	}
+
+	pub(crate) fn char(&mut self, id: EventId<KeyCharEvent>) {
+		self.0.push(Keystroke::Char(id));
+	}
+}
+
+pub(crate) fn clear_keystroke_order(mut order: ResMut<KeystrokeOrder>) {
+	order.0.clear();
+}
+
+/// Reads [`KeyCodeEvent`]s and [`KeyCharEvent`]s in the order they arrived
+#[derive(SystemParam)]
+pub(crate) struct KeyboardInputs<'w, 's> {
+	order: Res<'w, KeystrokeOrder>,
+	keys: EventReader<'w, 's, KeyCodeEvent>,
+	chars: EventReader<'w, 's, KeyCharEvent>,
+}
+
+impl KeyboardInputs<'_, '_> {
+	pub(crate) fn read(&mut self) -> Vec<KeyboardInput> {
+		let mut keys: Vec<_> = self.keys.read_with_id().collect();
+		let mut chars: Vec<_> = self.chars.read_with_id().collect();
+		let mut inputs = Vec::with_capacity(keys.len() + chars.len());
+
+		for keystroke in self.order.0.iter() {
+			match *keystroke {
+				Keystroke::Key(id) => {
+					if let Some(index) = keys.iter().position(|(_, key)| *key == id) {
+						inputs.push(KeyboardInput::Key(keys.remove(index).0.clone()));
+					}
+				}
+				Keystroke::Char(id) => {
+					if let Some(index) = chars.iter().position(|(_, char)| *char == id) {
+						inputs.push(KeyboardInput::Char(chars.remove(index).0.clone()));
+					}
+				}
+			}
+		}
+
+		// Events sent directly to the world, keys come before characters within a single keystroke
+		inputs.extend(keys.into_iter().map(|(key, _)| KeyboardInput::Key(key.clone())));
+		inputs.extend(chars.into_iter().map(|(char, _)| KeyboardInput::Char(char.clone())));
+		inputs
+	}
+}
+
+/// Whether something wants the on-screen keyboard this frame
+#[derive(Debug, Default, Resource)]
+pub(crate) struct OnScreenKeyboard {
+	pub(crate) requested: bool,
+}
+
+/// Shows the on-screen keyboard while a text input is focused, on platforms that have one
+pub(crate) fn show_on_screen_keyboard(mut keyboard: ResMut<OnScreenKeyboard>, mut shown: Local<bool>, clipboard: Res<ClipboardState>) {
+	let requested = std::mem::take(&mut keyboard.requested);
+
+	// Without an event loop there's no window to show it on
+	if clipboard.system && requested != *shown {
+		miniquad::window::show_keyboard(requested);
+		*shown = requested;
+	}
+}
+
+/// Feeds keyboard events into every focused [`TextInput`], in the order they arrived
+pub(crate) fn update_text_inputs(mut events: KeyboardInputs, mut clipboard: Clipboard, mut keyboard: ResMut<OnScreenKeyboard>, mut inputs: Query<&mut TextInput>) {
+	let events = events.read();
+
+	for mut input in inputs.iter_mut().filter(|input| input.focused) {
+		keyboard.requested = true;
+
+		for event in &events {
+			match event {
+				KeyboardInput::Key(key) => input.handle_key(key, &mut clipboard),
+				KeyboardInput::Char(char) => input.handle_char(char),
+			};
+		}
+	}
+}
@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy_ecs::{
+	event::{Event, EventWriter},
+	system::{Local, Res, Resource},
+};
+use glam::Vec2;
+use miniquad::TouchPhase;
+
+/// A finger on the screen. Positions are in physical pixels, times in seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+	pub id: u64,
+	pub start_position: Vec2,
+	pub position: Vec2,
+	/// Position at the end of the previous frame
+	pub previous_position: Vec2,
+	pub start_time: f64,
+	/// Time of the latest update, or of the release for released fingers
+	pub time: f64,
+}
+
+impl Touch {
+	/// Movement since the previous frame
+	pub fn delta(&self) -> Vec2 {
+		self.position - self.previous_position
+	}
+
+	/// Movement since the finger touched the screen
+	pub fn distance(&self) -> Vec2 {
+		self.position - self.start_position
+	}
+
+	/// How long the finger has been, or was, on the screen
+	pub fn duration(&self) -> f64 {
+		self.time - self.start_time
+	}
+}
+
+/// Fingers currently on the screen, fed by [`TouchEvent`](super::events::TouchEvent)s. Released and canceled fingers are kept for a single frame
+#[derive(Debug, Clone, Default, Resource)]
+pub struct Touches {
+	pressed: HashMap<u64, Touch>,
+	just_pressed: HashSet<u64>,
+	just_released: HashMap<u64, Touch>,
+	just_canceled: HashMap<u64, Touch>,
+}
+
+impl Touches {
+	/// Applies a touch event that happened at `time`
+	pub fn process(&mut self, phase: TouchPhase, id: u64, position: Vec2, time: f64) {
+		match phase {
+			TouchPhase::Started => {
+				self.pressed.insert(
+					id,
+					Touch {
+						id,
+						start_position: position,
+						position,
+						previous_position: position,
+						start_time: time,
+						time,
+					},
+				);
+				self.just_pressed.insert(id);
+			}
+			TouchPhase::Moved => {
+				if let Some(touch) = self.pressed.get_mut(&id) {
+					touch.position = position;
+					touch.time = time;
+				}
+			}
+			TouchPhase::Ended | TouchPhase::Cancelled => {
+				if let Some(mut touch) = self.pressed.remove(&id) {
+					touch.position = position;
+					touch.time = time;
+
+					let released = if phase == TouchPhase::Ended { &mut self.just_released } else { &mut self.just_canceled };
+					released.insert(id, touch);
+				}
+			}
+		}
+	}
+
+	/// Moves the time of held fingers forward, so their durations keep growing without events
+	pub fn advance(&mut self, time: f64) {
+		for touch in self.pressed.values_mut() {
+			touch.time = touch.time.max(time);
+		}
+	}
+
+	/// Ends the frame, forgetting released fingers and this frame's movement
+	pub fn clear(&mut self) {
+		self.just_pressed.clear();
+		self.just_released.clear();
+		self.just_canceled.clear();
+
+		for touch in self.pressed.values_mut() {
+			touch.previous_position = touch.position;
+		}
+	}
+
+	pub fn get(&self, id: u64) -> Option<&Touch> {
+		self.pressed.get(&id)
+	}
+
+	/// Fingers currently on the screen, in no particular order
+	pub fn iter(&self) -> impl ExactSizeIterator<Item = &Touch> {
+		self.pressed.values()
+	}
+
+	pub fn len(&self) -> usize {
+		self.pressed.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.pressed.is_empty()
+	}
+
+	pub fn just_pressed(&self, id: u64) -> bool {
+		self.just_pressed.contains(&id)
+	}
+
+	pub fn just_released(&self, id: u64) -> bool {
+		self.just_released.contains_key(&id)
+	}
+
+	pub fn just_canceled(&self, id: u64) -> bool {
+		self.just_canceled.contains_key(&id)
+	}
+
+	pub fn iter_just_pressed(&self) -> impl Iterator<Item = &Touch> {
+		self.just_pressed.iter().filter_map(|id| self.pressed.get(id))
+	}
+
+	pub fn iter_just_released(&self) -> impl Iterator<Item = &Touch> {
+		self.just_released.values()
+	}
+
+	pub fn iter_just_canceled(&self) -> impl Iterator<Item = &Touch> {
+		self.just_canceled.values()
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+	Left,
+	Right,
+	Up,
+	Down,
+}
+
+/// Gestures recognized from [`Touches`], positions are in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Event)]
+pub enum GestureEvent {
+	/// A short touch without movement
+	Tap { position: Vec2 },
+	/// A second tap close to the previous one, sent after its [`GestureEvent::Tap`]
+	DoubleTap { position: Vec2 },
+	/// A finger held in place, sent once per touch
+	LongPress { position: Vec2 },
+	/// A quick flick, sent when the finger is released
+	Swipe { direction: SwipeDirection },
+	/// Two fingers moving apart or together. `scale` is the change of the distance between them this frame
+	Pinch { scale: f32 },
+	/// One finger dragging, or two fingers moving together. `delta` is the movement this frame
+	Pan { delta: Vec2 },
+}
+
+/// Thresholds of gesture recognition, distances are in physical pixels and times in seconds
+#[derive(Debug, Clone, Resource)]
+pub struct GestureSettings {
+	/// Fingers moving less than this are considered still
+	pub slop: f32,
+	pub tap_duration: f64,
+	pub double_tap_interval: f64,
+	pub long_press_duration: f64,
+	pub swipe_distance: f32,
+	pub swipe_duration: f64,
+}
+
+impl Default for GestureSettings {
+	fn default() -> Self {
+		Self {
+			slop: 10.0,
+			tap_duration: 0.3,
+			double_tap_interval: 0.3,
+			long_press_duration: 0.5,
+			swipe_distance: 50.0,
+			swipe_duration: 0.5,
+		}
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct GestureState {
+	/// Time and position of the last tap, waiting for a second one
+	last_tap: Option<(f64, Vec2)>,
+	long_pressed: HashSet<u64>,
+	/// Fingers that moved past the slop, and are dragging instead of tapping
+	dragging: HashSet<u64>,
+}
+
+pub(crate) fn recognize_gestures(touches: Res<Touches>, settings: Res<GestureSettings>, mut state: Local<GestureState>, mut gestures: EventWriter<GestureEvent>) {
+	let state = &mut *state;
+
+	for touch in touches.iter_just_released().chain(touches.iter_just_canceled()) {
+		state.long_pressed.remove(&touch.id);
+		state.dragging.remove(&touch.id);
+	}
+
+	for touch in touches.iter_just_released() {
+		let distance = touch.distance();
+
+		if touch.duration() <= settings.tap_duration && distance.length() < settings.slop {
+			gestures.send(GestureEvent::Tap { position: touch.position });
+
+			match state.last_tap {
+				Some((time, position)) if touch.time - time <= settings.double_tap_interval && position.distance(touch.position) < settings.slop * 2.0 => {
+					gestures.send(GestureEvent::DoubleTap { position: touch.position });
+					state.last_tap = None;
+				}
+				_ => state.last_tap = Some((touch.time, touch.position)),
+			}
+		} else if touch.duration() <= settings.swipe_duration && distance.length() >= settings.swipe_distance {
+			let direction = if distance.x.abs() > distance.y.abs() {
+				if distance.x > 0.0 {
+					SwipeDirection::Right
+				} else {
+					SwipeDirection::Left
+				}
+			} else if distance.y > 0.0 {
+				SwipeDirection::Down
+			} else {
+				SwipeDirection::Up
+			};
+			gestures.send(GestureEvent::Swipe { direction });
+		}
+	}
+
+	for touch in touches.iter() {
+		if touch.distance().length() >= settings.slop {
+			state.dragging.insert(touch.id);
+		} else if touch.duration() >= settings.long_press_duration && !state.dragging.contains(&touch.id) && state.long_pressed.insert(touch.id) {
+			gestures.send(GestureEvent::LongPress { position: touch.position });
+		}
+	}
+
+	match touches.len() {
+		1 => {
+			let touch = touches.iter().next().unwrap();
+			if state.dragging.contains(&touch.id) && touch.delta() != Vec2::ZERO {
+				gestures.send(GestureEvent::Pan { delta: touch.delta() });
+			}
+		}
+		2 => {
+			let mut fingers: Vec<_> = touches.iter().collect();
+			fingers.sort_by_key(|touch| touch.id);
+			let (a, b) = (fingers[0], fingers[1]);
+
+			let previous = a.previous_position.distance(b.previous_position);
+			let current = a.position.distance(b.position);
+			if previous > 0.0 && current != previous {
+				gestures.send(GestureEvent::Pinch { scale: current / previous });
+			}
+
+			let delta = (a.delta() + b.delta()) / 2.0;
+			if delta != Vec2::ZERO {
+				gestures.send(GestureEvent::Pan { delta });
+			}
+		}
+		_ => {}
+	}
+}
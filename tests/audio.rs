@@ -0,0 +1,324 @@
+use bevy_app::prelude::*;
+use std::time::Duration;
+
+use bevy_asset::{Assets, Handle};
+use quadify::prelude::*;
+
+const SAMPLE_RATE: u32 = 4;
+
+fn app() -> App {
+	let mut app = App::new();
+	app.add_plugins((
+		quadify::asset::AssetPlugin::default(),
+		AudioPlugin {
+			output: AudioOutput::Null,
+			sample_rate: SAMPLE_RATE,
+		},
+	));
+	app
+}
+
+fn add_source(app: &mut App, samples: &[f32], sample_rate: u32) -> Handle<AudioSource> {
+	app.world_mut().resource_mut::<Assets<AudioSource>>().add(AudioSource::from_samples(samples.to_vec(), 1, sample_rate))
+}
+
+/// Left channel of `frames` rendered frames
+fn render(app: &App, frames: usize) -> Vec<f32> {
+	let samples = app.world().resource::<NullAudioOutput>().render(frames);
+	samples.chunks_exact(2).map(|frame| frame[0]).collect()
+}
+
+#[test]
+fn wav_decoding() {
+	let data: Vec<u8> = [0i16, 16384, -16384].iter().flat_map(|sample| sample.to_le_bytes()).collect();
+
+	let mut wav = Vec::new();
+	wav.extend(b"RIFF");
+	wav.extend((36 + data.len() as u32).to_le_bytes());
+	wav.extend(b"WAVEfmt ");
+	wav.extend(16u32.to_le_bytes());
+	wav.extend(1u16.to_le_bytes()); // PCM
+	wav.extend(1u16.to_le_bytes()); // Mono
+	wav.extend(8000u32.to_le_bytes());
+	wav.extend(16000u32.to_le_bytes());
+	wav.extend(2u16.to_le_bytes());
+	wav.extend(16u16.to_le_bytes());
+	wav.extend(b"data");
+	wav.extend((data.len() as u32).to_le_bytes());
+	wav.extend(data);
+
+	let source = AudioSource::from_wav(&wav).unwrap();
+	assert_eq!(source.channels(), 1);
+	assert_eq!(source.sample_rate(), 8000);
+	assert_eq!(source.samples(), &[0.0, 0.5, -0.5]);
+
+	assert!(AudioSource::from_wav(b"not a wav file").is_err());
+	assert!(AudioSource::from_ogg(b"not an ogg file").is_err());
+}
+
+#[test]
+fn plays_and_despawns() {
+	let mut app = app();
+	let source = add_source(&mut app, &[1.0; 4], SAMPLE_RATE);
+	let entity = app
+		.world_mut()
+		.spawn((
+			AudioPlayer {
+				volume: 0.5,
+				..AudioPlayer::new(source)
+			},
+			PlaybackSettings::DESPAWN,
+		))
+		.id();
+
+	app.update();
+	let stereo = app.world().resource::<NullAudioOutput>().render(4);
+	assert_eq!(stereo, [0.5; 8]);
+	assert_eq!(render(&app, 2), [0.0; 2]);
+
+	app.update();
+	assert!(app.world().get_entity(entity).is_err());
+}
+
+#[test]
+fn looping() {
+	let mut app = app();
+	let source = add_source(&mut app, &[0.0, 1.0], SAMPLE_RATE);
+	app.world_mut().spawn(AudioPlayer {
+		looping: true,
+		..AudioPlayer::new(source)
+	});
+
+	app.update();
+	assert_eq!(render(&app, 6), [0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+	assert_eq!(app.world().resource::<AudioMixer>().lock().voices(), 1);
+}
+
+#[test]
+fn resampling_and_pitch() {
+	let mut app = app();
+
+	// Half the output's rate, interpolated between source samples
+	let slow = add_source(&mut app, &[0.0, 1.0], SAMPLE_RATE / 2);
+	app.world_mut().spawn(AudioPlayer::new(slow));
+	app.update();
+	assert_eq!(render(&app, 6), [0.0, 0.5, 1.0, 1.0, 0.0, 0.0]);
+
+	// Twice the speed skips every other sample
+	let fast = add_source(&mut app, &[0.0, 0.25, 0.5, 0.75], SAMPLE_RATE);
+	app.world_mut().spawn(AudioPlayer { pitch: 2.0, ..AudioPlayer::new(fast) });
+	app.update();
+	assert_eq!(render(&app, 3), [0.0, 0.5, 0.0]);
+}
+
+#[test]
+fn sink_controls() {
+	let mut app = app();
+	let source = add_source(&mut app, &[0.1, 0.2, 0.3, 0.4], SAMPLE_RATE);
+	let entity = app
+		.world_mut()
+		.spawn((
+			AudioPlayer::new(source),
+			PlaybackSettings {
+				mode: PlaybackMode::Remove,
+				paused: true,
+				..Default::default()
+			},
+		))
+		.id();
+
+	app.update();
+	let sink = app.world().get::<AudioSink>(entity).unwrap();
+	assert!(sink.is_paused());
+	assert_eq!(render(&app, 2), [0.0; 2]);
+
+	sink.resume();
+	assert_eq!(render(&app, 2), [0.1, 0.2]);
+
+	sink.pause();
+	assert_eq!(render(&app, 1), [0.0]);
+
+	sink.resume();
+	sink.set_volume(2.0);
+	assert_eq!(render(&app, 1), [0.6]);
+
+	sink.stop();
+	assert!(sink.empty());
+	assert_eq!(render(&app, 1), [0.0]);
+
+	app.update();
+	assert!(app.world().get::<AudioPlayer>(entity).is_none());
+	assert!(app.world().get::<AudioSink>(entity).is_none());
+}
+
+#[test]
+fn despawning_stops_playback() {
+	let mut app = app();
+	let source = add_source(&mut app, &[1.0; 8], SAMPLE_RATE);
+	let entity = app.world_mut().spawn(AudioPlayer::new(source)).id();
+
+	app.update();
+	assert_eq!(render(&app, 1), [1.0]);
+
+	app.world_mut().despawn(entity);
+	assert_eq!(render(&app, 1), [0.0]);
+	assert_eq!(app.world().resource::<AudioMixer>().lock().voices(), 0);
+}
+
+fn mixer_with(samples: &[f32], settings: VoiceSettings) -> (Mixer, VoiceId) {
+	let mut mixer = Mixer::new(SAMPLE_RATE);
+	let voice = mixer.play(&AudioSource::from_samples(samples.to_vec(), 1, SAMPLE_RATE), settings);
+	(mixer, voice)
+}
+
+fn left(samples: Vec<f32>) -> Vec<f32> {
+	samples.chunks_exact(2).map(|frame| frame[0]).collect()
+}
+
+#[test]
+fn buses() {
+	let source = AudioSource::from_samples(vec![1.0; 4], 1, SAMPLE_RATE);
+	let mut mixer = Mixer::new(SAMPLE_RATE);
+	mixer.play(
+		&source,
+		VoiceSettings {
+			bus: AudioBus::Music,
+			..Default::default()
+		},
+	);
+	mixer.play(
+		&source,
+		VoiceSettings {
+			bus: AudioBus::Sfx,
+			..Default::default()
+		},
+	);
+
+	mixer.bus_mut(AudioBus::Music).volume = 0.5;
+	mixer.bus_mut(AudioBus::Sfx).volume = 0.25;
+	assert_eq!(left(mixer.render(1)), [0.75]);
+
+	// Master applies to every bus
+	mixer.bus_mut(AudioBus::Master).volume = 0.5;
+	assert_eq!(left(mixer.render(1)), [0.375]);
+
+	mixer.bus_mut(AudioBus::Music).muted = true;
+	assert_eq!(left(mixer.render(1)), [0.125]);
+
+	mixer.bus_mut(AudioBus::Master).muted = true;
+	assert_eq!(left(mixer.render(1)), [0.0]);
+	assert_eq!(mixer.voices(), 2);
+}
+
+#[test]
+fn fades() {
+	let (mut mixer, voice) = mixer_with(&[1.0; 16], VoiceSettings::default());
+	mixer.fade_in(voice, Duration::from_secs(1));
+	assert_eq!(left(mixer.render(6)), [0.25, 0.5, 0.75, 1.0, 1.0, 1.0]);
+
+	mixer.fade_out(voice, Duration::from_millis(500));
+	assert_eq!(left(mixer.render(3)), [0.5, 0.0, 0.0]);
+	assert!(!mixer.contains(voice));
+}
+
+#[test]
+fn fade_in_on_spawn() {
+	let mut app = app();
+	let source = add_source(&mut app, &[1.0; 8], SAMPLE_RATE);
+	let entity = app
+		.world_mut()
+		.spawn((
+			AudioPlayer::new(source),
+			PlaybackSettings {
+				fade_in: Some(Duration::from_millis(500)),
+				..PlaybackSettings::DESPAWN
+			},
+		))
+		.id();
+
+	app.update();
+	assert_eq!(render(&app, 3), [0.5, 1.0, 1.0]);
+
+	app.world().get::<AudioSink>(entity).unwrap().fade_out(Duration::from_millis(250));
+	assert_eq!(render(&app, 2), [0.0, 0.0]);
+
+	app.update();
+	assert!(app.world().get_entity(entity).is_err());
+}
+
+#[test]
+fn offline_render_is_deterministic() {
+	let samples: Vec<f32> = (0..32).map(|i| (i as f32 * 0.7).sin()).collect();
+	let settings = VoiceSettings {
+		pitch: 1.3,
+		looping: true,
+		..Default::default()
+	};
+
+	let (mut a, _) = mixer_with(&samples, settings);
+	let (mut b, _) = mixer_with(&samples, settings);
+	assert_eq!(a.render(100), b.render(100));
+	assert_eq!(a.render(7), b.render(7));
+}
+
+#[test]
+fn falloff_curves() {
+	let emitter = |falloff| AudioEmitter2D {
+		falloff,
+		reference_distance: 10.0,
+		max_distance: 50.0,
+	};
+
+	let linear = emitter(Falloff::Linear);
+	assert_eq!(linear.attenuation(5.0), 1.0);
+	assert_eq!(linear.attenuation(30.0), 0.5);
+
+	let inverse = emitter(Falloff::Inverse { rolloff: 1.0 });
+	assert_eq!(inverse.attenuation(10.0), 1.0);
+	assert_eq!(inverse.attenuation(20.0), 0.5);
+
+	let exponential = emitter(Falloff::Exponential { rolloff: 2.0 });
+	assert_eq!(exponential.attenuation(20.0), 0.25);
+
+	// Everything is silent past the max distance
+	for emitter in [linear, inverse, exponential] {
+		assert_eq!(emitter.attenuation(50.0), 0.0);
+	}
+}
+
+#[test]
+fn emitters_follow_the_camera() {
+	let mut app = app();
+	let camera = app.world_mut().spawn(Camera2D::default()).id();
+	app.insert_resource(CurrentCameraTag(camera));
+
+	let source = add_source(&mut app, &[1.0; 16], SAMPLE_RATE);
+	let emitter = app
+		.world_mut()
+		.spawn((
+			AudioPlayer::new(source),
+			AudioEmitter2D {
+				falloff: Falloff::Linear,
+				reference_distance: 10.0,
+				max_distance: 110.0,
+			},
+			Transform2D::from_xy(60.0, 0.0),
+		))
+		.id();
+
+	// Half way to the max distance, on the right
+	app.update();
+	let stereo = app.world().resource::<NullAudioOutput>().render(1);
+	assert_eq!(stereo, [0.0, 0.5]);
+
+	// The camera moved past the emitter, which is now on the left and within the reference distance
+	app.world_mut().get_mut::<Camera2D>(camera).unwrap().target = glam::vec2(65.0, 0.0);
+	app.update();
+	assert_eq!(app.world().resource::<AudioListener2D>().position, glam::vec2(65.0, 0.0));
+	let stereo = app.world().resource::<NullAudioOutput>().render(1);
+	assert_eq!(stereo, [1.0, 0.5]);
+
+	app.world_mut().get_mut::<Transform2D>(emitter).unwrap().translation.x = 500.0;
+	app.update();
+	assert_eq!(app.world().resource::<NullAudioOutput>().render(1), [0.0, 0.0]);
+}
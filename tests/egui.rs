@@ -0,0 +1,46 @@
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use quadify::prelude::*;
+
+#[derive(Resource)]
+struct Settings {
+	volume: f32,
+	name: String,
+	clear_color: [u8; 3],
+}
+
+#[test]
+fn main() {
+	App::new()
+		.add_plugins(QuadifyPlugins.set(WindowPlugin {
+			title: "Egui Integration Test".to_string(),
+			width: 800,
+			height: 600,
+			high_dpi: true,
+			resizeable: true,
+			..Default::default()
+		}))
+		.insert_resource(Settings {
+			volume: 0.5,
+			name: "Player".to_string(),
+			clear_color: [30, 30, 40],
+		})
+		.add_systems(Startup, || println!("TIP: press ESC to quit the test!"))
+		.add_systems(Update, (draw_egui, exit_on_esc))
+		.run();
+}
+
+fn draw_egui(context: Res<EguiContext>, mut settings: ResMut<Settings>, mut clear_color: ResMut<ClearColor>) {
+	egui::Window::new("Settings").show(&context, |ui| {
+		ui.add(egui::Slider::new(&mut settings.volume, 0.0..=1.0).text("Volume"));
+		ui.horizontal(|ui| {
+			ui.label("Name");
+			ui.text_edit_singleline(&mut settings.name);
+		});
+		ui.color_edit_button_srgb(&mut settings.clear_color);
+		ui.label(format!("Hello, {}!", settings.name));
+	});
+
+	let [r, g, b] = settings.clear_color;
+	clear_color.0 = rgba::Rgba::new(r, g, b, 255);
+}
@@ -0,0 +1,150 @@
+use bevy_app::App;
+use bevy_ecs::event::Events;
+use quadify::prelude::*;
+
+fn app() -> (App, MockGamepadBackend) {
+	let backend = MockGamepadBackend::default();
+	let mut app = App::new();
+	app.add_plugins((WindowPlugin::default(), GamepadPlugin::new(backend.clone())));
+	(app, backend)
+}
+
+fn connect(backend: &MockGamepadBackend, gamepad: usize) {
+	backend.push(RawGamepadEvent::Connected {
+		gamepad,
+		name: format!("Mock Pad {}", gamepad),
+	});
+}
+
+fn events<E: bevy_ecs::event::Event + Clone>(app: &App) -> Vec<E> {
+	let events = app.world().resource::<Events<E>>();
+	events.get_cursor().read(events).cloned().collect()
+}
+
+#[test]
+fn connection() {
+	let (mut app, backend) = app();
+	backend.push_frame([
+		RawGamepadEvent::Connected {
+			gamepad: 0,
+			name: "First".to_string(),
+		},
+		RawGamepadEvent::Connected {
+			gamepad: 3,
+			name: "Second".to_string(),
+		},
+	]);
+	app.update();
+
+	let gamepads = app.world().resource::<Gamepads>();
+	assert_eq!(gamepads.len(), 2);
+	assert_eq!(gamepads.get(3).unwrap().name(), "Second");
+	assert_eq!(events::<GamepadConnected>(&app).iter().map(|ev| ev.gamepad).collect::<Vec<_>>(), [0, 3]);
+
+	backend.push(RawGamepadEvent::Disconnected { gamepad: 0 });
+	app.update();
+
+	let gamepads = app.world().resource::<Gamepads>();
+	assert_eq!(gamepads.iter().map(|(id, _)| id).collect::<Vec<_>>(), [3]);
+	assert_eq!(events::<GamepadDisconnected>(&app).len(), 1);
+}
+
+#[test]
+fn buttons() {
+	let (mut app, backend) = app();
+	connect(&backend, 0);
+	backend.push_frame([
+		RawGamepadEvent::Button {
+			gamepad: 0,
+			button: GamepadButton::South,
+			pressed: true,
+		},
+		// Repeated presses are ignored
+		RawGamepadEvent::Button {
+			gamepad: 0,
+			button: GamepadButton::South,
+			pressed: true,
+		},
+		// Input from unknown gamepads too
+		RawGamepadEvent::Button {
+			gamepad: 1,
+			button: GamepadButton::East,
+			pressed: true,
+		},
+	]);
+	app.update();
+	app.update();
+
+	let buttons = app.world().resource::<Gamepads>().get(0).unwrap().buttons();
+	assert!(buttons.pressed(GamepadButton::South) && buttons.just_pressed(GamepadButton::South));
+	assert_eq!(events::<GamepadButtonEvent>(&app).len(), 1);
+
+	app.update();
+	assert!(!app.world().resource::<Gamepads>().get(0).unwrap().buttons().just_pressed(GamepadButton::South));
+
+	// Disconnecting releases held buttons
+	backend.push(RawGamepadEvent::Disconnected { gamepad: 0 });
+	app.update();
+
+	let released = events::<GamepadButtonEvent>(&app);
+	assert_eq!(released.len(), 1);
+	assert!(released[0].released && released[0].button == GamepadButton::South);
+}
+
+#[test]
+fn deadzone() {
+	let settings = GamepadSettings::default();
+	assert_eq!(settings.filter(GamepadAxis::LeftStickX, 0.05), 0.0);
+	assert_eq!(settings.filter(GamepadAxis::LeftStickX, -1.0), -1.0);
+	assert!((settings.filter(GamepadAxis::LeftStickX, 0.55) - 0.5).abs() < 1e-5);
+
+	let (mut app, backend) = app();
+	app.world_mut().resource_mut::<GamepadSettings>().axis_deadzones.insert(GamepadAxis::RightStickY, 0.5);
+	connect(&backend, 0);
+	app.update();
+
+	backend.push_frame([
+		RawGamepadEvent::Axis {
+			gamepad: 0,
+			axis: GamepadAxis::LeftStickX,
+			value: 0.4,
+		},
+		RawGamepadEvent::Axis {
+			gamepad: 0,
+			axis: GamepadAxis::RightStickY,
+			value: 0.4,
+		},
+	]);
+	app.update();
+
+	let gamepad = app.world().resource::<Gamepads>().get(0).unwrap();
+	assert!((gamepad.axis(GamepadAxis::LeftStickX) - 1.0 / 3.0).abs() < 1e-5);
+	assert_eq!(gamepad.axis(GamepadAxis::RightStickY), 0.0);
+
+	// Changes inside the deadzone don't send events
+	let sent = events::<GamepadAxisEvent>(&app);
+	assert_eq!(sent.len(), 1);
+	assert_eq!(sent[0].axis, GamepadAxis::LeftStickX);
+}
+
+#[test]
+fn drives_input_map() {
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	struct Jump;
+
+	let (mut app, backend) = app();
+	let mut map = InputMap::default();
+	map.bind(Jump, Binding::Gamepad(GamepadButton::South));
+	app.add_plugins(InputMapPlugin::<Jump>::default()).insert_resource(map);
+
+	connect(&backend, 0);
+	backend.push(RawGamepadEvent::Button {
+		gamepad: 0,
+		button: GamepadButton::South,
+		pressed: true,
+	});
+	app.update();
+	app.update();
+
+	assert!(app.world().resource::<ActionState<Jump>>().just_pressed(Jump));
+}
@@ -0,0 +1,154 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, AppExit, Update};
+use bevy_asset::Assets;
+use bevy_ecs::{
+	event::EventWriter,
+	system::{Local, NonSend, NonSendMut},
+};
+use glam::{vec2, vec3, Mat4};
+use quadify::prelude::*;
+
+fn app(frames: u32) -> App {
+	let mut app = App::new();
+	app.add_plugins((
+		QuadifyPlugins,
+		HeadlessPlugin {
+			frames,
+			width: 320,
+			height: 240,
+			..Default::default()
+		},
+	));
+	app
+}
+
+fn draw_triangle(mut render_ctx: NonSendMut<RenderingBackend>) {
+	let vertices = [
+		Vertex::new(vec3(-0.5, -0.5, 0.0), vec2(0.0, 0.0), quadify::color::RED),
+		Vertex::new(vec3(0.5, -0.5, 0.0), vec2(1.0, 0.0), quadify::color::RED),
+		Vertex::new(vec3(0.0, 0.5, 0.0), vec2(0.5, 1.0), quadify::color::RED),
+	];
+
+	render_ctx.texture(None);
+	render_ctx.geometry(&vertices, &[0, 1, 2]);
+	render_ctx.draw(Mat4::IDENTITY);
+}
+
+#[test]
+fn runs_frames() {
+	let updates = Arc::new(AtomicU32::new(0));
+	let counter = updates.clone();
+
+	let mut app = app(5);
+	app.insert_resource(ClearColor(quadify::color::BLUE))
+		.add_systems(Update, move || {
+			counter.fetch_add(1, Ordering::Relaxed);
+		})
+		.add_systems(MiniquadDraw, draw_triangle);
+
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+	assert_eq!(app.run(), AppExit::Success);
+
+	assert_eq!(updates.load(Ordering::Relaxed), 5);
+	assert_eq!(record.frames(), 5);
+
+	let draws = record.draw_calls();
+	assert_eq!(draws.len(), 1);
+	assert_eq!(draws[0].num_elements, 3);
+	assert_eq!(draws[0].render_pass, None);
+	assert_eq!(draws[0].viewport, Some((0, 0, 320, 240)));
+
+	let clears = record.clears();
+	assert_eq!(clears.len(), 1);
+	assert_eq!(clears[0].color, quadify::color::BLUE.to_float().into());
+}
+
+#[test]
+fn exits_early() {
+	let mut app = app(100);
+	app.add_systems(Update, |mut frame: Local<u32>, mut exit: EventWriter<AppExit>| {
+		*frame += 1;
+		if *frame == 3 {
+			exit.send(AppExit::from_code(7));
+		}
+	});
+
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+	assert_eq!(app.run(), AppExit::from_code(7));
+	assert_eq!(record.frames(), 3);
+}
+
+#[test]
+fn tracks_objects() {
+	let mut app = app(1);
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+	let (textures, pipelines, shaders) = (record.textures(), record.pipelines(), record.shaders());
+	let mut render_ctx = app.world_mut().non_send_resource_mut::<RenderingBackend>();
+
+	let texture = render_ctx.new_texture_from_rgba8(2, 2, &[255; 16]);
+	assert_eq!(record.textures(), textures + 1);
+
+	render_ctx.texture_update_part(texture, 1, 1, 1, 1, &[1, 2, 3, 4]);
+	let mut pixels = [0; 16];
+	render_ctx.texture_read_pixels(texture, &mut pixels);
+	assert_eq!(pixels[..12], [255; 12]);
+	assert_eq!(pixels[12..], [1, 2, 3, 4]);
+
+	render_ctx.delete_texture(texture);
+	assert_eq!(record.textures(), textures);
+
+	let material = render_ctx
+		.request_material(
+			miniquad::ShaderSource::new("", ""),
+			material::MaterialParams {
+				pipeline_params: Default::default(),
+				uniforms: vec![("Tint".to_string(), miniquad::UniformType::Float4)],
+				textures: vec![],
+			},
+		)
+		.unwrap();
+	assert_eq!((record.pipelines(), record.shaders()), (pipelines + 1, shaders + 1));
+
+	render_ctx.remove_material(material);
+	assert_eq!((record.pipelines(), record.shaders()), (pipelines, shaders));
+}
+
+#[test]
+fn shared_textures() {
+	let mut app = app(4);
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+
+	let texture = app.world_mut().non_send_resource_mut::<RenderingBackend>().new_texture_from_rgba8(1, 1, &[255; 4]);
+	let mut assets = app.world_mut().resource_mut::<Assets<quadify::asset::Texture>>();
+	let mut handles = vec![assets.add(quadify::asset::Texture::new(texture)), assets.add(quadify::asset::Texture::new(texture))];
+
+	// The texture is only freed once both assets are removed. Counted from the second frame, as plugins may create textures on the first one
+	let textures = Arc::new(Mutex::new(0));
+	let counted = textures.clone();
+	app.add_systems(Update, move |mut frame: Local<u32>, record: NonSend<RenderRecord>| {
+		*frame += 1;
+		match *frame {
+			2 => {
+				*counted.lock().unwrap() = record.textures();
+				drop(handles.pop());
+			}
+			3 => {
+				assert_eq!(record.textures(), *counted.lock().unwrap());
+				drop(handles.pop());
+			}
+			_ => {}
+		}
+	});
+
+	app.run();
+	assert_eq!(record.textures(), *textures.lock().unwrap() - 1);
+}
+
+#[test]
+fn window_properties_follow_screen() {
+	let app = app(1);
+	let window = app.world().resource::<WindowProperties>();
+	assert_eq!((window.width, window.height), (320, 240));
+}
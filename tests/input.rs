@@ -0,0 +1,152 @@
+use bevy_app::App;
+use bevy_ecs::event::Events;
+use miniquad::{KeyCode, MouseButton, TouchPhase};
+use quadify::prelude::*;
+
+#[test]
+fn keyboard_state() {
+	let mut keyboard = KeyboardState::default();
+
+	keyboard.press(KeyCode::A);
+	assert!(keyboard.pressed(KeyCode::A));
+	assert!(keyboard.just_pressed(KeyCode::A));
+	assert!(keyboard.any_pressed([KeyCode::B, KeyCode::A]));
+	assert!(!keyboard.any_pressed([KeyCode::B, KeyCode::C]));
+
+	keyboard.press(KeyCode::LeftControl);
+	assert!(keyboard.all_pressed([KeyCode::LeftControl, KeyCode::A]));
+	assert!(!keyboard.all_pressed([KeyCode::LeftControl, KeyCode::B]));
+	assert!(keyboard.any_just_pressed([KeyCode::LeftControl, KeyCode::B]));
+	assert_eq!(keyboard.get_just_pressed().len(), 2);
+	keyboard.release(KeyCode::LeftControl);
+
+	// Next frame, key repeats don't count as new presses
+	keyboard.clear();
+	keyboard.press(KeyCode::A);
+	assert!(keyboard.pressed(KeyCode::A));
+	assert!(!keyboard.just_pressed(KeyCode::A));
+
+	keyboard.release(KeyCode::A);
+	assert!(!keyboard.pressed(KeyCode::A));
+	assert!(keyboard.just_released(KeyCode::A));
+
+	keyboard.clear();
+	assert!(!keyboard.just_released(KeyCode::A));
+	assert_eq!(keyboard.get_pressed().len(), 0);
+}
+
+#[test]
+fn keyboard_release_all() {
+	let mut keyboard = KeyboardState::default();
+	keyboard.press(KeyCode::Left);
+	keyboard.press(KeyCode::Space);
+
+	keyboard.release_all();
+	assert!(!keyboard.any_pressed([KeyCode::Left, KeyCode::Space]));
+	assert!(keyboard.just_released(KeyCode::Left));
+	assert!(keyboard.just_released(KeyCode::Space));
+
+	keyboard.press(KeyCode::Left);
+	keyboard.reset_all();
+	assert!(!keyboard.pressed(KeyCode::Left));
+	assert!(!keyboard.just_pressed(KeyCode::Left));
+}
+
+#[test]
+fn mouse_state() {
+	let mut mouse = MouseState::default();
+
+	mouse.press(MouseButton::Left);
+	mouse.move_to(glam::vec2(200.0, 100.0), 2.0);
+	mouse.motion += glam::vec2(3.0, 4.0);
+	mouse.motion += glam::vec2(1.0, -1.0);
+	mouse.wheel += glam::vec2(0.0, 1.0);
+
+	assert!(mouse.pressed(MouseButton::Left));
+	assert!(mouse.just_pressed(MouseButton::Left));
+	assert_eq!(mouse.position, glam::vec2(200.0, 100.0));
+	assert_eq!(mouse.logical_position, glam::vec2(100.0, 50.0));
+	assert_eq!(mouse.motion, glam::vec2(4.0, 3.0));
+
+	// Motion and scrolling only last a frame, the position and held buttons stay
+	mouse.clear();
+	assert!(mouse.pressed(MouseButton::Left));
+	assert!(!mouse.just_pressed(MouseButton::Left));
+	assert_eq!(mouse.motion, glam::Vec2::ZERO);
+	assert_eq!(mouse.wheel, glam::Vec2::ZERO);
+	assert_eq!(mouse.position, glam::vec2(200.0, 100.0));
+
+	mouse.release(MouseButton::Left);
+	assert!(mouse.just_released(MouseButton::Left));
+}
+
+#[test]
+fn touches() {
+	let mut touches = Touches::default();
+	touches.process(TouchPhase::Started, 1, glam::vec2(10.0, 10.0), 1.0);
+	assert!(touches.just_pressed(1));
+
+	touches.clear();
+	touches.process(TouchPhase::Moved, 1, glam::vec2(15.0, 12.0), 1.1);
+	touches.advance(1.5);
+
+	let touch = touches.get(1).unwrap();
+	assert_eq!(touch.delta(), glam::vec2(5.0, 2.0));
+	assert_eq!(touch.distance(), glam::vec2(5.0, 2.0));
+	assert_eq!(touch.duration(), 0.5);
+	assert!(!touches.just_pressed(1));
+
+	touches.clear();
+	assert_eq!(touches.get(1).unwrap().delta(), glam::Vec2::ZERO);
+
+	touches.process(TouchPhase::Ended, 1, glam::vec2(20.0, 12.0), 2.0);
+	assert!(touches.is_empty());
+	assert!(touches.just_released(1));
+	assert_eq!(touches.iter_just_released().next().unwrap().distance(), glam::vec2(10.0, 2.0));
+
+	touches.clear();
+	assert!(!touches.just_released(1));
+}
+
+fn gestures(app: &mut App, events: &[(TouchPhase, u64, glam::Vec2, f64)]) -> Vec<GestureEvent> {
+	let mut touches = app.world_mut().resource_mut::<Touches>();
+	for &(phase, id, position, time) in events {
+		touches.process(phase, id, position, time);
+	}
+	app.update();
+	app.world_mut().resource_mut::<Touches>().clear();
+
+	app.world_mut().resource_mut::<Events<GestureEvent>>().drain().collect()
+}
+
+#[test]
+fn gesture_recognition() {
+	let mut app = App::new();
+	app.add_plugins(WindowPlugin::default());
+	let at = glam::vec2;
+
+	// Two quick taps in the same spot
+	let tap = gestures(&mut app, &[(TouchPhase::Started, 0, at(100.0, 100.0), 0.0), (TouchPhase::Ended, 0, at(101.0, 100.0), 0.1)]);
+	assert_eq!(tap, [GestureEvent::Tap { position: at(101.0, 100.0) }]);
+
+	let double_tap = gestures(&mut app, &[(TouchPhase::Started, 1, at(100.0, 100.0), 0.2), (TouchPhase::Ended, 1, at(100.0, 100.0), 0.3)]);
+	assert_eq!(double_tap, [GestureEvent::Tap { position: at(100.0, 100.0) }, GestureEvent::DoubleTap { position: at(100.0, 100.0) }]);
+
+	// Holding a finger still
+	assert!(gestures(&mut app, &[(TouchPhase::Started, 2, at(50.0, 50.0), 1.0)]).is_empty());
+	app.world_mut().resource_mut::<Touches>().advance(1.6);
+	assert_eq!(gestures(&mut app, &[]), [GestureEvent::LongPress { position: at(50.0, 50.0) }]);
+	assert!(gestures(&mut app, &[(TouchPhase::Ended, 2, at(50.0, 50.0), 2.0)]).is_empty());
+
+	// A quick flick to the left, panning along the way
+	assert!(gestures(&mut app, &[(TouchPhase::Started, 3, at(300.0, 100.0), 3.0)]).is_empty());
+	let pan = gestures(&mut app, &[(TouchPhase::Moved, 3, at(250.0, 110.0), 3.1)]);
+	assert_eq!(pan, [GestureEvent::Pan { delta: at(-50.0, 10.0) }]);
+	let swipe = gestures(&mut app, &[(TouchPhase::Ended, 3, at(200.0, 110.0), 3.2)]);
+	assert_eq!(swipe, [GestureEvent::Swipe { direction: SwipeDirection::Left }]);
+
+	// Two fingers moving apart
+	gestures(&mut app, &[(TouchPhase::Started, 4, at(100.0, 0.0), 4.0), (TouchPhase::Started, 5, at(200.0, 0.0), 4.0)]);
+	let pinch = gestures(&mut app, &[(TouchPhase::Moved, 4, at(50.0, 0.0), 4.1), (TouchPhase::Moved, 5, at(250.0, 0.0), 4.1)]);
+	assert_eq!(pinch, [GestureEvent::Pinch { scale: 2.0 }]);
+}
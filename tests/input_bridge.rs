@@ -0,0 +1,65 @@
+use bevy_app::prelude::*;
+use bevy_input::{
+	keyboard::{Key, KeyCode, KeyboardInput},
+	mouse::{AccumulatedMouseMotion, MouseButton},
+	touch::Touches,
+	ButtonInput,
+};
+use quadify::prelude::*;
+
+fn app() -> App {
+	let mut app = App::new();
+	app.add_plugins((WindowPlugin::default(), InputBridgePlugin));
+	app
+}
+
+#[test]
+fn keyboard() {
+	let mut app = app();
+	app.world_mut().send_event(KeyCodeEvent {
+		keycode: miniquad::KeyCode::W,
+		mods: miniquad::KeyMods { shift: true, ..Default::default() },
+		repeat: false,
+		released: false,
+	});
+	app.update();
+
+	let keys = app.world().resource::<ButtonInput<KeyCode>>();
+	assert!(keys.just_pressed(KeyCode::KeyW));
+
+	let events = app.world().resource::<bevy_ecs::event::Events<KeyboardInput>>();
+	let event = events.iter_current_update_events().next().unwrap();
+	assert_eq!(event.logical_key, Key::Character("W".into()));
+
+	// Minimizing releases everything
+	app.world_mut().send_event(WindowEvent::Minimized);
+	app.update();
+	let keys = app.world().resource::<ButtonInput<KeyCode>>();
+	assert!(!keys.pressed(KeyCode::KeyW));
+	assert!(keys.just_released(KeyCode::KeyW));
+}
+
+#[test]
+fn mouse_and_touches() {
+	let mut app = app();
+	app.world_mut().send_event(MouseButtonEvent {
+		position: glam::vec2(10.0, 10.0),
+		button: miniquad::MouseButton::Right,
+		released: false,
+	});
+	app.world_mut().send_event(MouseMotionEvent { delta: glam::vec2(2.0, 0.0) });
+	app.world_mut().send_event(MouseMotionEvent { delta: glam::vec2(1.0, 1.0) });
+	app.world_mut().send_event(TouchEvent {
+		phase: miniquad::TouchPhase::Started,
+		id: 7,
+		position: glam::vec2(50.0, 60.0),
+	});
+	app.update();
+
+	assert!(app.world().resource::<ButtonInput<MouseButton>>().just_pressed(MouseButton::Right));
+	assert_eq!(app.world().resource::<AccumulatedMouseMotion>().delta, glam::vec2(3.0, 1.0));
+
+	let touches = app.world().resource::<Touches>();
+	assert!(touches.just_pressed(7));
+	assert_eq!(touches.get_pressed(7).unwrap().position(), glam::vec2(50.0, 60.0));
+}
@@ -0,0 +1,174 @@
+use bevy_app::App;
+use miniquad::{KeyCode, KeyMods, MouseButton, TouchPhase};
+use quadify::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+enum Action {
+	Jump,
+	Fire,
+	Move,
+}
+
+fn input_map() -> InputMap<Action> {
+	let mut map = InputMap::default();
+	map.bind(Action::Jump, Binding::Key(KeyCode::Space))
+		.bind(Action::Jump, Binding::Gamepad(GamepadButton::South))
+		.bind(Action::Fire, Binding::Mouse(MouseButton::Left))
+		.bind(Action::Fire, Binding::Touch(Rect::new(0.0, 0.0, 100.0, 100.0)))
+		.bind_axis(
+			Action::Move,
+			AxisBinding::Keys {
+				negative: KeyCode::A,
+				positive: KeyCode::D,
+			},
+		)
+		.bind_axis(
+			Action::Move,
+			AxisBinding::Gamepad {
+				axis: GamepadAxis::LeftStickX,
+				deadzone: 0.1,
+			},
+		);
+	map
+}
+
+fn app() -> App {
+	let mut app = App::new();
+	app.add_plugins((WindowPlugin::default(), InputMapPlugin::<Action>::default())).insert_resource(input_map());
+	app
+}
+
+fn key(app: &mut App, keycode: KeyCode, released: bool) {
+	app.world_mut().send_event(KeyCodeEvent {
+		keycode,
+		mods: KeyMods::default(),
+		repeat: false,
+		released,
+	});
+}
+
+fn actions(app: &App) -> &ActionState<Action> {
+	app.world().resource::<ActionState<Action>>()
+}
+
+#[test]
+fn buttons() {
+	let mut app = app();
+	key(&mut app, KeyCode::Space, false);
+	app.update();
+	assert!(actions(&app).pressed(Action::Jump));
+	assert!(actions(&app).just_pressed(Action::Jump));
+	assert_eq!(actions(&app).value(Action::Jump), 1.0);
+
+	app.update();
+	assert!(actions(&app).pressed(Action::Jump));
+	assert!(!actions(&app).just_pressed(Action::Jump));
+
+	key(&mut app, KeyCode::Space, true);
+	app.update();
+	assert!(!actions(&app).pressed(Action::Jump));
+	assert!(actions(&app).just_released(Action::Jump));
+
+	// Fingers only press inside their region
+	app.world_mut().send_event(TouchEvent {
+		phase: TouchPhase::Started,
+		id: 0,
+		position: glam::vec2(300.0, 50.0),
+	});
+	app.update();
+	assert!(!actions(&app).pressed(Action::Fire));
+
+	app.world_mut().send_event(TouchEvent {
+		phase: TouchPhase::Moved,
+		id: 0,
+		position: glam::vec2(50.0, 50.0),
+	});
+	app.update();
+	assert!(actions(&app).just_pressed(Action::Fire));
+
+	app.world_mut().send_event(GamepadButtonEvent {
+		gamepad: 0,
+		button: GamepadButton::South,
+		released: false,
+	});
+	app.update();
+	assert!(actions(&app).just_pressed(Action::Jump));
+
+	// Minimizing releases everything
+	app.world_mut().send_event(WindowEvent::Minimized);
+	app.update();
+	assert!(actions(&app).just_released(Action::Jump));
+	assert!(actions(&app).just_released(Action::Fire));
+}
+
+#[test]
+fn axes() {
+	let mut app = app();
+	key(&mut app, KeyCode::A, false);
+	app.update();
+	assert_eq!(actions(&app).value(Action::Move), -1.0);
+	assert!(actions(&app).pressed(Action::Move));
+
+	key(&mut app, KeyCode::D, false);
+	app.update();
+	assert_eq!(actions(&app).value(Action::Move), 0.0);
+	assert!(actions(&app).just_released(Action::Move));
+
+	key(&mut app, KeyCode::A, true);
+	key(&mut app, KeyCode::D, true);
+	app.world_mut().send_event(GamepadAxisEvent {
+		gamepad: 0,
+		axis: GamepadAxis::LeftStickX,
+		value: 0.05,
+	});
+	app.update();
+	assert_eq!(actions(&app).value(Action::Move), 0.0);
+
+	app.world_mut().send_event(GamepadAxisEvent {
+		gamepad: 0,
+		axis: GamepadAxis::LeftStickX,
+		value: 0.3,
+	});
+	app.update();
+	assert_eq!(actions(&app).value(Action::Move), 0.3);
+	assert!(!actions(&app).pressed(Action::Move));
+}
+
+#[test]
+fn rebinding() {
+	let mut app = app();
+	app.world_mut()
+		.resource_mut::<InputMap<Action>>()
+		.clear_action(Action::Jump)
+		.bind(Action::Jump, Binding::Key(KeyCode::W));
+
+	key(&mut app, KeyCode::Space, false);
+	app.update();
+	assert!(!actions(&app).pressed(Action::Jump));
+
+	key(&mut app, KeyCode::W, false);
+	app.update();
+	assert!(actions(&app).pressed(Action::Jump));
+
+	// Unbinding the held key releases the action
+	app.world_mut().resource_mut::<InputMap<Action>>().unbind(Binding::Key(KeyCode::W));
+	app.update();
+	assert!(actions(&app).just_released(Action::Jump));
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn serialization() {
+	let map = input_map();
+
+	let ron = map.to_ron().unwrap();
+	assert!(ron.contains("Key(Space)"));
+	let from_ron = InputMap::<Action>::from_ron(&ron).unwrap();
+	assert_eq!(from_ron.bindings(Action::Jump).collect::<Vec<_>>(), map.bindings(Action::Jump).collect::<Vec<_>>());
+
+	let json = map.to_json().unwrap();
+	let from_json = InputMap::<Action>::from_json(&json).unwrap();
+	assert_eq!(from_json.axis_bindings(Action::Move).collect::<Vec<_>>(), map.axis_bindings(Action::Move).collect::<Vec<_>>());
+	assert_eq!(from_json.bindings(Action::Fire).collect::<Vec<_>>(), map.bindings(Action::Fire).collect::<Vec<_>>());
+}
@@ -0,0 +1,148 @@
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, Update};
+use bevy_asset::Assets;
+use bevy_ecs::{
+	event::{EventReader, EventWriter},
+	system::{Local, NonSend},
+};
+use glam::{vec2, vec3, vec4};
+use miniquad::{FilterMode, TextureFormat};
+use quadify::asset::Texture;
+use quadify::prelude::*;
+
+fn headless(frames: u32) -> App {
+	let mut app = App::new();
+	app.add_plugins((
+		QuadifyPlugins,
+		HeadlessPlugin {
+			frames,
+			width: 8,
+			height: 4,
+			rasterize: true,
+		},
+	));
+	app
+}
+
+#[test]
+fn create_and_delete() {
+	let mut app = headless(1);
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+	let (textures, render_passes) = (record.textures(), record.render_passes());
+
+	let mut render_ctx = app.world_mut().non_send_resource_mut::<RenderingBackend>();
+	let target = RenderTarget::new_texture(&mut render_ctx, 16, 8, true, FilterMode::Nearest);
+	assert_eq!(record.textures(), textures + 2);
+	assert_eq!(record.render_passes(), render_passes + 1);
+	assert!(target.depth_test_enabled());
+	assert_eq!(target.size(&render_ctx), Some((16, 8)));
+
+	let RenderTarget::Texture { colour_texture, .. } = target else {
+		panic!("Expected a texture target");
+	};
+	let params = render_ctx.texture_params(colour_texture);
+	assert_eq!((params.format, params.mag_filter), (TextureFormat::RGBA8, FilterMode::Nearest));
+
+	target.resize(&mut render_ctx, 4, 2);
+	assert_eq!(target.size(&render_ctx), Some((4, 2)));
+	assert_eq!(RenderTarget::Window.size(&render_ctx), None);
+
+	target.delete(&mut render_ctx);
+	assert_eq!(record.textures(), textures);
+	assert_eq!(record.render_passes(), render_passes);
+}
+
+#[test]
+fn follows_window_size() {
+	let mut app = headless(2);
+	let mut render_ctx = app.world_mut().non_send_resource_mut::<RenderingBackend>();
+	let target = RenderTarget::new_texture(&mut render_ctx, 1, 1, false, FilterMode::Nearest);
+	let texture = target.texture().unwrap();
+
+	// Drawn into by the current camera, at half the window's resolution
+	let camera = app.world_mut().spawn((Camera2D::default(), target, ResizeWithWindow { scale: 0.5 })).id();
+	app.insert_resource(CurrentCameraTag(camera)).insert_resource(ClearColor(quadify::color::RED));
+
+	let captures = Arc::new(Mutex::new(Vec::new()));
+	let collected = captures.clone();
+	app.add_systems(
+		Update,
+		move |mut sent: Local<bool>, mut requests: EventWriter<Screenshot>, mut captured: EventReader<ScreenshotCaptured>, render_ctx: NonSend<RenderingBackend>| {
+			if !std::mem::replace(&mut *sent, true) {
+				requests.send(Screenshot::camera(camera));
+			} else {
+				// The texture asset still points at the resized texture
+				assert_eq!(render_ctx.texture_size(texture.id()), (4.0, 2.0));
+			}
+			collected.lock().unwrap().extend(captured.read().map(|capture| capture.image.clone()));
+		},
+	);
+	app.run();
+
+	let captures = captures.lock().unwrap();
+	assert_eq!(captures[0].dimensions(), (4, 2));
+	assert!(captures[0].pixels().all(|pixel| pixel.0 == [230, 41, 56, 255]));
+}
+
+#[test]
+fn borrowed_textures_outlive_their_assets() {
+	let mut app = headless(3);
+	let drawn = Arc::new(Mutex::new(0));
+
+	let mut render_ctx = app.world_mut().non_send_resource_mut::<RenderingBackend>();
+	let target = RenderTarget::new_texture(&mut render_ctx, 4, 4, false, FilterMode::Linear);
+	let handle = app.world_mut().resource_mut::<Assets<Texture>>().add(target.texture().unwrap());
+	let texture = target.texture().unwrap().id();
+
+	// Dropping the asset leaves the render target's texture alone
+	drop(handle);
+	let counter = drawn.clone();
+	app.add_systems(MiniquadDraw, move |render_ctx: NonSend<RenderingBackend>| {
+		assert_eq!(render_ctx.texture_size(texture), (4.0, 4.0));
+		*counter.lock().unwrap() += 1;
+	});
+	app.run();
+	assert_eq!(*drawn.lock().unwrap(), 3);
+}
+
+#[test]
+fn textures_draw_upright() {
+	let mut app = headless(1);
+	let mut render_ctx = app.world_mut().non_send_resource_mut::<RenderingBackend>();
+	let target = RenderTarget::new_texture(&mut render_ctx, 1, 2, false, FilterMode::Nearest);
+	let RenderTarget::Texture { colour_texture, .. } = target else {
+		panic!("Expected a texture target");
+	};
+	// Rows are stored bottom first, like OpenGL draws them: red above blue
+	render_ctx.texture_update(colour_texture, &[0, 0, 255, 255, 255, 0, 0, 255]);
+	let image = render_ctx.new_texture_from_rgba8(1, 2, &[255, 0, 0, 255, 0, 0, 255, 255]);
+
+	let mut textures = app.world_mut().resource_mut::<Assets<Texture>>();
+	let (target_texture, image) = (textures.add(target.texture().unwrap()), textures.add(Texture::new(image)));
+	assert!(textures.get(&target_texture).unwrap().is_upside_down());
+
+	let camera = app.world().resource::<CurrentCameraTag>().0;
+	*app.world_mut().get_mut::<Camera2D>(camera).unwrap() = Camera2D::from_display_rect(vec4(-1.0, -1.0, 2.0, 2.0));
+
+	// The render target on the left, the same picture loaded as an image on the right
+	let sprite = |texture, x| {
+		(
+			Sprite {
+				custom_size: Some(vec2(1.0, 2.0)),
+				..Sprite::new(texture)
+			},
+			Transform2D::from_translation(vec3(x, 0.0, 0.0)),
+		)
+	};
+	app.world_mut().spawn_batch([sprite(target_texture, -0.5), sprite(image, 0.5)]);
+
+	let screen = app.world().non_send_resource::<SoftwareScreen>().clone();
+	app.run();
+
+	let pixels = screen.to_image();
+	for x in [0, 7] {
+		assert_eq!(pixels.get_pixel(x, 0).0, [255, 0, 0, 255]);
+		assert_eq!(pixels.get_pixel(x, 3).0, [0, 0, 255, 255]);
+	}
+}
@@ -0,0 +1,238 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, AppExit, Update};
+use bevy_ecs::{
+	event::EventReader,
+	system::{Query, Res, ResMut},
+};
+use miniquad::{KeyCode, KeyMods, MouseButton, TouchPhase};
+use quadify::prelude::*;
+
+fn headless(frames: u32) -> App {
+	let mut app = App::new();
+	app.add_plugins((QuadifyPlugins, HeadlessPlugin::new(frames)));
+	app
+}
+
+fn key_down(keycode: KeyCode) -> InputEvent {
+	InputEvent::KeyDown {
+		keycode,
+		mods: KeyMods::default(),
+		repeat: false,
+	}
+}
+
+fn key_up(keycode: KeyCode) -> InputEvent {
+	InputEvent::KeyUp { keycode, mods: KeyMods::default() }
+}
+
+fn script() -> InputScript {
+	let mut script = InputScript::new();
+	script
+		.push(0, key_down(KeyCode::A))
+		.push(0, InputEvent::MouseMotion { x: 10.0, y: 20.0 })
+		.push(
+			1,
+			InputEvent::MouseButtonDown {
+				button: MouseButton::Left,
+				x: 10.0,
+				y: 20.0,
+			},
+		)
+		.push(2, key_up(KeyCode::A))
+		.push(
+			2,
+			InputEvent::MouseButtonUp {
+				button: MouseButton::Left,
+				x: 10.0,
+				y: 20.0,
+			},
+		);
+	script
+}
+
+/// Keyboard and mouse state seen by each update
+type Frames = Arc<Mutex<Vec<(bool, bool, bool, glam::Vec2)>>>;
+
+fn observe(app: &mut App) -> Frames {
+	let frames = Frames::default();
+	let observed = frames.clone();
+
+	app.add_systems(Update, move |keyboard: Res<KeyboardState>, mouse: Res<MouseState>| {
+		observed
+			.lock()
+			.unwrap()
+			.push((keyboard.pressed(KeyCode::A), keyboard.just_pressed(KeyCode::A), mouse.pressed(MouseButton::Left), mouse.position));
+	});
+
+	frames
+}
+
+#[test]
+fn injects_events() {
+	let mut app = headless(4);
+	let frames = observe(&mut app);
+
+	let mut injector = InputInjector::new();
+	injector.play(&script());
+	app.insert_resource(injector);
+
+	assert_eq!(app.run(), AppExit::Success);
+
+	let position = glam::vec2(10.0, 20.0);
+	assert_eq!(
+		*frames.lock().unwrap(),
+		[
+			(true, true, false, position),
+			(true, false, true, position),
+			(false, false, false, position),
+			(false, false, false, position)
+		]
+	);
+}
+
+#[test]
+fn records_and_replays() {
+	let recording = Arc::new(Mutex::new(None));
+	let mut app = headless(4);
+	let frames = observe(&mut app);
+
+	let mut injector = InputInjector::new();
+	injector.play(&script());
+
+	let saved = recording.clone();
+	app.insert_resource(injector)
+		.insert_resource(InputRecorder::new())
+		.add_systems(Update, move |recorder: Res<InputRecorder>| {
+			*saved.lock().unwrap() = Some(recorder.script().clone());
+		});
+	app.run();
+
+	// Injected events are recorded on the frame they were injected
+	let recording = recording.lock().unwrap().take().unwrap();
+	assert_eq!(recording, script());
+
+	let mut replay = headless(4);
+	let replayed = observe(&mut replay);
+	let mut injector = InputInjector::new();
+	injector.play(&recording);
+	replay.insert_resource(injector);
+	replay.run();
+
+	assert_eq!(*frames.lock().unwrap(), *replayed.lock().unwrap());
+}
+
+#[test]
+fn window_events() {
+	let dropped = Arc::new(Mutex::new(Vec::new()));
+	let mut app = headless(2);
+
+	let mut injector = InputInjector::new();
+	injector.push(InputEvent::Resize { width: 640.0, height: 480.0 });
+	injector.push(InputEvent::FilesDropped {
+		paths: vec![PathBuf::from("level.ron")],
+		bytes: Some(vec![vec![1, 2, 3]]),
+	});
+	injector.push(InputEvent::Touch {
+		phase: TouchPhase::Started,
+		id: 0,
+		x: 5.0,
+		y: 5.0,
+	});
+
+	let received = dropped.clone();
+	app.insert_resource(injector).add_systems(
+		Update,
+		move |mut files: EventReader<DroppedFileEvent>, properties: Res<WindowProperties>, touches: Res<Touches>, mut injector: ResMut<InputInjector>| {
+			if injector.frame() == 0 {
+				assert_eq!((properties.width, properties.height), (640, 480));
+				assert_eq!(touches.len(), 1);
+				injector.push(InputEvent::Touch {
+					phase: TouchPhase::Ended,
+					id: 0,
+					x: 5.0,
+					y: 5.0,
+				});
+			}
+
+			received.lock().unwrap().extend(files.read().map(|file| (file.path.clone(), file.bytes.clone())));
+		},
+	);
+
+	assert_eq!(app.run(), AppExit::Success);
+	assert_eq!(*dropped.lock().unwrap(), [(PathBuf::from("level.ron"), Some(vec![1, 2, 3]))]);
+}
+
+#[test]
+fn quit_requests() {
+	let updates = Arc::new(Mutex::new(0));
+
+	let counter = updates.clone();
+	let mut app = headless(10);
+	let mut injector = InputInjector::new();
+	injector.push_in(2, InputEvent::QuitRequested);
+	app.insert_resource(injector).add_systems(Update, move || *counter.lock().unwrap() += 1);
+
+	assert_eq!(app.run(), AppExit::Success);
+	assert_eq!(*updates.lock().unwrap(), 3);
+
+	// Refused requests keep the app running
+	let mut app = headless(10);
+	let mut injector = InputInjector::new();
+	injector.push_in(2, InputEvent::QuitRequested);
+	app.insert_resource(injector).insert_resource(QuitRequested { accept: false, status: 0 });
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+
+	assert_eq!(app.run(), AppExit::Success);
+	assert_eq!(record.frames(), 10);
+}
+
+#[test]
+fn text_input_order() {
+	let text = Arc::new(Mutex::new(String::new()));
+	let mut app = headless(1);
+	app.world_mut().spawn(TextInput::default());
+
+	// Typed and deleted within a single frame, text inputs see the events in the same order
+	let mut injector = InputInjector::new();
+	for character in ['a', 'b'] {
+		injector.push(InputEvent::Char {
+			character,
+			mods: KeyMods::default(),
+			repeat: false,
+		});
+	}
+	injector.push(InputEvent::KeyDown {
+		keycode: KeyCode::Backspace,
+		mods: KeyMods::default(),
+		repeat: false,
+	});
+
+	let edited = text.clone();
+	app.insert_resource(injector).add_systems(Update, move |inputs: Query<&TextInput>| {
+		*edited.lock().unwrap() = inputs.single().text().to_string();
+	});
+	app.run();
+
+	assert_eq!(*text.lock().unwrap(), "a");
+}
+
+#[cfg(feature = "serialize")]
+#[test]
+fn serialization() {
+	let script = script();
+	assert_eq!(InputScript::from_ron(&script.to_ron().unwrap()).unwrap(), script);
+	assert_eq!(InputScript::from_json(&script.to_json().unwrap()).unwrap(), script);
+
+	let path = std::env::temp_dir().join(format!("quadify-replay-{}.ron", std::process::id()));
+	let mut app = headless(3);
+	let mut injector = InputInjector::new();
+	injector.play(&script);
+	app.insert_resource(injector).insert_resource(InputRecorder::new().save_on_exit(&path));
+	app.run();
+
+	let saved = InputScript::load(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+	assert_eq!(saved, script);
+}
@@ -0,0 +1,105 @@
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, Update};
+use bevy_ecs::{
+	entity::Entity,
+	event::{EventReader, EventWriter},
+	system::Local,
+};
+use quadify::prelude::*;
+
+fn headless(rasterize: bool) -> App {
+	let mut app = App::new();
+	app.add_plugins((
+		QuadifyPlugins,
+		HeadlessPlugin {
+			frames: 2,
+			width: 4,
+			height: 2,
+			rasterize,
+		},
+	));
+	app
+}
+
+/// Sends `request` on the first frame, and collects the captures
+fn capture(app: &mut App, request: Screenshot) -> Arc<Mutex<Vec<ScreenshotCaptured>>> {
+	let captures = Arc::new(Mutex::new(Vec::new()));
+	let collected = captures.clone();
+
+	app.add_systems(
+		Update,
+		move |mut sent: Local<bool>, mut requests: EventWriter<Screenshot>, mut captured: EventReader<ScreenshotCaptured>| {
+			if !*sent {
+				requests.send(request.clone());
+				*sent = true;
+			}
+			collected.lock().unwrap().extend(captured.read().cloned());
+		},
+	);
+
+	captures
+}
+
+#[test]
+fn window() {
+	let path = std::env::temp_dir().join(format!("quadify-screenshot-{}.png", std::process::id()));
+	let mut app = headless(true);
+	app.insert_resource(ClearColor(quadify::color::GREEN));
+	let captures = capture(&mut app, Screenshot::window().save_to(&path));
+	app.run();
+
+	let captures = captures.lock().unwrap();
+	assert_eq!(captures.len(), 1);
+	assert_eq!(captures[0].camera, None);
+	assert_eq!(captures[0].image.dimensions(), (4, 2));
+	assert!(captures[0].image.pixels().all(|pixel| pixel.0 == [0, 227, 48, 255]));
+
+	let saved = image::open(&path).unwrap().into_rgba8();
+	std::fs::remove_file(&path).unwrap();
+	assert_eq!(saved, captures[0].image);
+}
+
+#[test]
+fn render_target() {
+	let mut app = headless(false);
+
+	let mut render_ctx = app.world_mut().non_send_resource_mut::<RenderingBackend>();
+	#[rustfmt::skip]
+	let texture = render_ctx.new_texture_from_rgba8(2, 2, &[
+		255, 0, 0, 255, 255, 0, 0, 255,
+		0, 0, 255, 255, 0, 0, 255, 255,
+	]);
+	let render_pass = render_ctx.new_render_pass(texture, None);
+	let camera = app
+		.world_mut()
+		.spawn(RenderTarget::Texture {
+			colour_texture: texture,
+			depth: None,
+			render_pass,
+		})
+		.id();
+
+	let captures = capture(&mut app, Screenshot::camera(camera));
+	app.run();
+
+	// The bottom row of the texture comes first in OpenGL
+	let captures = captures.lock().unwrap();
+	assert_eq!(captures[0].camera, Some(camera));
+	assert_eq!(captures[0].image.get_pixel(0, 0).0, [0, 0, 255, 255]);
+	assert_eq!(captures[0].image.get_pixel(1, 1).0, [255, 0, 0, 255]);
+}
+
+#[test]
+fn unreadable() {
+	// The null backend doesn't keep the window's pixels, and entities without render targets can't be captured
+	let mut app = headless(false);
+	let captures = capture(&mut app, Screenshot::window());
+	app.run();
+	assert!(captures.lock().unwrap().is_empty());
+
+	let mut app = headless(true);
+	let captures = capture(&mut app, Screenshot::camera(Entity::PLACEHOLDER));
+	app.run();
+	assert!(captures.lock().unwrap().is_empty());
+}
@@ -0,0 +1,173 @@
+use bevy_app::{App, AppExit};
+use bevy_ecs::system::NonSendMut;
+use glam::{vec2, vec3, Mat4};
+use quadify::prelude::*;
+
+const SIZE: u32 = 8;
+
+/// Pixel coordinates, with the origin on the top left
+fn projection() -> Mat4 {
+	Mat4::orthographic_rh_gl(0.0, SIZE as f32, SIZE as f32, 0.0, -1.0, 1.0)
+}
+
+fn backend() -> (RenderingBackend, SoftwareScreen) {
+	let backend = SoftwareBackend::new(SIZE, SIZE);
+	let screen = backend.screen();
+	(RenderingBackend::software(backend), screen)
+}
+
+fn quad(x: f32, y: f32, w: f32, h: f32, color: rgba::Rgba) -> [Vertex; 4] {
+	[
+		Vertex::new(vec3(x, y, 0.0), vec2(0.0, 0.0), color),
+		Vertex::new(vec3(x + w, y, 0.0), vec2(1.0, 0.0), color),
+		Vertex::new(vec3(x + w, y + h, 0.0), vec2(1.0, 1.0), color),
+		Vertex::new(vec3(x, y + h, 0.0), vec2(0.0, 1.0), color),
+	]
+}
+
+fn pixel(pixels: &[u8], x: u32, y: u32) -> [u8; 4] {
+	let index = ((y * SIZE + x) * 4) as usize;
+	pixels[index..index + 4].try_into().unwrap()
+}
+
+#[test]
+fn fills_triangles() {
+	let (mut render_ctx, screen) = backend();
+	render_ctx.clear(quadify::color::BLUE);
+	render_ctx.texture(None);
+	render_ctx.geometry(&quad(2.0, 2.0, 4.0, 4.0, quadify::color::RED), &[0, 1, 2, 0, 2, 3]);
+	render_ctx.draw(projection());
+	render_ctx.commit_frame();
+
+	let pixels = screen.read_pixels();
+	let (red, blue) = ([230, 41, 56, 255], [0, 120, 242, 255]);
+	for y in 0..SIZE {
+		for x in 0..SIZE {
+			let inside = (2..6).contains(&x) && (2..6).contains(&y);
+			assert_eq!(pixel(&pixels, x, y), if inside { red } else { blue }, "pixel at ({x}, {y})");
+		}
+	}
+}
+
+#[test]
+fn samples_textures() {
+	let (mut render_ctx, screen) = backend();
+	#[rustfmt::skip]
+	let texture = render_ctx.new_texture_from_rgba8(2, 2, &[
+		255, 0, 0, 255, 0, 255, 0, 255,
+		0, 0, 255, 255, 255, 255, 255, 255,
+	]);
+	render_ctx.texture_set_filter(texture, miniquad::FilterMode::Nearest, miniquad::MipmapFilterMode::None);
+
+	render_ctx.clear(quadify::color::BLANK);
+	render_ctx.texture(Some(&texture));
+	render_ctx.geometry(&quad(0.0, 0.0, 8.0, 8.0, quadify::color::WHITE), &[0, 1, 2, 0, 2, 3]);
+	render_ctx.draw(projection());
+	render_ctx.commit_frame();
+
+	// Each texel covering 4 by 4 pixels
+	let pixels = screen.read_pixels();
+	assert_eq!(pixel(&pixels, 0, 0), [255, 0, 0, 255]);
+	assert_eq!(pixel(&pixels, 7, 3), [0, 255, 0, 255]);
+	assert_eq!(pixel(&pixels, 3, 4), [0, 0, 255, 255]);
+	assert_eq!(pixel(&pixels, 4, 7), [255, 255, 255, 255]);
+}
+
+#[test]
+fn blends_and_clips() {
+	let (mut render_ctx, screen) = backend();
+	render_ctx.clear(quadify::color::BLACK);
+	render_ctx.texture(None);
+	render_ctx.scissor(Some((0, 0, 4, 8)));
+	render_ctx.geometry(&quad(0.0, 0.0, 8.0, 8.0, rgba::Rgba::new(255, 255, 255, 128)), &[0, 1, 2, 0, 2, 3]);
+	render_ctx.draw(projection());
+	render_ctx.commit_frame();
+
+	let pixels = screen.read_pixels();
+	assert_eq!(pixel(&pixels, 0, 0), [128, 128, 128, 191]);
+	assert_eq!(pixel(&pixels, 3, 7), [128, 128, 128, 191]);
+	assert_eq!(pixel(&pixels, 4, 0), [0, 0, 0, 255]);
+}
+
+#[test]
+fn draws_lines() {
+	let (mut render_ctx, screen) = backend();
+	render_ctx.clear(quadify::color::BLACK);
+	render_ctx.texture(None);
+	render_ctx.draw_mode(pipeline::DrawMode::Lines);
+	render_ctx.geometry(
+		&[
+			Vertex::new(vec3(0.0, 1.5, 0.0), vec2(0.0, 0.0), quadify::color::WHITE),
+			Vertex::new(vec3(8.0, 1.5, 0.0), vec2(0.0, 0.0), quadify::color::WHITE),
+		],
+		&[0, 1],
+	);
+	render_ctx.draw(projection());
+	render_ctx.commit_frame();
+
+	let pixels = screen.read_pixels();
+	for x in 0..SIZE {
+		assert_eq!(pixel(&pixels, x, 1), [255, 255, 255, 255]);
+		assert_eq!(pixel(&pixels, x, 0), [0, 0, 0, 255]);
+		assert_eq!(pixel(&pixels, x, 2), [0, 0, 0, 255]);
+	}
+}
+
+#[test]
+fn renders_headless_frames() {
+	let mut app = App::new();
+	app.add_plugins((
+		QuadifyPlugins,
+		HeadlessPlugin {
+			width: SIZE,
+			height: SIZE,
+			rasterize: true,
+			..Default::default()
+		},
+	))
+	.insert_resource(ClearColor(quadify::color::GREEN))
+	.add_systems(MiniquadDraw, |mut render_ctx: NonSendMut<RenderingBackend>| {
+		render_ctx.texture(None);
+		render_ctx.geometry(&quad(0.0, 0.0, 4.0, 4.0, quadify::color::WHITE), &[0, 1, 2, 0, 2, 3]);
+		render_ctx.geometry(
+			&[
+				Vertex::new(vec3(4.0, 1.0, 0.0), vec2(0.0, 0.0), quadify::color::RED),
+				Vertex::new(vec3(8.0, 8.0, 0.0), vec2(0.0, 0.0), quadify::color::BLUE),
+				Vertex::new(vec3(1.0, 8.0, 0.0), vec2(0.0, 0.0), quadify::color::YELLOW),
+			],
+			&[0, 1, 2],
+		);
+		render_ctx.draw(projection());
+	});
+
+	let screen = app.world().non_send_resource::<SoftwareScreen>().clone();
+	assert_eq!(app.run(), AppExit::Success);
+
+	let pixels = screen.read_pixels();
+	assert_eq!(pixel(&pixels, 1, 1), [255, 255, 255, 255]);
+	assert_eq!(pixel(&pixels, 7, 1), [0, 227, 48, 255]);
+
+	// Set QUADIFY_BLESS to update the golden image after intended changes
+	let golden_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/headless_frame.png");
+	if std::env::var_os("QUADIFY_BLESS").is_some() {
+		screen.to_image().save(golden_path).unwrap();
+	}
+
+	let golden = image::open(golden_path).unwrap().into_rgba8();
+	assert_eq!(golden, screen.to_image());
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn saves_png() {
+	let (mut render_ctx, screen) = backend();
+	render_ctx.clear(quadify::color::BLUE);
+	render_ctx.commit_frame();
+
+	let path = std::env::temp_dir().join(format!("quadify-software-{}.png", std::process::id()));
+	screen.save_png(&path).unwrap();
+	let saved = image::open(&path).unwrap().into_rgba8();
+	std::fs::remove_file(&path).unwrap();
+
+	assert_eq!(saved, screen.to_image());
+}
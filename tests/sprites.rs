@@ -0,0 +1,183 @@
+use bevy_app::prelude::*;
+use bevy_asset::AssetServer;
+use bevy_ecs::prelude::*;
+use glam::vec2;
+use quadify::asset::AssetPlugin;
+use quadify::prelude::*;
+
+#[derive(Component)]
+struct Spin(f32);
+
+#[test]
+#[ignore = "interactive, needs a window"]
+fn main() {
+	App::new()
+		.add_plugins(
+			QuadifyPlugins
+				.set(WindowPlugin {
+					title: "Sprite Batching Test".to_string(),
+					width: 600,
+					height: 600,
+					high_dpi: false,
+					resizeable: true,
+					..Default::default()
+				})
+				.set(AssetPlugin {
+					file_path: "tests".to_string(),
+					..Default::default()
+				}),
+		)
+		.add_systems(Startup, (setup_camera, spawn_sprites))
+		.add_systems(Update, (spin_sprites, exit_on_esc))
+		.run();
+}
+
+fn setup_camera(current_camera: Res<CurrentCameraTag>, mut cameras: Query<&mut Camera2D>) {
+	// World units are pixels, with (0, 0) at the center of the window
+	let mut camera = cameras.get_mut(current_camera.0).unwrap();
+	*camera = Camera2D::from_display_rect(glam::vec4(-300.0, -300.0, 600.0, 600.0));
+}
+
+fn spawn_sprites(mut commands: Commands, asset_server: Res<AssetServer>) {
+	println!("TIP: press ESC to quit the test!");
+	let texture = asset_server.load("peashooter2.png");
+
+	for i in 0..8 {
+		let x = (i as f32 - 3.5) * 70.0;
+
+		commands.spawn((
+			Sprite {
+				flip_x: i % 2 == 0,
+				custom_size: Some(vec2(64.0, 64.0)),
+				anchor: Anchor::BottomCenter,
+				..Sprite::new(texture.clone())
+			},
+			Transform2D::from_xy(x, 0.0).with_z(i as f32),
+			Spin(0.5 + i as f32 * 0.2),
+		));
+	}
+
+	// Tinted top-left corner of the texture
+	commands.spawn((
+		Sprite {
+			color: rgba::SKYBLUE,
+			rect: Some(Rect::new(0.0, 0.0, 32.0, 32.0)),
+			custom_size: Some(vec2(128.0, 128.0)),
+			..Sprite::new(texture)
+		},
+		Transform2D::from_xy(0.0, -160.0),
+	));
+}
+
+fn spin_sprites(mut sprites: Query<(&mut Transform2D, &Spin)>) {
+	for (mut transform, spin) in sprites.iter_mut() {
+		transform.rotation += spin.0 / 60.0;
+	}
+}
+
+#[cfg(feature = "headless")]
+fn headless(size: u32) -> App {
+	let mut app = App::new();
+	app.add_plugins((
+		QuadifyPlugins,
+		HeadlessPlugin {
+			frames: 1,
+			width: size,
+			height: size,
+			rasterize: true,
+		},
+	));
+
+	// World units are pixels, with (0, 0) at the center of the screen
+	let (camera, half) = (app.world().resource::<CurrentCameraTag>().0, size as f32 / 2.0);
+	*app.world_mut().get_mut::<Camera2D>(camera).unwrap() = Camera2D::from_display_rect(glam::vec4(-half, -half, size as f32, size as f32));
+	app
+}
+
+#[cfg(feature = "headless")]
+fn texture(app: &mut App, width: u16, height: u16, rgba: &[u8]) -> (miniquad::TextureId, bevy_asset::Handle<quadify::asset::Texture>) {
+	let params = miniquad::TextureParams {
+		width: width as u32,
+		height: height as u32,
+		min_filter: miniquad::FilterMode::Nearest,
+		mag_filter: miniquad::FilterMode::Nearest,
+		..Default::default()
+	};
+	let texture = app.world_mut().non_send_resource_mut::<RenderingBackend>().new_texture_from_data_and_format(rgba, params);
+	let handle = app.world_mut().resource_mut::<bevy_asset::Assets<quadify::asset::Texture>>().add(quadify::asset::Texture::new(texture));
+	(texture, handle)
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn batches_by_texture() {
+	let mut app = headless(8);
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+	let (first, first_handle) = texture(&mut app, 1, 1, &[255; 4]);
+	let (second, second_handle) = texture(&mut app, 1, 1, &[255; 4]);
+
+	// Sprites at the same depth are grouped by texture, and consecutive sprites sharing a texture are drawn together
+	for (handle, z) in [(&first_handle, 0.0), (&second_handle, 0.0), (&first_handle, 0.0), (&second_handle, 1.0), (&first_handle, 2.0)] {
+		app.world_mut().spawn((Sprite::new(handle.clone()), Transform2D::default().with_z(z)));
+	}
+	// Hidden sprites aren't drawn
+	app.world_mut().spawn((Sprite::new(first_handle), Transform2D::default(), Visibility::Hidden));
+	app.run();
+
+	let draws: Vec<_> = record.draw_calls().iter().map(|draw| (draw.textures[0], draw.num_elements)).collect();
+	assert_eq!(draws, [(first, 12), (second, 12), (first, 6)]);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn flips_anchors_and_rects() {
+	const RED: [u8; 4] = [255, 0, 0, 255];
+	const GREEN: [u8; 4] = [0, 255, 0, 255];
+	const BLUE: [u8; 4] = [0, 0, 255, 255];
+	const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+	let mut app = headless(8);
+	let (_, texture) = texture(&mut app, 2, 2, &[RED, GREEN, BLUE, WHITE].concat());
+	let sprite = |sprite: Sprite| Sprite {
+		custom_size: Some(vec2(4.0, 4.0)),
+		..sprite
+	};
+
+	// One sprite per quarter of the screen
+	app.world_mut().spawn_batch([
+		(sprite(Sprite::new(texture.clone())), Transform2D::from_xy(-2.0, 2.0)),
+		(
+			sprite(Sprite {
+				flip_x: true,
+				..Sprite::new(texture.clone())
+			}),
+			Transform2D::from_xy(2.0, 2.0),
+		),
+		(
+			sprite(Sprite {
+				flip_y: true,
+				anchor: Anchor::BottomLeft,
+				..Sprite::new(texture.clone())
+			}),
+			Transform2D::from_xy(0.0, -4.0),
+		),
+		// The top-right texel, stretched over the whole sprite
+		(
+			sprite(Sprite {
+				rect: Some(Rect::new(1.0, 0.0, 1.0, 1.0)),
+				..Sprite::new(texture)
+			}),
+			Transform2D::from_xy(-2.0, -2.0),
+		),
+	]);
+
+	let screen = app.world().non_send_resource::<SoftwareScreen>().clone();
+	app.run();
+
+	let pixels = screen.to_image();
+	let quarter = |x: u32, y: u32| [0, 3].map(|dy| [0, 3].map(|dx| pixels.get_pixel(x + dx, y + dy).0));
+	assert_eq!(quarter(0, 0), [[RED, GREEN], [BLUE, WHITE]]);
+	assert_eq!(quarter(4, 0), [[GREEN, RED], [WHITE, BLUE]]);
+	assert_eq!(quarter(4, 4), [[BLUE, WHITE], [RED, GREEN]]);
+	assert_eq!(quarter(0, 4), [[GREEN, GREEN], [GREEN, GREEN]]);
+}
@@ -0,0 +1,139 @@
+use bevy_app::prelude::*;
+use bevy_asset::AssetServer;
+use bevy_ecs::prelude::*;
+use quadify::asset::AssetPlugin;
+use quadify::prelude::*;
+
+#[test]
+#[ignore = "interactive, needs a window"]
+fn main() {
+	App::new()
+		.add_plugins(
+			QuadifyPlugins
+				.set(WindowPlugin {
+					title: "Text Rendering Test".to_string(),
+					width: 600,
+					height: 600,
+					high_dpi: false,
+					resizeable: true,
+					..Default::default()
+				})
+				.set(AssetPlugin {
+					file_path: "tests".to_string(),
+					..Default::default()
+				}),
+		)
+		.add_systems(Startup, (setup_camera, spawn_text))
+		.add_systems(Update, (count_frames, exit_on_esc))
+		.run();
+}
+
+#[derive(Component)]
+struct FrameCounter(u32);
+
+fn setup_camera(current_camera: Res<CurrentCameraTag>, mut cameras: Query<&mut Camera2D>) {
+	let mut camera = cameras.get_mut(current_camera.0).unwrap();
+	*camera = Camera2D::from_display_rect(glam::vec4(-300.0, -300.0, 600.0, 600.0));
+}
+
+fn spawn_text(mut commands: Commands, asset_server: Res<AssetServer>) {
+	println!("TIP: press ESC to quit the test!");
+	let font = asset_server.load("DejaVuSans.ttf");
+
+	commands.spawn((
+		Text2D {
+			font_size: 40.0,
+			alignment: TextAlignment::Center,
+			..Text2D::new("Hello, Quadify!", font.clone())
+		},
+		Transform2D::from_xy(0.0, 260.0),
+	));
+
+	commands.spawn((
+		Text2D {
+			font_size: 18.0,
+			color: rgba::SKYBLUE,
+			max_width: Some(260.0),
+			line_height: 1.2,
+			..Text2D::new("This paragraph is wrapped at 260 units, so long lines break between words.\nNew lines are kept as well.", font.clone())
+		},
+		Transform2D::from_xy(-280.0, 160.0),
+	));
+
+	commands.spawn((
+		Text2D {
+			alignment: TextAlignment::Right,
+			..Text2D::new("Right aligned\nand rotated", font.clone())
+		},
+		Transform2D::from_xy(280.0, -120.0).with_rotation(0.2),
+	));
+
+	commands.spawn((
+		Text2D {
+			font_size: 14.0,
+			..Text2D::new("", font)
+		},
+		Transform2D::from_xy(-280.0, -260.0),
+		FrameCounter(0),
+	));
+}
+
+fn count_frames(mut counters: Query<(&mut Text2D, &mut FrameCounter)>) {
+	for (mut text, mut counter) in counters.iter_mut() {
+		counter.0 += 1;
+		text.text = format!("Frame {}", counter.0);
+	}
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn rasterizes_text() {
+	const SIZE: u32 = 32;
+
+	let mut app = App::new();
+	app.add_plugins((
+		QuadifyPlugins,
+		HeadlessPlugin {
+			frames: 1,
+			width: SIZE,
+			height: SIZE,
+			rasterize: true,
+		},
+	));
+
+	// World units are pixels, with (0, 0) at the center of the screen
+	let camera = app.world().resource::<CurrentCameraTag>().0;
+	*app.world_mut().get_mut::<Camera2D>(camera).unwrap() = Camera2D::from_display_rect(glam::vec4(-16.0, -16.0, 32.0, 32.0));
+
+	let font = app
+		.world_mut()
+		.resource_mut::<bevy_asset::Assets<Font>>()
+		.add(Font::from_bytes(include_bytes!("DejaVuSans.ttf")).unwrap());
+	app.world_mut().spawn((
+		Text2D {
+			font_size: 16.0,
+			color: rgba::YELLOW,
+			..Text2D::new("Hi", font)
+		},
+		Transform2D::from_xy(-12.0, 4.0),
+	));
+
+	let screen = app.world().non_send_resource::<SoftwareScreen>().clone();
+	app.run();
+	let pixels = screen.to_image();
+
+	// Glyphs hang below the transform's translation, the top-left of the line
+	let lit: Vec<_> = pixels.enumerate_pixels().filter(|(_, _, pixel)| pixel.0[..3] != [0, 0, 0]).collect();
+	assert!(!lit.is_empty());
+	assert!(lit.iter().all(|(x, y, _)| *x >= 4 && *y >= 12));
+	assert!(lit.iter().any(|(_, _, pixel)| pixel.0 == [252, 250, 0, 255]));
+
+	// Set QUADIFY_BLESS to update the golden image after intended changes
+	let golden_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/text.png");
+	if std::env::var_os("QUADIFY_BLESS").is_some() {
+		pixels.save(golden_path).unwrap();
+	}
+
+	let golden = image::open(golden_path).unwrap().into_rgba8();
+	assert_eq!(golden, pixels);
+}
@@ -0,0 +1,65 @@
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, Update};
+use bevy_asset::Assets;
+use bevy_ecs::system::NonSend;
+use quadify::asset::Texture;
+use quadify::prelude::*;
+
+fn headless() -> App {
+	let mut app = App::new();
+	app.add_plugins((QuadifyPlugins, HeadlessPlugin::new(1)));
+	app
+}
+
+#[test]
+fn depth_sorted_with_sprites() {
+	let mut app = headless();
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+
+	let sprite_texture = app.world_mut().non_send_resource_mut::<RenderingBackend>().new_texture_from_rgba8(1, 1, &[255; 4]);
+	let texture = app.world_mut().resource_mut::<Assets<Texture>>().add(Texture::new(sprite_texture));
+	let font = app.world_mut().resource_mut::<Assets<Font>>().add(Font::from_bytes(include_bytes!("DejaVuSans.ttf")).unwrap());
+
+	// Text between two sprites splits their batch
+	app.world_mut().spawn((Sprite::new(texture.clone()), Transform2D::from_translation(glam::vec3(0.0, 0.0, 0.0))));
+	app.world_mut().spawn((Text2D::new("A", font), Transform2D::from_translation(glam::vec3(0.0, 0.0, 1.0))));
+	app.world_mut().spawn((Sprite::new(texture), Transform2D::from_translation(glam::vec3(0.0, 0.0, 2.0))));
+	app.run();
+
+	let textures: Vec<_> = record.draw_calls().iter().map(|draw| draw.textures[0] == sprite_texture).collect();
+	assert_eq!(textures, [true, false, true]);
+}
+
+#[test]
+fn glyph_pages_stay_cached() {
+	let mut app = App::new();
+	app.add_plugins((QuadifyPlugins, HeadlessPlugin::new(4)));
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+	let font = app.world_mut().resource_mut::<Assets<Font>>().add(Font::from_bytes(include_bytes!("DejaVuSans.ttf")).unwrap());
+
+	// Too many large glyphs for a single atlas page
+	for size in (120..=300).step_by(20) {
+		let text = Text2D {
+			font_size: size as f32,
+			..Text2D::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ", font.clone())
+		};
+		app.world_mut().spawn((text, Transform2D::default()));
+	}
+
+	// Texture ids drawn each frame, and the number of live textures
+	let frames = Arc::new(Mutex::new(Vec::new()));
+	let collected = frames.clone();
+	app.add_systems(Update, move |record: NonSend<RenderRecord>| {
+		let mut textures: Vec<_> = record.draw_calls().iter().map(|draw| draw.textures[0]).collect();
+		textures.dedup();
+		collected.lock().unwrap().push((textures, record.textures()));
+	});
+	app.run();
+
+	// The first frame draws nothing yet, after that the same pages are drawn every frame
+	let frames = frames.lock().unwrap();
+	assert!(frames[1].0.len() > 1, "Expected several atlas pages, got {:?}", frames[1].0);
+	assert!(frames[2..].iter().all(|frame| *frame == frames[1]), "{:?}", frames);
+	assert_eq!(record.textures(), frames[1].1);
+}
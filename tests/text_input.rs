@@ -0,0 +1,199 @@
+use bevy_app::App;
+use bevy_ecs::system::RunSystemOnce;
+use miniquad::{KeyCode, KeyMods};
+use quadify::prelude::*;
+
+const CTRL: KeyMods = KeyMods {
+	shift: false,
+	ctrl: true,
+	alt: false,
+	logo: false,
+};
+const SHIFT: KeyMods = KeyMods {
+	shift: true,
+	ctrl: false,
+	alt: false,
+	logo: false,
+};
+
+fn app() -> App {
+	let mut app = App::new();
+	app.add_plugins(WindowPlugin::default());
+	app
+}
+
+fn key(app: &mut App, keycode: KeyCode, mods: KeyMods) {
+	app.world_mut().send_event(KeyCodeEvent {
+		keycode,
+		mods,
+		repeat: false,
+		released: false,
+	});
+}
+
+fn type_text(app: &mut App, text: &str) {
+	for character in text.chars() {
+		app.world_mut().send_event(KeyCharEvent {
+			character,
+			mods: KeyMods::default(),
+			repeat: false,
+		});
+	}
+}
+
+fn input(app: &mut App) -> TextInput {
+	app.world_mut().query::<&TextInput>().single(app.world()).clone()
+}
+
+#[test]
+fn typing_and_editing() {
+	let mut app = app();
+	app.world_mut().spawn(TextInput::default());
+
+	type_text(&mut app, "hello world");
+	app.update();
+	assert_eq!(input(&mut app).text(), "hello world");
+
+	key(&mut app, KeyCode::Backspace, KeyMods::default());
+	key(&mut app, KeyCode::Home, KeyMods::default());
+	key(&mut app, KeyCode::Delete, KeyMods::default());
+	app.update();
+	assert_eq!(input(&mut app).text(), "ello worl");
+	assert_eq!(input(&mut app).cursor(), 0);
+
+	// Unfocused inputs are left alone
+	app.world_mut().query::<&mut TextInput>().single_mut(app.world_mut()).focused = false;
+	type_text(&mut app, "ignored");
+	app.update();
+	assert_eq!(input(&mut app).text(), "ello worl");
+}
+
+#[test]
+fn cursor_and_selection() {
+	let mut app = app();
+	app.world_mut().spawn(TextInput::new("one two_2  three"));
+
+	// Word jumps skip over whitespace, `_` and digits are part of words
+	key(&mut app, KeyCode::Left, CTRL);
+	key(&mut app, KeyCode::Left, CTRL);
+	app.update();
+	assert_eq!(input(&mut app).cursor(), 4);
+
+	key(&mut app, KeyCode::Right, KeyMods { shift: true, ..CTRL });
+	app.update();
+	assert_eq!(input(&mut app).selected_text(), "two_2");
+
+	// Typing replaces the selection
+	type_text(&mut app, "2");
+	app.update();
+	key(&mut app, KeyCode::Backspace, CTRL);
+	app.update();
+	assert_eq!(input(&mut app).text(), "one   three");
+
+	key(&mut app, KeyCode::Home, SHIFT);
+	app.update();
+	assert_eq!(input(&mut app).selection(), Some(0..4));
+
+	// Collapses to the start of the selection
+	key(&mut app, KeyCode::Left, KeyMods::default());
+	key(&mut app, KeyCode::Right, KeyMods::default());
+	app.update();
+	assert_eq!((input(&mut app).cursor(), input(&mut app).selection()), (1, None));
+}
+
+#[test]
+fn multiline() {
+	let mut text = TextInput::new("first\nsecond line\nend");
+	text.multiline = true;
+	text.set_cursor(10, false);
+
+	let mut app = app();
+	app.world_mut().spawn(text);
+
+	key(&mut app, KeyCode::Up, KeyMods::default());
+	app.update();
+	assert_eq!(input(&mut app).cursor(), 4);
+
+	key(&mut app, KeyCode::Down, KeyMods::default());
+	key(&mut app, KeyCode::Down, KeyMods::default());
+	app.update();
+	assert_eq!(input(&mut app).cursor(), 21, "clamped to the end of a shorter line");
+
+	key(&mut app, KeyCode::Enter, KeyMods::default());
+	app.update();
+	assert_eq!(input(&mut app).text(), "first\nsecond line\nend\n");
+}
+
+#[test]
+fn undo_redo() {
+	let mut input = TextInput::default();
+	for character in "hello world".chars() {
+		input.handle_char(&KeyCharEvent {
+			character,
+			mods: KeyMods::default(),
+			repeat: false,
+		});
+	}
+
+	input.select_all();
+	input.delete_selection();
+	assert_eq!(input.text(), "");
+
+	assert!(input.undo());
+	assert_eq!(input.text(), "hello world");
+	assert_eq!(input.selected_text(), "hello world");
+
+	// Typing is undone a word at a time
+	assert!(input.undo());
+	assert_eq!(input.text(), "hello ");
+	assert!(input.undo() && input.undo());
+	assert_eq!(input.text(), "");
+	assert!(!input.undo());
+
+	assert!(input.redo());
+	assert_eq!(input.text(), "hello");
+
+	// New edits clear the redo history
+	input.insert("!");
+	assert_eq!(input.text(), "hello!");
+	assert!(!input.redo());
+}
+
+#[test]
+fn clipboard() {
+	let mut app = app();
+	app.world_mut().spawn(TextInput::new("copy me"));
+
+	key(&mut app, KeyCode::A, CTRL);
+	key(&mut app, KeyCode::X, CTRL);
+	app.update();
+	assert_eq!(input(&mut app).text(), "");
+
+	key(&mut app, KeyCode::V, CTRL);
+	key(&mut app, KeyCode::V, CTRL);
+	app.update();
+	assert_eq!(input(&mut app).text(), "copy mecopy me");
+
+	let contents = app.world_mut().run_system_once(|clipboard: Clipboard| clipboard.get()).unwrap();
+	assert_eq!(contents.as_deref(), Some("copy me"));
+
+	// Line breaks are flattened in single line inputs
+	app.world_mut().run_system_once(|mut clipboard: Clipboard| clipboard.set("a\r\nb")).unwrap();
+	key(&mut app, KeyCode::V, CTRL);
+	app.update();
+	assert_eq!(input(&mut app).text(), "copy mecopy mea  b");
+}
+
+#[test]
+fn altgr_characters() {
+	let mut app = app();
+	app.world_mut().spawn(TextInput::default());
+
+	// AltGr is reported as ctrl and alt on Windows
+	let altgr = KeyMods { alt: true, ..CTRL };
+	for (character, mods) in [('@', altgr), ('q', CTRL)] {
+		app.world_mut().send_event(KeyCharEvent { character, mods, repeat: false });
+	}
+	app.update();
+	assert_eq!(input(&mut app).text(), "@");
+}
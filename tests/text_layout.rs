@@ -0,0 +1,110 @@
+use quadify::prelude::*;
+
+const SIZE: f32 = 32.0;
+
+fn font() -> Font {
+	Font::from_bytes(include_bytes!("DejaVuSans.ttf")).unwrap()
+}
+
+fn line_text<'a>(text: &'a str, layout: &TextLayout, line: usize) -> &'a str {
+	let glyphs = &layout.glyphs[layout.lines[line].glyphs.clone()];
+	match (glyphs.first(), glyphs.last()) {
+		(Some(first), Some(last)) => text[first.byte_index..last.byte_index + last.character.len_utf8()].trim_end(),
+		_ => "",
+	}
+}
+
+#[test]
+fn single_line() {
+	let font = font();
+	let layout = measure_text(&font, "Hello", SIZE, None);
+
+	assert_eq!(layout.lines.len(), 1);
+	assert_eq!(layout.glyphs.len(), 5);
+	assert_eq!(layout.glyphs[0].position.x, 0.0);
+	assert!(layout.glyphs.windows(2).all(|pair| pair[0].position.x < pair[1].position.x));
+	assert!(layout.glyphs.iter().all(|glyph| glyph.position.y == font.ascent(SIZE)));
+
+	let last = layout.glyphs.last().unwrap();
+	assert_eq!(layout.size(), glam::vec2(last.position.x + last.advance, font.line_size(SIZE)));
+}
+
+#[test]
+fn kerning() {
+	let font = font();
+	let a = measure_text(&font, "A", SIZE, None);
+	let v = measure_text(&font, "V", SIZE, None);
+	let av = measure_text(&font, "AV", SIZE, None);
+
+	// "AV" is a classic kerning pair, the V tucks under the A
+	assert!(av.size().x < a.size().x + v.size().x);
+	assert!(av.glyphs[1].position.x < a.glyphs[0].advance);
+}
+
+#[test]
+fn line_breaks() {
+	let font = font();
+	let text = "first\r\nsecond\n\nfourth";
+	let layout = measure_text(&font, text, SIZE, None);
+
+	assert_eq!(layout.lines.len(), 4);
+	assert_eq!(line_text(text, &layout, 0), "first");
+	assert_eq!(line_text(text, &layout, 1), "second");
+	assert_eq!(line_text(text, &layout, 2), "");
+	assert_eq!(line_text(text, &layout, 3), "fourth");
+
+	assert_eq!(layout.lines[1].baseline - layout.lines[0].baseline, font.line_size(SIZE));
+	assert_eq!(layout.size().y, font.line_size(SIZE) * 4.0);
+}
+
+#[test]
+fn word_wrap() {
+	let font = font();
+	let text = "the quick brown fox jumps over the lazy dog";
+	let max_width = measure_text(&font, "the quick brown", SIZE, None).size().x + 1.0;
+	let layout = measure_text(&font, text, SIZE, Some(max_width));
+
+	assert!(layout.lines.len() > 1);
+	assert_eq!(line_text(text, &layout, 0), "the quick brown");
+
+	for (index, line) in layout.lines.iter().enumerate() {
+		assert!(line.width <= max_width, "line {} is too wide", index);
+
+		// Every line starts with a whole word
+		let first = &layout.glyphs[line.glyphs.start];
+		assert_eq!(first.position.x, 0.0);
+		assert!(first.byte_index == 0 || text.as_bytes()[first.byte_index - 1] == b' ');
+	}
+
+	let words: Vec<_> = (0..layout.lines.len()).flat_map(|line| line_text(text, &layout, line).split(' ')).collect();
+	assert_eq!(words, text.split(' ').collect::<Vec<_>>());
+}
+
+#[test]
+fn long_words_are_split() {
+	let font = font();
+	let layout = measure_text(&font, "aaaaaaaaaaaaaaaaaaaa", SIZE, Some(SIZE * 2.0));
+
+	assert!(layout.lines.len() > 1);
+	assert!(layout.lines.iter().all(|line| line.width <= SIZE * 2.0 && !line.glyphs.is_empty()));
+	assert_eq!(layout.glyphs.len(), 20);
+}
+
+#[test]
+fn alignment() {
+	let font = font();
+	let text = "short\na longer line";
+
+	let left = TextLayout::new(&font, text, SIZE, 1.0, None, TextAlignment::Left);
+	let center = TextLayout::new(&font, text, SIZE, 1.0, None, TextAlignment::Center);
+	let right = TextLayout::new(&font, text, SIZE, 1.0, None, TextAlignment::Right);
+
+	for line in 0..2 {
+		let width = left.lines[line].width;
+		assert_eq!(center.lines[line].x, -width / 2.0);
+		assert_eq!(right.lines[line].x, -width);
+	}
+
+	assert_eq!(left.size(), center.size());
+	assert_eq!(right.bounds.max.x, 0.0);
+}
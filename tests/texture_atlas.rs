@@ -0,0 +1,34 @@
+use glam::vec2;
+use quadify::prelude::*;
+
+#[test]
+fn grid_layout() {
+	let layout = TextureAtlasLayout::from_grid(vec2(16.0, 16.0), 3, 2, Some(vec2(2.0, 2.0)), Some(vec2(1.0, 1.0)));
+
+	assert_eq!(layout.len(), 6);
+	assert_eq!(layout.get(0), Some(Rect::new(1.0, 1.0, 16.0, 16.0)));
+	// Tiles are ordered row by row
+	assert_eq!(layout.get(2), Some(Rect::new(37.0, 1.0, 16.0, 16.0)));
+	assert_eq!(layout.get(3), Some(Rect::new(1.0, 19.0, 16.0, 16.0)));
+	assert_eq!(layout.get(6), None);
+}
+
+#[test]
+fn animation_frames() {
+	let mut looping = SpriteAnimation::new([4, 5, 6], 0.1);
+	assert_eq!(looping.frame(), 4);
+
+	looping.tick(0.25);
+	assert_eq!(looping.frame(), 6);
+	looping.tick(0.1);
+	assert_eq!(looping.frame(), 4);
+
+	let mut once = SpriteAnimation::new(0..3, 0.1).with_mode(AnimationMode::Once);
+	once.tick(1.0);
+	assert_eq!(once.frame(), 2);
+	assert!(once.finished());
+
+	once.restart();
+	assert_eq!(once.frame(), 0);
+	assert!(!once.finished());
+}
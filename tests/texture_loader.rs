@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, PluginGroup, Update};
+use bevy_asset::{AssetServer, Assets, Handle, LoadState};
+use bevy_ecs::system::{Local, NonSend, Res};
+use quadify::asset::{AssetPlugin, Texture};
+use quadify::prelude::*;
+
+/// How a texture finished loading
+#[derive(Debug)]
+enum Loaded {
+	/// Frame it was loaded on, and the size of its GPU texture
+	Texture(u32, (f32, f32)),
+	Failed(String),
+}
+
+/// Runs a few headless frames loading textures from `folder`, and reports how each one went along with the number of live textures
+fn load(folder: &str, paths: &[&str]) -> (Vec<Option<Loaded>>, usize) {
+	let mut app = App::new();
+	app.add_plugins((
+		QuadifyPlugins.set(AssetPlugin {
+			file_path: folder.to_string(),
+			..Default::default()
+		}),
+		HeadlessPlugin::new(20),
+	));
+	let record = app.world().non_send_resource::<RenderRecord>().clone();
+
+	let handles: Vec<Handle<Texture>> = paths.iter().map(|path| app.world().resource::<AssetServer>().load(*path)).collect();
+	let results = Arc::new(Mutex::new(handles.iter().map(|_| None).collect::<Vec<_>>()));
+	let collected = results.clone();
+	app.add_systems(
+		Update,
+		move |mut frame: Local<u32>, asset_server: Res<AssetServer>, assets: Res<Assets<Texture>>, render_ctx: NonSend<RenderingBackend>| {
+			*frame += 1;
+			for (handle, result) in handles.iter().zip(collected.lock().unwrap().iter_mut()) {
+				if result.is_some() {
+					continue;
+				}
+
+				match asset_server.load_state(handle) {
+					LoadState::Loaded => *result = Some(Loaded::Texture(*frame, render_ctx.texture_size(assets.get(handle).unwrap().id()))),
+					LoadState::Failed(err) => *result = Some(Loaded::Failed(err.to_string())),
+					_ => {}
+				}
+			}
+		},
+	);
+	app.run();
+
+	let results = std::mem::take(&mut *results.lock().unwrap());
+	(results, record.textures())
+}
+
+/// A fresh assets folder, for images written by the test
+fn temp_folder(name: &str) -> PathBuf {
+	let folder = std::env::temp_dir().join(format!("quadify_{}_{}", name, std::process::id()));
+	std::fs::create_dir_all(&folder).unwrap();
+	folder
+}
+
+#[test]
+fn loads_textures() {
+	let folder = concat!(env!("CARGO_MANIFEST_DIR"), "/tests");
+	let (results, textures) = load(folder, &["peashooter2.png"]);
+	let (width, height) = image::image_dimensions("tests/peashooter2.png").unwrap();
+
+	// Resolves once the GPU texture exists, a couple of frames after the image is decoded
+	let Some(Loaded::Texture(frame, size)) = results[0] else {
+		panic!("Expected a loaded texture, got {:?}", results[0]);
+	};
+	assert!(frame < 20);
+	assert_eq!(size, (width as f32, height as f32));
+	// Other plugins may create textures of their own
+	assert_eq!(textures, load(folder, &[]).1 + 1);
+}
+
+#[test]
+fn loader_errors() {
+	let folder = temp_folder("loader_errors");
+	image::GrayImage::new(65536, 1).save(folder.join("wide.png")).unwrap();
+	std::fs::copy("tests/peashooter2.png", folder.join("peashooter.img")).unwrap();
+	std::fs::write(folder.join("garbage.img"), b"not an image").unwrap();
+
+	let (results, textures) = load(folder.to_str().unwrap(), &["wide.png", "peashooter.img", "garbage.img"]);
+	let (_, empty) = load(folder.to_str().unwrap(), &[]);
+	std::fs::remove_dir_all(folder).unwrap();
+
+	assert!(matches!(&results[0], Some(Loaded::Failed(err)) if err.contains("65536x1 pixels is too large")), "{:?}", results[0]);
+	// Unknown extensions go to the texture loader too, which guesses the format from the bytes
+	assert!(matches!(results[1], Some(Loaded::Texture(..))), "{:?}", results[1]);
+	assert!(matches!(&results[2], Some(Loaded::Failed(err)) if err.contains("Failed to decode image")), "{:?}", results[2]);
+	assert_eq!(textures, empty + 1);
+}
@@ -0,0 +1,71 @@
+use bevy_asset::Assets;
+use glam::uvec2;
+use quadify::asset::Texture;
+use quadify::prelude::*;
+
+#[test]
+fn shelf_packing() {
+	let mut packer = ShelfPacker::new(64, 64);
+
+	assert_eq!(packer.pack(32, 16), Some(uvec2(0, 0)));
+	assert_eq!(packer.pack(32, 16), Some(uvec2(32, 0)));
+	// First shelf is full, so a new one is opened below
+	assert_eq!(packer.pack(16, 8), Some(uvec2(0, 16)));
+	// Shorter rectangles fit on the shelf that wastes the least space
+	assert_eq!(packer.pack(8, 8), Some(uvec2(16, 16)));
+
+	assert_eq!(packer.pack(65, 1), None);
+	assert_eq!(packer.pack(64, 48), None);
+
+	packer.grow(64, 128);
+	assert_eq!(packer.pack(64, 48), Some(uvec2(0, 24)));
+}
+
+#[test]
+fn atlas_growth_and_pages() {
+	let textures = Assets::<Texture>::default();
+	let mut atlas = DynamicTextureAtlas::new(32, 64);
+	let image = vec![255; 20 * 20 * 4];
+
+	let first = atlas.add(&textures, 20, 20, &image).unwrap();
+	assert_eq!(first.page, 0);
+	assert_eq!(first.rect, Rect::new(0.0, 0.0, 20.0, 20.0));
+
+	// The page grows to fit more images, keeping previous ones in place
+	let second = atlas.add(&textures, 20, 20, &image).unwrap();
+	assert_eq!(second.page, 0);
+	assert_eq!(atlas.page_size(0), Some(uvec2(64, 64)));
+
+	// 3x3 padded images fit into a 64x64 page
+	for _ in 0..7 {
+		assert_eq!(atlas.add(&textures, 20, 20, &image).unwrap().page, 0);
+	}
+
+	// Maximum size reached, so a new page is made
+	assert_eq!(atlas.add(&textures, 20, 20, &image).unwrap().page, 1);
+	assert_eq!(atlas.pages(), 2);
+
+	assert!(atlas.add(&textures, 64, 64, &vec![0; 64 * 64 * 4]).is_none());
+}
+
+#[test]
+fn atlas_rounds_max_size() {
+	let textures = Assets::<Texture>::default();
+	let mut atlas = DynamicTextureAtlas::new(48, 160);
+	let image = vec![255; 40 * 40 * 4];
+
+	// Pages double from 48 to 96, as 192 would be over the limit
+	for _ in 0..4 {
+		assert_eq!(atlas.add(&textures, 40, 40, &image).unwrap().page, 0);
+	}
+	assert_eq!(atlas.page_size(0), Some(uvec2(96, 96)));
+	assert_eq!(atlas.add(&textures, 40, 40, &image).unwrap().page, 1);
+
+	assert!(atlas.add(&textures, 100, 100, &vec![0; 100 * 100 * 4]).is_none());
+}
+
+#[test]
+#[should_panic]
+fn atlas_pages_fit_textures() {
+	DynamicTextureAtlas::new(256, 65536);
+}
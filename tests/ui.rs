@@ -0,0 +1,222 @@
+use bevy_app::prelude::*;
+use bevy_asset::AssetServer;
+use bevy_ecs::prelude::*;
+use glam::vec2;
+use quadify::asset::AssetPlugin;
+use quadify::prelude::*;
+
+#[derive(Resource)]
+struct Settings {
+	volume: f32,
+	fullscreen: bool,
+	name: String,
+	clicks: u32,
+}
+
+#[test]
+#[ignore = "interactive, needs a window"]
+fn main() {
+	App::new()
+		.add_plugins(
+			QuadifyPlugins
+				.set(WindowPlugin {
+					title: "Immediate Mode UI Test".to_string(),
+					width: 800,
+					height: 600,
+					high_dpi: false,
+					resizeable: true,
+					..Default::default()
+				})
+				.set(AssetPlugin {
+					file_path: "tests".to_string(),
+					..Default::default()
+				}),
+		)
+		.insert_resource(Settings {
+			volume: 0.5,
+			fullscreen: false,
+			name: "Player".to_string(),
+			clicks: 0,
+		})
+		.add_systems(Startup, load_font)
+		.add_systems(Update, (draw_ui, exit_on_esc))
+		.run();
+}
+
+fn load_font(asset_server: Res<AssetServer>, mut style: ResMut<UiStyle>) {
+	println!("TIP: press ESC to quit the test!");
+	style.font = asset_server.load("DejaVuSans.ttf");
+}
+
+fn draw_ui(mut ui: Ui, mut settings: ResMut<Settings>) {
+	ui.label("Widgets outside of windows are stacked from the top-left corner");
+	if ui.button("Click me") {
+		settings.clicks += 1;
+	}
+	ui.label(&format!("Clicked {} times", settings.clicks));
+
+	let settings = settings.as_mut();
+	ui.window("Settings", vec2(300.0, 100.0), vec2(320.0, 260.0), |ui| {
+		ui.slider("Volume", &mut settings.volume, 0.0..=1.0);
+		ui.checkbox("Fullscreen", &mut settings.fullscreen);
+		ui.text_input("Name", &mut settings.name);
+
+		ui.scroll_area("Log", 100.0, |ui| {
+			for i in 0..20 {
+				ui.label(&format!("Log line {}", i));
+			}
+		});
+	});
+
+	ui.window("Second Window", vec2(100.0, 300.0), vec2(200.0, 120.0), |ui| {
+		ui.label(&format!("Hello, {}!", settings.name));
+		if ui.button("Reset##volume") {
+			settings.volume = 0.5;
+		}
+	});
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn text_input_widget() {
+	let name = std::sync::Arc::new(std::sync::Mutex::new(String::from("Player")));
+	let mut app = App::new();
+	app.add_plugins((QuadifyPlugins, HeadlessPlugin::new(3)));
+
+	// Click on the field, then select the last word and type over it in a single frame
+	let mut injector = InputInjector::new();
+	injector.push(InputEvent::MouseMotion { x: 20.0, y: 20.0 });
+	injector.push(InputEvent::MouseButtonDown {
+		button: miniquad::MouseButton::Left,
+		x: 20.0,
+		y: 20.0,
+	});
+	injector.push_in(
+		1,
+		InputEvent::MouseButtonUp {
+			button: miniquad::MouseButton::Left,
+			x: 20.0,
+			y: 20.0,
+		},
+	);
+	let ctrl_shift = miniquad::KeyMods {
+		shift: true,
+		ctrl: true,
+		..Default::default()
+	};
+	injector.push_in(
+		1,
+		InputEvent::KeyDown {
+			keycode: miniquad::KeyCode::Left,
+			mods: ctrl_shift,
+			repeat: false,
+		},
+	);
+	for character in "Bob".chars() {
+		injector.push_in(
+			1,
+			InputEvent::Char {
+				character,
+				mods: Default::default(),
+				repeat: false,
+			},
+		);
+	}
+
+	let edited = name.clone();
+	app.insert_resource(injector).add_systems(Update, move |mut ui: Ui| {
+		ui.text_input("Name", &mut edited.lock().unwrap());
+	});
+	app.run();
+
+	assert_eq!(*name.lock().unwrap(), "Bob");
+}
+
+/// Presses the left mouse button at `(x, y)` in `frames` frames
+#[cfg(feature = "headless")]
+fn press(injector: &mut InputInjector, frames: u64, x: f32, y: f32) {
+	injector.push_in(frames, InputEvent::MouseMotion { x, y });
+	injector.push_in(
+		frames,
+		InputEvent::MouseButtonDown {
+			button: miniquad::MouseButton::Left,
+			x,
+			y,
+		},
+	);
+}
+
+/// Releases the left mouse button at `(x, y)` in `frames` frames
+#[cfg(feature = "headless")]
+fn release(injector: &mut InputInjector, frames: u64, x: f32, y: f32) {
+	injector.push_in(frames, InputEvent::MouseMotion { x, y });
+	injector.push_in(
+		frames,
+		InputEvent::MouseButtonUp {
+			button: miniquad::MouseButton::Left,
+			x,
+			y,
+		},
+	);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn button_clicks() {
+	let clicks = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+	let mut app = App::new();
+	app.add_plugins((QuadifyPlugins, HeadlessPlugin::new(6)));
+
+	// A click on the button, then a press on it that's released elsewhere
+	let mut injector = InputInjector::new();
+	press(&mut injector, 0, 20.0, 15.0);
+	release(&mut injector, 1, 20.0, 15.0);
+	press(&mut injector, 2, 20.0, 15.0);
+	release(&mut injector, 3, 300.0, 200.0);
+
+	let counted = clicks.clone();
+	app.insert_resource(injector).add_systems(Update, move |mut ui: Ui| {
+		if ui.button("Click me") {
+			counted.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+		}
+	});
+	app.run();
+
+	assert_eq!(clicks.load(std::sync::atomic::Ordering::Relaxed), 1);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn slider_drag() {
+	let values = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+	let mut app = App::new();
+	app.add_plugins((
+		QuadifyPlugins,
+		HeadlessPlugin {
+			frames: 5,
+			width: 400,
+			height: 300,
+			..Default::default()
+		},
+	));
+
+	// Without a font, "Volume: 0.50" is 96 units wide, so the track spans from 4 to 296
+	let mut injector = InputInjector::new();
+	press(&mut injector, 0, 4.0 + 292.0 * 0.25, 10.0);
+	injector.push_in(1, InputEvent::MouseMotion { x: 4.0 + 292.0 * 0.75, y: 10.0 });
+	release(&mut injector, 2, 4.0 + 292.0 * 0.75, 10.0);
+	injector.push_in(3, InputEvent::MouseMotion { x: 10.0, y: 10.0 });
+
+	let collected = values.clone();
+	let mut volume = 0.5;
+	app.insert_resource(injector).add_systems(Update, move |mut ui: Ui| {
+		ui.slider("Volume", &mut volume, 0.0..=1.0);
+		collected.lock().unwrap().push(volume);
+	});
+	app.run();
+
+	// Follows the mouse while held, and stays put after the release
+	let values = values.lock().unwrap();
+	let expected = [0.25, 0.75, 0.75, 0.75, 0.75];
+	assert!(values.iter().zip(expected).all(|(value, expected)| (value - expected).abs() < 1e-4), "{:?}", values);
+}